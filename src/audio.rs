@@ -0,0 +1,224 @@
+use std::mem::ManuallyDrop;
+
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use rodio::{OutputStream, OutputStreamBuilder, Sink};
+
+pub const SYSTEM_DEFAULT_DEVICE: &str = "System default";
+const OUTPUT_CHANNELS: u16 = 2;
+const OUTPUT_SAMPLE_RATE: u32 = 44100;
+
+/// Output settings handed to each decoder thread when playback starts.
+#[derive(Debug, Clone)]
+pub struct AudioConfig {
+    pub device: Option<String>,
+    pub gain: f32,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            device: None,
+            gain: 1.0,
+        }
+    }
+}
+
+pub fn output_device_names() -> Vec<String> {
+    rodio::cpal::default_host()
+        .output_devices()
+        .map(|devices| devices.filter_map(|d| d.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Opens the named output device, falling back to the default one when no
+/// device is named or the named one is gone (e.g. an unplugged HDMI sink).
+pub fn create_audio_output(
+    device_name: Option<&str>,
+) -> Option<(ManuallyDrop<OutputStream>, Sink)> {
+    let stream = device_name
+        .and_then(open_named_stream)
+        .or_else(|| OutputStreamBuilder::open_default_stream().ok())?;
+    let sink = Sink::connect_new(stream.mixer());
+    Some((ManuallyDrop::new(stream), sink))
+}
+
+fn open_named_stream(name: &str) -> Option<OutputStream> {
+    let device = rodio::cpal::default_host()
+        .output_devices()
+        .ok()?
+        .find(|d| d.name().is_ok_and(|n| n == name))?;
+    OutputStreamBuilder::from_device(device)
+        .ok()?
+        .open_stream()
+        .ok()
+}
+
+/// Builds a resampler that downmixes whatever the source carries (mono,
+/// 5.1, ...) to packed 16-bit stereo. Streams that don't report a layout get
+/// the default one for their channel count.
+pub fn create_resampler(
+    decoder: &ffmpeg_next::decoder::Audio,
+) -> Option<ffmpeg_next::software::resampling::Context> {
+    let layout = match decoder.channel_layout() {
+        layout if layout.is_empty() => {
+            ffmpeg_next::ChannelLayout::default(i32::from(decoder.channels()))
+        }
+        layout => layout,
+    };
+    stereo_resampler(decoder.format(), layout, decoder.rate())
+}
+
+fn stereo_resampler(
+    format: ffmpeg_next::format::Sample,
+    layout: ffmpeg_next::ChannelLayout,
+    rate: u32,
+) -> Option<ffmpeg_next::software::resampling::Context> {
+    ffmpeg_next::software::resampling::Context::get(
+        format,
+        layout,
+        rate,
+        ffmpeg_next::format::Sample::I16(ffmpeg_next::format::sample::Type::Packed),
+        ffmpeg_next::ChannelLayout::STEREO,
+        OUTPUT_SAMPLE_RATE,
+    )
+    .ok()
+}
+
+/// Wraps a resampled frame for the sink, taking the channel count and rate
+/// from the frame so playback speed always matches what the resampler made.
+pub fn frame_source(
+    frame: &ffmpeg_next::frame::Audio,
+    gain: f32,
+) -> Option<rodio::buffer::SamplesBuffer> {
+    if frame.samples() == 0 {
+        return None;
+    }
+    let channels = match frame.channels() {
+        0 => OUTPUT_CHANNELS,
+        channels => channels,
+    };
+    let rate = match frame.rate() {
+        0 => OUTPUT_SAMPLE_RATE,
+        rate => rate,
+    };
+    let mut samples = frame_samples(frame);
+    apply_gain(&mut samples, gain);
+    Some(rodio::buffer::SamplesBuffer::new(channels, rate, samples))
+}
+
+/// Reads the samples of a resampled packed-i16 frame. The plane can be
+/// padded past the last sample, so the length comes from the frame itself.
+fn frame_samples(frame: &ffmpeg_next::frame::Audio) -> Vec<f32> {
+    let len = frame.samples() * usize::from(frame.channels()) * 2;
+    let data = frame.data(0);
+    data[..len.min(data.len())]
+        .chunks_exact(2)
+        .map(|c| i16::from_le_bytes([c[0], c[1]]) as f32 / 32768.0)
+        .collect()
+}
+
+/// Level above which boosted samples are bent toward full scale instead of
+/// passing through unchanged.
+const SOFT_CLIP_KNEE: f32 = 0.8;
+
+/// Boosts quiet audio by a fixed `gain`. Peaks pushed past the knee are
+/// soft-clipped sample by sample, so loud passages don't pump the way a
+/// per-buffer limiter would.
+fn apply_gain(samples: &mut [f32], gain: f32) {
+    if gain <= 1.0 {
+        return;
+    }
+    for sample in samples {
+        *sample = soft_clip(*sample * gain);
+    }
+}
+
+/// Passes samples below the knee through and maps everything above it onto
+/// the remaining headroom with a tanh curve, which meets the linear part with
+/// matching slope and never reaches full scale.
+fn soft_clip(sample: f32) -> f32 {
+    let magnitude = sample.abs();
+    if magnitude <= SOFT_CLIP_KNEE {
+        return sample;
+    }
+    let headroom = 1.0 - SOFT_CLIP_KNEE;
+    let clipped = SOFT_CLIP_KNEE + headroom * ((magnitude - SOFT_CLIP_KNEE) / headroom).tanh();
+    clipped.copysign(sample)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ffmpeg_next::format::{sample::Type, Sample};
+    use ffmpeg_next::ChannelLayout;
+    use rodio::Source;
+
+    const PACKED_I16: Sample = Sample::I16(Type::Packed);
+
+    fn input_frame(
+        layout: ChannelLayout,
+        rate: u32,
+        samples: usize,
+        level: i16,
+    ) -> ffmpeg_next::frame::Audio {
+        let mut frame = ffmpeg_next::frame::Audio::new(PACKED_I16, samples, layout);
+        frame.set_rate(rate);
+        for chunk in frame.data_mut(0).chunks_exact_mut(2) {
+            chunk.copy_from_slice(&level.to_le_bytes());
+        }
+        frame
+    }
+
+    fn resample_once(layout: ChannelLayout) -> ffmpeg_next::frame::Audio {
+        let mut resampler = stereo_resampler(PACKED_I16, layout, OUTPUT_SAMPLE_RATE).unwrap();
+        let input = input_frame(layout, OUTPUT_SAMPLE_RATE, 1024, 8000);
+        let mut output = ffmpeg_next::frame::Audio::empty();
+        resampler.run(&input, &mut output).unwrap();
+        output
+    }
+
+    #[test]
+    fn mono_source_plays_on_both_stereo_channels() {
+        let output = resample_once(ChannelLayout::MONO);
+        let samples = frame_samples(&output);
+
+        assert_eq!(output.channels(), 2);
+        assert_eq!(samples.len(), output.samples() * 2);
+        assert!(samples.iter().all(|sample| *sample != 0.0));
+        assert!(samples.chunks_exact(2).all(|pair| pair[0] == pair[1]));
+        assert_eq!(frame_source(&output, 1.0).unwrap().channels(), 2);
+    }
+
+    #[test]
+    fn surround_source_is_downmixed_to_stereo() {
+        let output = resample_once(ChannelLayout::_5POINT1);
+        let samples = frame_samples(&output);
+
+        assert_eq!(output.channels(), 2);
+        assert_eq!(samples.len(), output.samples() * 2);
+        assert!(samples.iter().all(|sample| *sample != 0.0));
+    }
+
+    #[test]
+    fn source_at_48_khz_plays_for_its_real_duration() {
+        let mut resampler = stereo_resampler(PACKED_I16, ChannelLayout::STEREO, 48_000).unwrap();
+        let input = input_frame(ChannelLayout::STEREO, 48_000, 48_000, 8000);
+        let mut output = ffmpeg_next::frame::Audio::empty();
+        resampler.run(&input, &mut output).unwrap();
+        let mut tail = ffmpeg_next::frame::Audio::new(PACKED_I16, 4096, ChannelLayout::STEREO);
+        tail.set_rate(OUTPUT_SAMPLE_RATE);
+        resampler.flush(&mut tail).unwrap();
+
+        let played: std::time::Duration = [&output, &tail]
+            .into_iter()
+            .filter_map(|frame| frame_source(frame, 1.0))
+            .map(|source| {
+                assert_eq!(source.sample_rate(), OUTPUT_SAMPLE_RATE);
+                source.total_duration().unwrap()
+            })
+            .sum();
+
+        assert_eq!(output.rate(), OUTPUT_SAMPLE_RATE);
+        assert!((played.as_secs_f64() - 1.0).abs() < 0.005, "{played:?}");
+    }
+}
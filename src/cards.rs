@@ -1,15 +1,17 @@
 use iced::widget::{button, column, container, row, scrollable, text, Column, Row, Space};
 use iced::{Border, Color, Element, Length, Padding, Shadow};
 
+use crate::detail_popup::format_full_date;
 use crate::media::{
-    section_id, ContentSection, MediaId, MediaItem, Message, Page, ScrollDirection, NETFLIX_RED,
-    SURFACE_DARK_GRAY, TEXT_GRAY, TEXT_WHITE,
+    section_id, CardExpandStyle, Category, ContentSection, MediaId, MediaItem, Message,
+    ScrollDirection, NETFLIX_RED, SURFACE_DARK_GRAY, TEXT_GRAY, TEXT_WHITE,
 };
 use crate::tmdb::ImageSize;
 use crate::Movix;
 
 const ICON_PLAY_FILL: char = '\u{F4F4}';
 const ICON_PLUS_LG: char = '\u{F64D}';
+const ICON_CHECK_LG: char = '\u{F633}';
 const ICON_INFO_CIRCLE: char = '\u{F431}';
 const ICON_FILM: char = '\u{F3A9}';
 const ICON_CHEVRON_LEFT: char = '\u{F284}';
@@ -19,6 +21,8 @@ const CARD_WIDTH: f32 = 150.0;
 const CARD_HEIGHT: f32 = 225.0;
 const EXPANDED_WIDTH: f32 = 400.0;
 const EXPANDED_HEIGHT: f32 = 225.0;
+const ROW_VIEWPORT_WIDTH: f32 = 800.0;
+const CARD_SPACING: f32 = 16.0;
 
 fn icon(icon_char: char) -> iced::widget::Text<'static> {
     text(icon_char.to_string()).font(iced::Font {
@@ -60,13 +64,22 @@ fn hidden_horizontal_scrollbar_style(
 }
 
 impl Movix {
+    pub fn section_max_scroll(&self, section_index: usize) -> f32 {
+        let card_count = self
+            .content_sections
+            .get(section_index)
+            .map_or(0, |s| s.items.len().min(self.settings.section_item_cap));
+        let total_width = (card_count as f32) * (CARD_WIDTH + 12.0) - 12.0;
+        (total_width - ROW_VIEWPORT_WIDTH).max(0.0)
+    }
+
     pub fn view_content_sections(&self) -> Element<'_, Message> {
         let gradient_color = self.get_hero_gradient_color();
 
         let mut sections: Vec<Element<Message>> = Vec::new();
 
         for (index, section) in self.content_sections.iter().enumerate() {
-            if index == 0 {
+            if index == 0 && self.settings.show_hero {
                 let section_element = self.view_content_section_with_arrows(section, index);
                 let with_gradient = container(section_element)
                     .width(Length::Fill)
@@ -110,19 +123,26 @@ impl Movix {
                     ..Default::default()
                 });
 
+        if section.items.is_empty() {
+            return column![section_title, view_section_status(section, section_index)]
+                .spacing(20)
+                .width(Length::Fill)
+                .into();
+        }
+
         let cards: Vec<Element<Message>> = section
             .items
             .iter()
-            .take(20)
-            .map(|item| self.view_movie_card(item))
+            .take(self.settings.section_item_cap)
+            .map(|item| self.view_movie_card(item, &section.category))
             .collect();
 
         let cards_row = Row::with_children(cards)
-            .spacing(16)
+            .spacing(CARD_SPACING)
             .align_y(iced::Alignment::Start);
 
         let Some(section_id_str) = section_id(section_index) else {
-            return self.view_content_section(section);
+            return self.view_content_section(section, section_index);
         };
         let scrollable_id = iced::widget::Id::new(section_id_str);
         let section_idx = section_index;
@@ -136,6 +156,14 @@ impl Movix {
             ))
             .width(Length::Fill)
             .style(hidden_horizontal_scrollbar_style);
+        // Only capture the wheel while Shift is held, so plain scrolling over a
+        // row still scrolls the page. The mouse area is always present so the
+        // scrollable keeps its state when Shift toggles.
+        let mut scrollable_cards = iced::widget::mouse_area(scrollable_cards);
+        if self.keyboard_modifiers.shift() {
+            scrollable_cards = scrollable_cards
+                .on_scroll(move |delta| Message::WheelScrollSection(section_idx, delta));
+        }
 
         let is_hovered = self.hovered_section == Some(section_index);
         let scroll_offset = self
@@ -143,19 +171,23 @@ impl Movix {
             .get(section_index)
             .copied()
             .unwrap_or(0.0);
+        // Posters evicted from the cache while in view are loaded again.
+        for url in self.visible_poster_urls(section_index, scroll_offset) {
+            self.image_cache.request(&url);
+        }
         let scroll_target = self
             .section_scroll_targets
             .get(section_index)
             .copied()
             .unwrap_or(0.0);
 
-        let card_count = section.items.len().min(20);
-        let total_width = (card_count as f32) * (CARD_WIDTH + 12.0) - 12.0;
         let can_scroll_left = scroll_target > 0.0 || scroll_offset > 1.0;
-        let can_scroll_right = total_width > 800.0 && scroll_target < total_width - 800.0;
+        let can_scroll_right = scroll_target < self.section_max_scroll(section_index);
 
+        let expanded_overlay = self.view_expanded_card_overlay(section, scroll_offset);
         let cards_with_arrows = self.view_scrollable_with_arrows(
             scrollable_cards.into(),
+            expanded_overlay,
             section_index,
             is_hovered,
             can_scroll_left,
@@ -175,6 +207,7 @@ impl Movix {
     fn view_scrollable_with_arrows<'a>(
         &'a self,
         scrollable_content: Element<'a, Message>,
+        expanded_overlay: Option<Element<'a, Message>>,
         section_index: usize,
         is_hovered: bool,
         can_scroll_left: bool,
@@ -204,7 +237,13 @@ impl Movix {
             .align_x(iced::alignment::Horizontal::Right)
             .align_y(iced::alignment::Vertical::Center);
 
-        iced::widget::stack![scrollable_content, left_overlay, right_overlay]
+        let mut layers = iced::widget::stack![scrollable_content];
+        if let Some(expanded) = expanded_overlay {
+            layers = layers.push(expanded);
+        }
+        layers
+            .push(left_overlay)
+            .push(right_overlay)
             .width(Length::Fill)
             .height(Length::Fixed(CARD_HEIGHT))
             .into()
@@ -258,7 +297,12 @@ impl Movix {
         arrow_button.into()
     }
 
-    pub fn view_content_section(&self, section: &ContentSection) -> Element<'_, Message> {
+    /// Plain row without arrows, for rows that have no scrollable id to drive.
+    pub fn view_content_section(
+        &self,
+        section: &ContentSection,
+        section_index: usize,
+    ) -> Element<'_, Message> {
         let section_title =
             text(section.title.clone())
                 .size(24)
@@ -271,36 +315,71 @@ impl Movix {
         let cards: Vec<Element<Message>> = section
             .items
             .iter()
-            .take(20)
-            .map(|item| self.view_movie_card(item))
+            .take(self.settings.section_item_cap)
+            .map(|item| self.view_movie_card(item, &section.category))
             .collect();
 
         let cards_row = Row::with_children(cards)
-            .spacing(16)
+            .spacing(CARD_SPACING)
             .align_y(iced::Alignment::Start);
 
         let scrollable_cards = scrollable(cards_row)
+            .on_scroll(move |viewport| {
+                Message::SectionScrolled(section_index, viewport.absolute_offset().x)
+            })
             .direction(scrollable::Direction::Horizontal(
                 scrollable::Scrollbar::new().width(0).scroller_width(0),
             ))
             .width(Length::Fill)
             .style(hidden_horizontal_scrollbar_style);
+        let scroll_offset = self
+            .section_scroll_offsets
+            .get(section_index)
+            .copied()
+            .unwrap_or(0.0);
+        let mut cards_layers = iced::widget::stack![scrollable_cards];
+        if let Some(expanded) = self.view_expanded_card_overlay(section, scroll_offset) {
+            cards_layers = cards_layers.push(expanded);
+        }
 
-        iced::widget::column![section_title, scrollable_cards]
+        iced::widget::column![section_title, cards_layers]
             .spacing(20)
             .width(Length::Fill)
             .into()
     }
 
-    pub fn view_movie_card(&self, media_item: &MediaItem) -> Element<'_, Message> {
+    /// Continue Watching cards show progress and resume on click; Coming
+    /// Soon cards show when the title is released.
+    pub fn view_movie_card(
+        &self,
+        media_item: &MediaItem,
+        category: &Category,
+    ) -> Element<'_, Message> {
         let media_id = media_item.id;
         let is_hovered = self.hovered_card == Some(media_id);
+        let resumes = *category == Category::ContinueWatching;
 
-        if is_hovered {
-            return self.view_expanded_card(media_item);
+        let expands_in_place = self.settings.card_expand_style == CardExpandStyle::Overlay;
+        if is_hovered && !expands_in_place {
+            return self.view_expanded_card(media_item, category);
         }
 
-        let poster_content = self.view_card_poster(media_item, CARD_WIDTH, CARD_HEIGHT);
+        let poster = self.view_card_poster(media_item, CARD_WIDTH, CARD_HEIGHT);
+        let release_date = media_item
+            .release_date
+            .as_deref()
+            .filter(|_| *category == Category::Upcoming);
+        let poster_content: Element<'_, Message> = match self
+            .movie_player
+            .get_watched_fraction(media_id)
+            .filter(|_| resumes)
+        {
+            Some(fraction) => iced::widget::stack![poster, view_watched_bar(fraction)].into(),
+            None => match release_date {
+                Some(date) => iced::widget::stack![poster, view_release_badge(date)].into(),
+                None => poster,
+            },
+        };
 
         let card_container = container(poster_content)
             .width(Length::Fixed(CARD_WIDTH))
@@ -320,19 +399,72 @@ impl Movix {
                 ..Default::default()
             });
 
+        // The overlay drawn above this card owns its hover, so the card itself
+        // must not report an exit when the overlay appears.
+        if is_hovered {
+            return card_container.into();
+        }
+
         iced::widget::mouse_area(card_container)
             .on_enter(Message::HoverCard(Some(media_id)))
             .on_exit(Message::HoverCard(None))
-            .on_press(Message::NavigateTo(Page::Detail(media_id)))
+            .on_press(self.card_press_message(media_id, resumes))
+            .on_double_click(Message::PlayContent(media_id))
             .into()
     }
 
-    pub fn view_expanded_card(&self, media_item: &MediaItem) -> Element<'_, Message> {
+    fn card_press_message(&self, media_id: MediaId, resumes: bool) -> Message {
+        if resumes {
+            Message::PlayContent(media_id)
+        } else {
+            self.settings.card_click_action.message(media_id)
+        }
+    }
+
+    /// With `CardExpandStyle::Overlay`, the hovered card's backdrop card is
+    /// centred over its slot in a layer above the row, so no card moves.
+    fn view_expanded_card_overlay(
+        &self,
+        section: &ContentSection,
+        scroll_offset: f32,
+    ) -> Option<Element<'_, Message>> {
+        if self.settings.card_expand_style != CardExpandStyle::Overlay {
+            return None;
+        }
+        let hovered = self.hovered_card?;
+        let (index, item) = section
+            .items
+            .iter()
+            .take(self.settings.section_item_cap)
+            .enumerate()
+            .find(|(_, item)| item.id == hovered)?;
+
+        let slot_x = index as f32 * (CARD_WIDTH + CARD_SPACING) - scroll_offset;
+        let left = (slot_x - (EXPANDED_WIDTH - CARD_WIDTH) / 2.0).max(0.0);
+
+        Some(
+            container(self.view_expanded_card(item, &section.category))
+                .width(Length::Fill)
+                .height(Length::Fixed(EXPANDED_HEIGHT))
+                .padding(Padding::new(0.0).left(left))
+                .into(),
+        )
+    }
+
+    pub fn view_expanded_card(
+        &self,
+        media_item: &MediaItem,
+        category: &Category,
+    ) -> Element<'_, Message> {
         let media_id = media_item.id;
+        let resumes = *category == Category::ContinueWatching;
         let backdrop_content = self.view_card_backdrop_with_load(media_item);
         let hover_overlay = self.view_expanded_hover_overlay(media_item);
 
-        let stacked_content = iced::widget::stack![backdrop_content, hover_overlay];
+        let mut stacked_content = iced::widget::stack![backdrop_content, hover_overlay];
+        if self.card_trailer_resolving == Some(media_id) {
+            stacked_content = stacked_content.push(Self::view_trailer_loading_badge());
+        }
 
         let card_container = container(stacked_content)
             .width(Length::Fixed(EXPANDED_WIDTH))
@@ -353,9 +485,33 @@ impl Movix {
             });
 
         iced::widget::mouse_area(card_container)
+            .interaction(iced::mouse::Interaction::Pointer)
             .on_enter(Message::HoverCard(Some(media_id)))
             .on_exit(Message::HoverCard(None))
-            .on_press(Message::NavigateTo(Page::Detail(media_id)))
+            .on_press(self.card_press_message(media_id, resumes))
+            .on_double_click(Message::PlayContent(media_id))
+            .into()
+    }
+
+    fn view_trailer_loading_badge() -> Element<'static, Message> {
+        let badge = container(text("Loading trailer…").size(11).color(TEXT_GRAY))
+            .padding(Padding::new(4.0).left(8.0).right(8.0))
+            .style(|_theme| container::Style {
+                background: Some(iced::Background::Color(Color::from_rgba(
+                    0.0, 0.0, 0.0, 0.6,
+                ))),
+                border: Border {
+                    radius: 10.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        container(badge)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(iced::alignment::Horizontal::Right)
+            .align_y(iced::alignment::Vertical::Top)
+            .padding(Padding::new(8.0))
             .into()
     }
 
@@ -381,26 +537,21 @@ impl Movix {
             }
         }
 
-        if let Some(backdrop_path) = &media_item.backdrop_path {
-            if let Some(client) = &self.tmdb_client {
-                let image_url = client.image_url(backdrop_path, ImageSize::Backdrop);
-                if let Some(handle) = self.image_cache.get(&image_url) {
-                    return container(
-                        iced::widget::image(handle.clone())
-                            .width(Length::Fixed(EXPANDED_WIDTH))
-                            .height(Length::Fixed(EXPANDED_HEIGHT))
-                            .content_fit(iced::ContentFit::Cover),
-                    )
-                    .style(|_theme| container::Style {
-                        border: Border {
-                            radius: 8.0.into(),
-                            ..Default::default()
-                        },
-                        ..Default::default()
-                    })
-                    .into();
-                }
-            }
+        if let Some(handle) = self.cached_backdrop(media_item) {
+            return container(
+                iced::widget::image(handle)
+                    .width(Length::Fixed(EXPANDED_WIDTH))
+                    .height(Length::Fixed(EXPANDED_HEIGHT))
+                    .content_fit(iced::ContentFit::Cover),
+            )
+            .style(|_theme| container::Style {
+                border: Border {
+                    radius: 8.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .into();
         }
         container(Space::new().width(EXPANDED_WIDTH).height(EXPANDED_HEIGHT))
             .width(Length::Fixed(EXPANDED_WIDTH))
@@ -421,8 +572,13 @@ impl Movix {
         let title_element = self.view_expanded_card_title(media_item);
 
         let play_button = self.view_expanded_play_button(media_id);
-        let add_button = self.view_expanded_action_button(media_id, ICON_PLUS_LG, false);
-        let info_button = self.view_expanded_action_button(media_id, ICON_INFO_CIRCLE, true);
+        let add_button = if self.my_list.contains(media_id) {
+            self.view_expanded_action_button(ICON_CHECK_LG, Message::RemoveFromList(media_id))
+        } else {
+            self.view_expanded_action_button(ICON_PLUS_LG, Message::AddToList(media_id))
+        };
+        let info_button =
+            self.view_expanded_action_button(ICON_INFO_CIRCLE, Message::ShowMoreInfo(media_id));
 
         let action_buttons = row![play_button, add_button, info_button]
             .spacing(6)
@@ -540,16 +696,10 @@ impl Movix {
 
     pub fn view_expanded_action_button(
         &self,
-        media_id: MediaId,
         icon_char: char,
-        is_info: bool,
+        message: Message,
     ) -> Element<'_, Message> {
         let button_size = 36.0;
-        let message = if is_info {
-            Message::ShowMoreInfo(media_id)
-        } else {
-            Message::HoverCard(Some(media_id))
-        };
 
         button(
             container(icon(icon_char).size(16).color(TEXT_WHITE))
@@ -617,3 +767,99 @@ impl Movix {
             .into()
     }
 }
+
+/// Thin bar along the bottom of a poster showing how much has been watched.
+fn view_watched_bar(fraction: f64) -> Element<'static, Message> {
+    let bar = container(Space::new().width(Length::Fill).height(Length::Fill))
+        .width(Length::Fixed(CARD_WIDTH * fraction as f32))
+        .height(Length::Fixed(4.0))
+        .style(|_theme| container::Style {
+            background: Some(iced::Background::Color(NETFLIX_RED)),
+            ..Default::default()
+        });
+    let track = container(bar)
+        .width(Length::Fill)
+        .height(Length::Fixed(4.0))
+        .style(|_theme| container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                1.0, 1.0, 1.0, 0.3,
+            ))),
+            ..Default::default()
+        });
+    container(track)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_y(iced::alignment::Vertical::Bottom)
+        .into()
+}
+
+fn view_release_badge(date: &str) -> Element<'static, Message> {
+    let badge = container(
+        text(format!("Coming {}", format_full_date(date)))
+            .size(11)
+            .color(TEXT_WHITE),
+    )
+    .padding(Padding::new(4.0).left(8.0).right(8.0))
+    .style(|_theme| container::Style {
+        background: Some(iced::Background::Color(Color::from_rgba(
+            0.0, 0.0, 0.0, 0.75,
+        ))),
+        border: Border {
+            radius: 4.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    container(badge)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_x(iced::alignment::Horizontal::Center)
+        .align_y(iced::alignment::Vertical::Bottom)
+        .padding(Padding::new(8.0))
+        .into()
+}
+
+/// Stands in for the cards of a row that failed to load, or is being
+/// retried, so the rest of the page stays usable.
+fn view_section_status(
+    section: &ContentSection,
+    section_index: usize,
+) -> Element<'static, Message> {
+    let content: Element<'static, Message> = match &section.error {
+        Some(_) => row![
+            text("Couldn't load this row").size(14).color(TEXT_GRAY),
+            button(text("Retry").size(14).color(TEXT_WHITE))
+                .padding(Padding::new(6.0).left(16.0).right(16.0))
+                .style(|_theme, status| button::Style {
+                    background: Some(iced::Background::Color(
+                        if matches!(status, button::Status::Hovered) {
+                            Color::from_rgb(0.25, 0.25, 0.25)
+                        } else {
+                            SURFACE_DARK_GRAY
+                        },
+                    )),
+                    text_color: TEXT_WHITE,
+                    border: Border {
+                        radius: 4.0.into(),
+                        ..Default::default()
+                    },
+                    shadow: Shadow::default(),
+                    snap: false,
+                })
+                .on_press(Message::RetrySection(section_index)),
+        ]
+        .spacing(16)
+        .align_y(iced::Alignment::Center)
+        .into(),
+        None if section.loading => text("Loading…").size(14).color(TEXT_GRAY).into(),
+        None => text("Nothing to show here yet")
+            .size(14)
+            .color(TEXT_GRAY)
+            .into(),
+    };
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fixed(64.0))
+        .align_y(iced::alignment::Vertical::Center)
+        .into()
+}
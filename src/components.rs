@@ -3,14 +3,17 @@ use iced::widget::{
 };
 use iced::{Border, Color, Element, Length, Padding, Shadow};
 
+use crate::hero::HERO_HEIGHT;
 use crate::media::{
-    LoadingState, Message, NavItem, Page, ProfileAction, NETFLIX_RED, SURFACE_DARK_GRAY, TEXT_GRAY,
-    TEXT_WHITE,
+    LoadingState, Message, NavItem, Page, ProfileAction, MAIN_SCROLL_ID, NETFLIX_RED,
+    SURFACE_DARK_GRAY, TEXT_GRAY, TEXT_WHITE,
 };
 use crate::Movix;
 
+const ICON_ARROW_UP: char = '\u{F148}';
 const ICON_PERSON_FILL: char = '\u{F4DA}';
 const ICON_SEARCH: char = '\u{F52A}';
+const ICON_WIFI_OFF: char = '\u{F61B}';
 
 fn icon(icon_char: char) -> iced::widget::Text<'static> {
     text(icon_char.to_string()).font(iced::Font {
@@ -111,6 +114,7 @@ impl Movix {
             (NavItem::Series, "Series", Page::Series),
             (NavItem::Movies, "Movies", Page::Movies),
             (NavItem::MyList, "My List", Page::MyList),
+            (NavItem::People, "People", Page::People),
         ];
 
         let nav_buttons: Vec<Element<Message>> = nav_items
@@ -245,6 +249,7 @@ impl Movix {
         let menu_items = [
             ("Settings", ProfileAction::OpenSettings),
             ("Profile Settings", ProfileAction::OpenProfileSettings),
+            ("Open File", ProfileAction::OpenFile),
             ("Switch Profile", ProfileAction::SwitchProfile(0)),
         ];
 
@@ -291,6 +296,112 @@ impl Movix {
             .into()
     }
 
+    pub fn view_open_file_overlay(&self) -> Element<'_, Message> {
+        let path = self.open_file_path.as_deref().unwrap_or_default();
+        let path_input = text_input("/path/to/video.mkv", path)
+            .on_input(Message::OpenFilePathChanged)
+            .on_submit(Message::OpenFileSubmit)
+            .padding(10)
+            .size(15)
+            .style(|_theme, _status| text_input::Style {
+                background: iced::Background::Color(Color::from_rgb(0.2, 0.2, 0.2)),
+                border: Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                icon: TEXT_GRAY,
+                placeholder: TEXT_GRAY,
+                value: TEXT_WHITE,
+                selection: NETFLIX_RED,
+            });
+
+        let action_button = |label: &'static str, primary: bool, message: Message| {
+            button(text(label).size(15).color(TEXT_WHITE))
+                .padding(Padding::new(10.0).left(24.0).right(24.0))
+                .style(move |_theme, status| {
+                    let hovered = matches!(status, button::Status::Hovered);
+                    let background = match (primary, hovered) {
+                        (true, false) => NETFLIX_RED,
+                        (true, true) => Color::from_rgb(0.698, 0.027, 0.063),
+                        (false, false) => Color::from_rgba(1.0, 1.0, 1.0, 0.1),
+                        (false, true) => Color::from_rgba(1.0, 1.0, 1.0, 0.2),
+                    };
+                    button::Style {
+                        background: Some(iced::Background::Color(background)),
+                        text_color: TEXT_WHITE,
+                        border: Border {
+                            radius: 4.0.into(),
+                            ..Default::default()
+                        },
+                        shadow: Shadow::default(),
+                        snap: false,
+                    }
+                })
+                .on_press(message)
+        };
+
+        let mut content = column![
+            text("Open File")
+                .size(24)
+                .color(TEXT_WHITE)
+                .font(iced::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+            text("Enter the path of a local video file to play.")
+                .size(14)
+                .color(TEXT_GRAY),
+            path_input,
+        ]
+        .spacing(14);
+        if let Some(error) = &self.open_file_error {
+            content = content.push(text(error).size(13).color(NETFLIX_RED));
+        }
+        content = content.push(
+            row![
+                action_button("Play", true, Message::OpenFileSubmit),
+                action_button("Cancel", false, Message::CloseOpenFileDialog),
+            ]
+            .spacing(12),
+        );
+
+        let dialog = container(content)
+            .width(Length::Fixed(520.0))
+            .padding(Padding::new(32.0))
+            .style(|_theme| container::Style {
+                background: Some(iced::Background::Color(SURFACE_DARK_GRAY)),
+                border: Border {
+                    radius: 12.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+        let overlay_bg = iced::widget::mouse_area(
+            container(Space::new().width(Length::Fill).height(Length::Fill))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(|_theme| container::Style {
+                    background: Some(iced::Background::Color(Color::from_rgba(
+                        0.0, 0.0, 0.0, 0.85,
+                    ))),
+                    ..Default::default()
+                }),
+        )
+        .on_press(Message::CloseOpenFileDialog);
+
+        let centered = container(iced::widget::mouse_area(dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill);
+
+        iced::widget::stack![overlay_bg, centered]
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
     pub fn view_header_with_dropdown(&self) -> Element<'_, Message> {
         container(self.view_header())
             .width(Length::Fill)
@@ -307,7 +418,8 @@ impl Movix {
     }
 
     fn view_error_state<'a>(&'a self, error_message: &'a str) -> Element<'a, Message> {
-        let error_text = text(error_message).size(18).color(NETFLIX_RED);
+        let message = self.proxy_error.as_deref().unwrap_or(error_message);
+        let error_text = text(message).size(18).color(NETFLIX_RED);
         let retry_button = button(text("Retry").size(16).color(TEXT_WHITE))
             .padding(Padding::new(12.0).left(24.0).right(24.0))
             .style(|_theme, _status| button::Style {
@@ -335,28 +447,111 @@ impl Movix {
         .into()
     }
 
+    pub fn view_offline_banner(&self) -> Element<'_, Message> {
+        let mut content = row![
+            icon(ICON_WIFI_OFF).size(16).color(TEXT_WHITE),
+            text(self.proxy_error.as_deref().unwrap_or(
+                "Offline mode: showing saved titles. Trailers and streaming are paused."
+            ))
+            .size(14)
+            .color(TEXT_WHITE)
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center);
+        // Local files are the only thing that can still be played.
+        if self.is_offline {
+            content = content.push(
+                button(text("Open File").size(14).color(TEXT_WHITE))
+                    .padding(Padding::new(4.0).left(12.0).right(12.0))
+                    .style(|_theme, status| button::Style {
+                        background: Some(iced::Background::Color(
+                            if matches!(status, button::Status::Hovered) {
+                                Color::from_rgb(0.25, 0.25, 0.25)
+                            } else {
+                                SURFACE_DARK_GRAY
+                            },
+                        )),
+                        text_color: TEXT_WHITE,
+                        border: Border {
+                            radius: 4.0.into(),
+                            ..Default::default()
+                        },
+                        shadow: Shadow::default(),
+                        snap: false,
+                    })
+                    .on_press(Message::ProfileAction(ProfileAction::OpenFile)),
+            );
+        }
+        let banner = container(content)
+            .padding(Padding::new(10.0).left(20.0).right(20.0))
+            .style(|_theme| container::Style {
+                background: Some(iced::Background::Color(Color::from_rgba(
+                    0.15, 0.15, 0.15, 0.95,
+                ))),
+                border: Border {
+                    color: NETFLIX_RED,
+                    width: 1.0,
+                    radius: 20.0.into(),
+                },
+                ..Default::default()
+            });
+
+        container(banner)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .align_y(iced::alignment::Vertical::Bottom)
+            .padding(Padding::new(24.0))
+            .into()
+    }
+
     fn view_idle_state(&self) -> Element<'_, Message> {
         let header = self.view_header_with_dropdown();
 
         let main_column = if self.search_active {
             column![self.view_search_page()].width(Length::Fill)
+        } else if self.browse_genre.is_some() {
+            column![self.view_genre_browse_page()].width(Length::Fill)
+        } else if matches!(self.current_page, Page::Detail(_)) {
+            column![self.view_detail_page()].width(Length::Fill)
+        } else if matches!(self.current_page, Page::MyList) {
+            column![self.view_my_list_page()].width(Length::Fill)
+        } else if matches!(self.current_page, Page::People) {
+            column![self.view_people_page()].width(Length::Fill)
+        } else if matches!(self.current_page, Page::Person(_)) {
+            column![self.view_person_page()].width(Length::Fill)
         } else {
-            let hero = self.view_hero_section();
             let content_sections = self.view_content_sections();
-            column![hero, content_sections].width(Length::Fill)
+            if self.settings.show_hero {
+                column![self.view_hero_section(), content_sections].width(Length::Fill)
+            } else {
+                column![content_sections]
+                    .width(Length::Fill)
+                    .padding(Padding::new(0.0).top(100.0))
+            }
         };
 
-        let base_content = iced::widget::stack![
+        let mut base_content = iced::widget::stack![
             scrollable(main_column)
+                .id(iced::widget::Id::new(MAIN_SCROLL_ID))
                 .direction(scrollable::Direction::Vertical(
                     scrollable::Scrollbar::new().width(0).scroller_width(0),
                 ))
-                .on_scroll(|viewport| Message::MainScrolled(viewport.absolute_offset().y))
+                .on_scroll(|viewport| {
+                    Message::MainScrolled(
+                        viewport.absolute_offset().y,
+                        viewport.bounds().height,
+                        viewport.content_bounds().height,
+                    )
+                })
                 .width(Length::Fill)
                 .height(Length::Fill)
                 .style(hidden_vertical_scrollbar_style),
             header
         ];
+        if self.main_scroll_offset > self.page_height() {
+            base_content = base_content.push(self.view_scroll_to_top_button());
+        }
 
         if self.profile_menu_open {
             let dropdown = self.view_profile_dropdown();
@@ -376,6 +571,51 @@ impl Movix {
 }
 
 impl Movix {
+    fn view_scroll_to_top_button(&self) -> Element<'_, Message> {
+        let scroll_button = button(
+            container(icon(ICON_ARROW_UP).size(20).color(TEXT_WHITE))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .center_x(Length::Fill)
+                .center_y(Length::Fill),
+        )
+        .width(Length::Fixed(48.0))
+        .height(Length::Fixed(48.0))
+        .padding(0)
+        .style(|_theme, status| {
+            let bg_alpha = match status {
+                button::Status::Hovered => 0.9,
+                _ => 0.7,
+            };
+            button::Style {
+                background: Some(iced::Background::Color(Color::from_rgba(
+                    0.2, 0.2, 0.2, bg_alpha,
+                ))),
+                text_color: TEXT_WHITE,
+                border: Border {
+                    color: Color::from_rgba(1.0, 1.0, 1.0, 0.3),
+                    width: 1.0,
+                    radius: 24.0.into(),
+                },
+                shadow: Shadow {
+                    color: Color::from_rgba(0.0, 0.0, 0.0, 0.5),
+                    offset: iced::Vector::new(0.0, 2.0),
+                    blur_radius: 8.0,
+                },
+                snap: false,
+            }
+        })
+        .on_press(Message::PageScroll(PageScroll::Home));
+
+        container(scroll_button)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(iced::alignment::Horizontal::Right)
+            .align_y(iced::alignment::Vertical::Bottom)
+            .padding(Padding::new(32.0))
+            .into()
+    }
+
     pub fn view_skeleton_ui(&self) -> Element<'_, Message> {
         let skeleton_header = self.view_skeleton_header();
         let skeleton_hero = self.view_skeleton_hero();
@@ -486,7 +726,7 @@ impl Movix {
 
         let backdrop_skeleton = container(Space::new().width(Length::Fill).height(Length::Fill))
             .width(Length::Fill)
-            .height(Length::Fixed(500.0))
+            .height(Length::Fixed(HERO_HEIGHT))
             .style(|_theme| container::Style {
                 background: Some(iced::Background::Color(SURFACE_DARK_GRAY)),
                 ..Default::default()
@@ -494,7 +734,7 @@ impl Movix {
 
         iced::widget::stack![backdrop_skeleton, hero_overlay]
             .width(Length::Fill)
-            .height(Length::Fixed(500.0))
+            .height(Length::Fixed(HERO_HEIGHT))
             .into()
     }
 
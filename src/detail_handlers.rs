@@ -1,49 +1,97 @@
 use iced::Task;
 
-use crate::media::{ApiError, MediaId, Message};
+use crate::handlers;
+use crate::media::{ApiError, MediaId, MediaItem, MediaType, Message, Page};
+use crate::player_handlers;
 use crate::tmdb::ImageSize;
+use crate::video::select_best_trailer;
 use crate::Movix;
 
 pub fn handle_open_detail_popup(app: &mut Movix, media_id: MediaId) -> Task<Message> {
-    app.detail_popup_open = true;
-    app.detail_popup_media_id = Some(media_id);
+    let media_type = lookup_media_type(app, media_id);
+    open_detail_popup(app, media_id, media_type)
+}
+
+pub fn lookup_media_type(app: &Movix, media_id: MediaId) -> MediaType {
+    lookup_media_item(app, media_id)
+        .map(|i| i.media_type.clone())
+        .unwrap_or(MediaType::Movie)
+}
+
+pub fn lookup_media_item(app: &Movix, media_id: MediaId) -> Option<&MediaItem> {
+    let detail_items = app.detail_popup_data.iter().flat_map(|d| {
+        std::iter::once(&d.media_item)
+            .chain(&d.similar)
+            .chain(d.collection.iter().flat_map(|c| &c.parts))
+    });
+
+    app.content_sections
+        .iter()
+        .flat_map(|s| &s.items)
+        .chain(app.hero_items.iter())
+        .chain(app.search_results.iter())
+        .chain(app.browse_results.iter())
+        .chain(app.person_detail.iter().flat_map(|d| &d.filmography))
+        .chain(detail_items)
+        .chain(app.my_list.items())
+        .find(|i| i.id == media_id)
+}
+
+fn reset_detail_state(app: &mut Movix, media_id: Option<MediaId>) {
+    app.detail_popup_media_id = media_id;
     app.detail_popup_data = None;
     app.detail_selected_season = None;
     app.detail_episodes.clear();
+    app.detail_episodes_loading = false;
+    app.detail_advanced_expanded = false;
+    app.detail_cast_expanded = false;
+    app.detail_overview_expanded = false;
+    app.detail_revealed_episodes.clear();
+    app.detail_video_index = None;
+    app.detail_imdb_rating = None;
     app.detail_hovered_card = None;
     app.pending_detail_hover_card = None;
     app.detail_video_frame = None;
+}
 
+fn fetch_detail_data(app: &Movix, media_id: MediaId, media_type: MediaType) -> Task<Message> {
     let Some(client) = &app.tmdb_client else {
-        return Task::done(Message::PauseHeroTrailer);
+        return Task::none();
     };
 
-    let item = app
-        .content_sections
-        .iter()
-        .flat_map(|s| &s.items)
-        .find(|i| i.id == media_id)
-        .or_else(|| app.hero_content.as_ref().filter(|h| h.id == media_id));
-
-    let media_type = item
-        .map(|i| i.media_type.clone())
-        .unwrap_or(crate::media::MediaType::Movie);
-
     let fetch_client = client.clone();
-    let fetch_task = Task::perform(
+    Task::perform(
         async move {
             fetch_client
                 .fetch_detail_popup_data(media_id, &media_type)
                 .await
         },
         |result| Message::DetailDataLoaded(Box::new(result)),
-    );
+    )
+}
+
+pub fn open_detail_popup(
+    app: &mut Movix,
+    media_id: MediaId,
+    media_type: MediaType,
+) -> Task<Message> {
+    app.detail_popup_open = true;
+    reset_detail_state(app, Some(media_id));
+
+    if app.tmdb_client.is_none() {
+        return Task::done(Message::PauseHeroTrailer);
+    }
+
+    let fetch_task = fetch_detail_data(app, media_id, media_type);
 
     app.hero_player.pause();
     app.card_player.stop();
 
     let mut tasks = vec![fetch_task];
 
+    if app.is_offline || !app.settings.autoplay_trailers() {
+        return Task::batch(tasks);
+    }
     if app.stream_url_cache.contains_key(&media_id) {
         tasks.push(Task::done(Message::PlayDetailTrailer(media_id)));
     } else if let Some(Some(youtube_id)) = app.trailer_cache.get(&media_id) {
@@ -58,18 +106,36 @@ pub fn handle_open_detail_popup(app: &mut Movix, media_id: MediaId) -> Task<Mess
     Task::batch(tasks)
 }
 
+/// Shows the full-page detail view. It shares the popup's detail state, so an
+/// open popup is dismissed first.
+pub fn open_detail_page(app: &mut Movix, media_id: MediaId) -> Task<Message> {
+    let media_type = lookup_media_type(app, media_id);
+
+    app.detail_popup_open = false;
+    app.detail_player.stop();
+    reset_detail_state(app, Some(media_id));
+
+    app.hero_player.pause();
+    app.card_player.stop();
+    app.hovered_card = None;
+    app.pending_hover_card = None;
+
+    fetch_detail_data(app, media_id, media_type)
+}
+
 pub fn handle_close_detail_popup(app: &mut Movix) -> Task<Message> {
+    if let Page::Detail(media_id) = app.current_page {
+        if !app.detail_popup_open {
+            return Task::none();
+        }
+        return open_detail_page(app, media_id);
+    }
+
     let was_hero_ended = app.hero_ended;
     let should_resume_hero = app.hero_visible && !app.movie_player_active;
 
     app.detail_popup_open = false;
-    app.detail_popup_media_id = None;
-    app.detail_popup_data = None;
-    app.detail_selected_season = None;
-    app.detail_episodes.clear();
-    app.detail_hovered_card = None;
-    app.pending_detail_hover_card = None;
-    app.detail_video_frame = None;
+    reset_detail_state(app, None);
 
     app.detail_player.stop();
 
@@ -91,9 +157,13 @@ pub fn handle_detail_data_loaded(
     app: &mut Movix,
     result: Box<Result<crate::media::DetailPopupData, ApiError>>,
 ) -> Task<Message> {
+    handlers::track_connectivity(app, &*result);
     let Ok(data) = *result else {
         return Task::none();
     };
+    if app.detail_popup_media_id != Some(data.media_item.id) {
+        return Task::none();
+    }
 
     let Some(client) = &app.tmdb_client else {
         app.detail_popup_data = Some(data);
@@ -121,8 +191,7 @@ pub fn handle_detail_data_loaded(
     }
 
     for item in &data.similar {
-        if let Some(backdrop_path) = &item.backdrop_path {
-            let url = client.image_url(backdrop_path, ImageSize::Backdrop);
+        if let Some(url) = app.backdrop_url(item) {
             if app.image_cache.get(&url).is_none() && !app.image_cache.is_pending(&url) {
                 tasks.push(Task::done(Message::LoadImage(url)));
             }
@@ -137,8 +206,7 @@ pub fn handle_detail_data_loaded(
 
     if let Some(collection) = &data.collection {
         for item in &collection.parts {
-            if let Some(backdrop_path) = &item.backdrop_path {
-                let url = client.image_url(backdrop_path, ImageSize::Backdrop);
+            if let Some(url) = app.backdrop_url(item) {
                 if app.image_cache.get(&url).is_none() && !app.image_cache.is_pending(&url) {
                     tasks.push(Task::done(Message::LoadImage(url)));
                 }
@@ -152,8 +220,7 @@ pub fn handle_detail_data_loaded(
         }
     }
 
-    if let Some(backdrop_path) = &data.media_item.backdrop_path {
-        let url = client.image_url(backdrop_path, ImageSize::Backdrop);
+    if let Some(url) = app.backdrop_url(&data.media_item) {
         if app.image_cache.get(&url).is_none() && !app.image_cache.is_pending(&url) {
             tasks.push(Task::done(Message::LoadImage(url)));
         }
@@ -166,6 +233,18 @@ pub fn handle_detail_data_loaded(
         }
     }
 
+    if let (Some(omdb), Some(imdb_id)) = (&app.omdb_client, &data.external_ids.imdb_id) {
+        let omdb = omdb.clone();
+        let imdb_id = imdb_id.clone();
+        tasks.push(Task::perform(
+            async move {
+                let result = omdb.fetch_imdb_rating(&imdb_id).await;
+                (imdb_id, result)
+            },
+            |(imdb_id, result)| Message::DetailImdbRatingLoaded(imdb_id, result),
+        ));
+    }
+
     let is_tv = matches!(
         data.media_item.media_type,
         crate::media::MediaType::TvSeries
@@ -173,12 +252,19 @@ pub fn handle_detail_data_loaded(
     let has_seasons = !data.seasons.is_empty();
     let media_id = data.media_item.id;
 
+    let playing_key = match app.trailer_cache.get(&media_id) {
+        Some(Some(key)) => Some(key.as_str()),
+        _ => select_best_trailer(&data.videos).map(|v| v.key.as_str()),
+    };
+    app.detail_video_index =
+        playing_key.and_then(|key| data.videos.iter().position(|v| v.key == key));
     app.detail_popup_data = Some(data);
 
     if is_tv && has_seasons {
         let Some(client) = &app.tmdb_client else {
             return Task::batch(tasks);
         };
+        app.detail_episodes_loading = true;
         let fetch_client = client.clone();
         let episodes_task = Task::perform(
             async move { fetch_client.fetch_season_episodes(media_id, 1).await },
@@ -190,11 +276,50 @@ pub fn handle_detail_data_loaded(
     Task::batch(tasks)
 }
 
+pub fn handle_detail_imdb_rating_loaded(
+    app: &mut Movix,
+    imdb_id: String,
+    result: Result<Option<String>, ApiError>,
+) -> Task<Message> {
+    let is_current = app
+        .detail_popup_data
+        .as_ref()
+        .and_then(|d| d.external_ids.imdb_id.as_ref())
+        == Some(&imdb_id);
+    if is_current {
+        app.detail_imdb_rating = result.ok().flatten();
+    }
+    Task::none()
+}
+
+pub fn handle_toggle_detail_cast(app: &mut Movix) -> Task<Message> {
+    app.detail_cast_expanded = !app.detail_cast_expanded;
+    if !app.detail_cast_expanded {
+        return Task::none();
+    }
+
+    let (Some(client), Some(data)) = (&app.tmdb_client, &app.detail_popup_data) else {
+        return Task::none();
+    };
+
+    let tasks: Vec<Task<Message>> = data
+        .cast
+        .iter()
+        .filter_map(|m| m.profile_path.as_ref())
+        .map(|path| client.image_url(path, ImageSize::Poster))
+        .filter(|url| app.image_cache.get(url).is_none() && !app.image_cache.is_pending(url))
+        .map(|url| Task::done(Message::LoadImage(url)))
+        .collect();
+
+    Task::batch(tasks)
+}
+
 pub fn handle_detail_select_season(app: &mut Movix, season: Option<u32>) -> Task<Message> {
     app.detail_selected_season = season;
 
     let Some(season_number) = season else {
         app.detail_episodes.clear();
+        app.detail_episodes_loading = false;
         return Task::none();
     };
 
@@ -206,6 +331,8 @@ pub fn handle_detail_select_season(app: &mut Movix, season: Option<u32>) -> Task
         return Task::none();
     };
 
+    app.detail_episodes.clear();
+    app.detail_episodes_loading = true;
     let fetch_client = client.clone();
     Task::perform(
         async move {
@@ -221,6 +348,7 @@ pub fn handle_detail_episodes_loaded(
     app: &mut Movix,
     result: Result<Vec<crate::media::Episode>, ApiError>,
 ) -> Task<Message> {
+    app.detail_episodes_loading = false;
     let Ok(episodes) = result else {
         return Task::none();
     };
@@ -292,8 +420,7 @@ pub fn handle_detail_hover_card_delayed(app: &mut Movix, media_id: MediaId) -> T
 
     let mut tasks = Vec::new();
 
-    if let Some(backdrop_path) = &item.backdrop_path {
-        let url = client.image_url(backdrop_path, ImageSize::Backdrop);
+    if let Some(url) = app.backdrop_url(item) {
         if app.image_cache.get(&url).is_none() && !app.image_cache.is_pending(&url) {
             tasks.push(Task::done(Message::LoadImage(url)));
         }
@@ -306,6 +433,9 @@ pub fn handle_detail_hover_card_delayed(app: &mut Movix, media_id: MediaId) -> T
         }
     }
 
+    if app.is_offline || !app.settings.autoplay_trailers() {
+        return Task::batch(tasks);
+    }
     if app.stream_url_cache.contains_key(&media_id) {
         tasks.push(Task::done(Message::PlayDetailTrailer(media_id)));
     } else if let Some(Some(youtube_id)) = app.trailer_cache.get(&media_id) {
@@ -357,3 +487,103 @@ pub fn handle_detail_trailer_loaded(
 
     Task::done(Message::PlayDetailTrailer(media_id))
 }
+
+pub fn handle_detail_select_video(app: &mut Movix, index: usize) -> Task<Message> {
+    let Some(data) = &app.detail_popup_data else {
+        return Task::none();
+    };
+    let Some(video) = data.videos.get(index) else {
+        return Task::none();
+    };
+    let media_id = data.media_item.id;
+    let youtube_id = video.key.clone();
+    app.detail_video_index = Some(index);
+    if app.is_offline {
+        return Task::none();
+    }
+
+    let manager = app.trailer_manager.clone();
+    Task::perform(
+        async move { manager.get_stream_url(&youtube_id).await },
+        move |result| Message::DetailVideoLoaded(media_id, index, result),
+    )
+}
+
+pub fn handle_detail_video_loaded(
+    app: &mut Movix,
+    media_id: MediaId,
+    index: usize,
+    result: Result<String, String>,
+) -> Task<Message> {
+    let Ok(url) = result else {
+        return Task::none();
+    };
+    if app.detail_popup_media_id != Some(media_id) || app.detail_video_index != Some(index) {
+        return Task::none();
+    }
+    player_handlers::play_detail_video(app, media_id, &url);
+    Task::none()
+}
+
+pub fn handle_set_stream_provider(
+    app: &mut Movix,
+    media_id: MediaId,
+    provider: Option<String>,
+) -> Task<Message> {
+    match provider {
+        Some(name) => {
+            app.settings
+                .stream_provider_overrides
+                .insert(media_id, name);
+        }
+        None => {
+            app.settings.stream_provider_overrides.remove(&media_id);
+        }
+    }
+    let _ = app.settings.save();
+    Task::none()
+}
+
+/// Hands the link to the system browser. Only web links are opened, since
+/// the URL comes from TMDb data.
+pub fn handle_open_external_url(app: &mut Movix, url: &str) -> Task<Message> {
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Task::none();
+    }
+    if let Err(e) = open_in_browser(url) {
+        app.error_message = Some(format!("Couldn't open {}: {}", url, e));
+    }
+    Task::none()
+}
+
+/// The child is reaped on a separate thread so it doesn't linger as a zombie.
+fn open_in_browser(url: &str) -> std::io::Result<()> {
+    let mut child = browser_command(url)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn browser_command(url: &str) -> std::process::Command {
+    // `cmd /C start` would treat `&` in query strings as a command separator.
+    let mut command = std::process::Command::new("rundll32");
+    command.args(["url.dll,FileProtocolHandler", url]);
+    command
+}
+
+#[cfg(target_os = "macos")]
+fn browser_command(url: &str) -> std::process::Command {
+    let mut command = std::process::Command::new("open");
+    command.arg(url);
+    command
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn browser_command(url: &str) -> std::process::Command {
+    let mut command = std::process::Command::new("xdg-open");
+    command.arg(url);
+    command
+}
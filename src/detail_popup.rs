@@ -1,22 +1,34 @@
-use iced::widget::{button, column, container, row, scrollable, text, Column, Row, Space};
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, text, Column, Row, Space,
+};
 use iced::{Border, Color, Element, Length, Padding, Shadow};
 
+use crate::detail_sections::pill_button_style;
 use crate::media::{
-    DetailPopupData, MediaItem, MediaType, Message, NETFLIX_RED, SURFACE_DARK_GRAY, TEXT_GRAY,
-    TEXT_WHITE,
+    DetailPopupData, MediaItem, MediaType, Message, Page, NETFLIX_RED, SURFACE_DARK_GRAY,
+    TEXT_GRAY, TEXT_WHITE,
 };
 use crate::tmdb::ImageSize;
 use crate::Movix;
 
 const POPUP_WIDTH: f32 = 920.0;
+const OVERVIEW_PREVIEW_CHARS: usize = 400;
+const PAGE_MAX_WIDTH: f32 = 1280.0;
 const MINI_HERO_HEIGHT: f32 = 420.0;
 
 pub const ICON_X_LG: char = '\u{F659}';
 pub const ICON_PLAY_FILL: char = '\u{F4F4}';
 pub const ICON_PLUS_LG: char = '\u{F64D}';
+const ICON_CHECK_LG: char = '\u{F633}';
 pub const ICON_FILM: char = '\u{F3A9}';
 pub const ICON_PERSON_FILL: char = '\u{F4DA}';
 pub const ICON_GLOBE: char = '\u{F3EF}';
+const ICON_ARROW_LEFT: char = '\u{F12F}';
+const ICON_ARROWS_ANGLE_EXPAND: char = '\u{F136}';
+const ICON_CHEVRON_LEFT: char = '\u{F284}';
+const ICON_CHEVRON_RIGHT: char = '\u{F285}';
+
+const AUTOMATIC_STREAM_PROVIDER: &str = "Automatic";
 
 pub fn icon(icon_char: char) -> iced::widget::Text<'static> {
     text(icon_char.to_string()).font(iced::Font {
@@ -52,14 +64,6 @@ pub fn format_currency(amount: u64) -> String {
     format!("${}", formatted)
 }
 
-pub fn format_genres(genres: &[crate::media::Genre]) -> String {
-    genres
-        .iter()
-        .map(|g| g.name.as_str())
-        .collect::<Vec<_>>()
-        .join(", ")
-}
-
 pub fn format_episode_number(season: u32, episode: u32) -> String {
     format!("S{} E{}", season, episode)
 }
@@ -72,6 +76,13 @@ fn format_runtime(minutes: u32) -> String {
     }
 }
 
+fn format_media_runtime(media_item: &MediaItem) -> Option<String> {
+    match media_item.runtime_range {
+        Some((min, max)) => Some(format!("{}–{}", format_runtime(min), format_runtime(max))),
+        None => media_item.runtime.map(format_runtime),
+    }
+}
+
 pub fn hidden_scrollbar_style(
     _theme: &iced::Theme,
     _status: scrollable::Status,
@@ -378,6 +389,17 @@ impl Movix {
     }
 
     fn view_detail_popup_content(&self, data: &DetailPopupData) -> Element<'_, Message> {
+        scrollable(self.view_detail_sections(data))
+            .direction(scrollable::Direction::Vertical(
+                scrollable::Scrollbar::new().width(0).scroller_width(0),
+            ))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(hidden_scrollbar_style)
+            .into()
+    }
+
+    fn view_detail_sections(&self, data: &DetailPopupData) -> Column<'_, Message> {
         let mut sections: Vec<Element<Message>> = vec![self.view_detail_mini_hero(data)];
 
         if matches!(data.media_item.media_type, MediaType::TvSeries) && !data.seasons.is_empty() {
@@ -395,13 +417,64 @@ impl Movix {
 
         sections.push(self.view_detail_advanced_info(data));
 
-        scrollable(Column::with_children(sections).width(Length::Fill))
-            .direction(scrollable::Direction::Vertical(
-                scrollable::Scrollbar::new().width(0).scroller_width(0),
-            ))
+        Column::with_children(sections).width(Length::Fill)
+    }
+
+    pub fn view_detail_page(&self) -> Element<'_, Message> {
+        let back_button = button(
+            row![
+                icon(ICON_ARROW_LEFT).size(14).color(TEXT_WHITE),
+                text("Back").size(14).color(TEXT_WHITE)
+            ]
+            .spacing(8)
+            .align_y(iced::Alignment::Center),
+        )
+        .padding(Padding::new(8.0).left(16.0).right(16.0))
+        .style(|_theme, status| button::Style {
+            background: Some(iced::Background::Color(
+                if matches!(status, button::Status::Hovered) {
+                    Color::from_rgb(0.25, 0.25, 0.25)
+                } else {
+                    SURFACE_DARK_GRAY
+                },
+            )),
+            text_color: TEXT_WHITE,
+            border: Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            shadow: Shadow::default(),
+            snap: false,
+        })
+        .on_press(Message::NavigateTo(self.previous_page.clone()));
+
+        let body: Element<'_, Message> = match &self.detail_popup_data {
+            Some(data) => self.view_detail_sections(data).into(),
+            None => container(text("Loading...").size(16).color(TEXT_GRAY))
+                .width(Length::Fill)
+                .height(Length::Fixed(MINI_HERO_HEIGHT))
+                .center_x(Length::Fill)
+                .center_y(Length::Fill)
+                .style(|_theme| container::Style {
+                    background: Some(iced::Background::Color(SURFACE_DARK_GRAY)),
+                    border: Border {
+                        radius: 16.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .into(),
+        };
+
+        let page = column![back_button, body]
+            .spacing(24)
             .width(Length::Fill)
-            .height(Length::Fill)
-            .style(hidden_scrollbar_style)
+            .max_width(PAGE_MAX_WIDTH);
+
+        container(page)
+            .width(Length::Fill)
+            .center_x(Length::Fill)
+            .padding(Padding::new(100.0).left(48.0).right(48.0).bottom(48.0))
             .into()
     }
 
@@ -426,8 +499,13 @@ impl Movix {
                 ..Default::default()
             });
 
+        let mut layers = iced::widget::stack![backdrop, gradient];
+        if let Some(picker) = self.view_detail_video_picker(data) {
+            layers = layers.push(picker);
+        }
+
         container(
-            iced::widget::stack![backdrop, gradient]
+            layers
                 .width(Length::Fill)
                 .height(Length::Fixed(MINI_HERO_HEIGHT)),
         )
@@ -442,6 +520,77 @@ impl Movix {
         .into()
     }
 
+    /// Steps through the title's other trailers, teasers and featurettes.
+    fn view_detail_video_picker(&self, data: &DetailPopupData) -> Option<Element<'_, Message>> {
+        let count = data.videos.len();
+        if count < 2 {
+            return None;
+        }
+        let index = self.detail_video_index.unwrap_or(0).min(count - 1);
+        let video = &data.videos[index];
+        let label = if video.name.is_empty() {
+            video.video_type.clone()
+        } else {
+            video.name.clone()
+        };
+
+        let step_button = |glyph: char, target: usize| {
+            button(icon(glyph).size(14).color(TEXT_WHITE))
+                .padding([6, 10])
+                .style(|_theme, status| button::Style {
+                    background: Some(iced::Background::Color(
+                        if matches!(status, button::Status::Hovered) {
+                            Color::from_rgba(1.0, 1.0, 1.0, 0.2)
+                        } else {
+                            Color::TRANSPARENT
+                        },
+                    )),
+                    text_color: TEXT_WHITE,
+                    border: Border {
+                        radius: 14.0.into(),
+                        ..Default::default()
+                    },
+                    shadow: Shadow::default(),
+                    snap: false,
+                })
+                .on_press(Message::DetailSelectVideo(target))
+        };
+
+        let picker = row![
+            step_button(ICON_CHEVRON_LEFT, (index + count - 1) % count),
+            text(format!("{} · {}/{}", label, index + 1, count))
+                .size(13)
+                .color(TEXT_WHITE)
+                .wrapping(text::Wrapping::None),
+            step_button(ICON_CHEVRON_RIGHT, (index + 1) % count),
+        ]
+        .spacing(4)
+        .align_y(iced::Alignment::Center);
+
+        let pill = container(picker)
+            .max_width(420.0)
+            .clip(true)
+            .padding([2, 4])
+            .style(|_theme| container::Style {
+                background: Some(iced::Background::Color(Color::from_rgba(
+                    0.0, 0.0, 0.0, 0.6,
+                ))),
+                border: Border {
+                    radius: 18.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+        Some(
+            container(pill)
+                .width(Length::Fill)
+                .align_x(iced::alignment::Horizontal::Left)
+                .padding(Padding::new(20.0))
+                .into(),
+        )
+    }
+
     fn view_detail_backdrop(&self, media_item: &MediaItem) -> Element<'_, Message> {
         let media_id = media_item.id;
 
@@ -456,16 +605,8 @@ impl Movix {
             }
         }
 
-        let handle = media_item.backdrop_path.as_ref().and_then(|path| {
-            let url = self
-                .tmdb_client
-                .as_ref()?
-                .image_url(path, ImageSize::Backdrop);
-            self.image_cache.get(&url)
-        });
-
-        match handle {
-            Some(h) => iced::widget::image(h.clone())
+        match self.cached_backdrop(media_item) {
+            Some(h) => iced::widget::image(h)
                 .width(Length::Fill)
                 .height(Length::Fixed(MINI_HERO_HEIGHT))
                 .content_fit(iced::ContentFit::Cover)
@@ -537,23 +678,33 @@ impl Movix {
                 items.push(text("•").size(14).color(TEXT_GRAY).into());
             }
             items.push(
-                text(format_rating_with_star(media_item.vote_average))
-                    .size(14)
-                    .color(Color::from_rgb(1.0, 0.84, 0.0))
-                    .into(),
+                text(format!(
+                    "TMDb {}",
+                    format_rating_with_star(media_item.vote_average)
+                ))
+                .size(14)
+                .color(Color::from_rgb(1.0, 0.84, 0.0))
+                .into(),
             );
         }
 
-        if let Some(runtime) = media_item.runtime {
+        if let Some(ref rating) = self.detail_imdb_rating {
             if !items.is_empty() {
                 items.push(text("•").size(14).color(TEXT_GRAY).into());
             }
             items.push(
-                text(format_runtime(runtime))
+                text(format!("IMDb {}★", rating))
                     .size(14)
-                    .color(TEXT_GRAY)
+                    .color(Color::from_rgb(0.96, 0.77, 0.09))
                     .into(),
             );
+        }
+
+        if let Some(runtime) = format_media_runtime(media_item) {
+            if !items.is_empty() {
+                items.push(text("•").size(14).color(TEXT_GRAY).into());
+            }
+            items.push(text(runtime).size(14).color(TEXT_GRAY).into());
         } else if let Some(episodes) = media_item.number_of_episodes {
             if !items.is_empty() {
                 items.push(text("•").size(14).color(TEXT_GRAY).into());
@@ -601,9 +752,12 @@ impl Movix {
         })
         .on_press(Message::PlayContent(media_id));
 
+        let in_list = self.my_list.contains(media_id);
         let list = button(
             row![
-                icon(ICON_PLUS_LG).size(16).color(TEXT_WHITE),
+                icon(if in_list { ICON_CHECK_LG } else { ICON_PLUS_LG })
+                    .size(16)
+                    .color(TEXT_WHITE),
                 text("My List").size(16).color(TEXT_WHITE)
             ]
             .spacing(8)
@@ -630,12 +784,96 @@ impl Movix {
                 snap: false,
             }
         })
-        .on_press(Message::HoverCard(None));
+        .on_press(if in_list {
+            Message::RemoveFromList(media_id)
+        } else {
+            Message::AddToList(media_id)
+        });
 
-        row![play, list]
+        let mut buttons = row![play, list]
             .spacing(12)
-            .align_y(iced::Alignment::Center)
-            .into()
+            .align_y(iced::Alignment::Center);
+
+        if self.detail_popup_open {
+            let full_page = button(
+                row![
+                    icon(ICON_ARROWS_ANGLE_EXPAND).size(16).color(TEXT_WHITE),
+                    text("Full Page").size(16).color(TEXT_WHITE)
+                ]
+                .spacing(8)
+                .align_y(iced::Alignment::Center),
+            )
+            .padding(Padding::new(12.0).left(24.0).right(24.0))
+            .style(|_theme, status| {
+                let alpha = if matches!(status, button::Status::Hovered) {
+                    0.15
+                } else {
+                    0.1
+                };
+                button::Style {
+                    background: Some(iced::Background::Color(Color::from_rgba(
+                        1.0, 1.0, 1.0, alpha,
+                    ))),
+                    text_color: TEXT_WHITE,
+                    border: Border {
+                        color: Color::from_rgba(1.0, 1.0, 1.0, 0.3),
+                        width: 1.0,
+                        radius: 4.0.into(),
+                    },
+                    shadow: Shadow::default(),
+                    snap: false,
+                }
+            })
+            .on_press(Message::NavigateTo(Page::Detail(media_id)));
+            buttons = buttons.push(full_page);
+        }
+
+        if self.stream_provider_names.len() > 1 {
+            buttons = buttons.push(self.view_stream_provider_picker(media_id));
+        }
+
+        buttons.into()
+    }
+
+    fn view_stream_provider_picker(&self, media_id: u64) -> Element<'_, Message> {
+        let options: Vec<String> = std::iter::once(String::from(AUTOMATIC_STREAM_PROVIDER))
+            .chain(self.stream_provider_names.iter().cloned())
+            .collect();
+        let selected = self
+            .settings
+            .stream_provider_overrides
+            .get(&media_id)
+            .cloned()
+            .unwrap_or_else(|| String::from(AUTOMATIC_STREAM_PROVIDER));
+
+        pick_list(options, Some(selected), move |sel| {
+            let provider = (sel != AUTOMATIC_STREAM_PROVIDER).then_some(sel);
+            Message::SetStreamProvider(media_id, provider)
+        })
+        .text_size(14)
+        .padding(Padding::new(12.0).left(16.0).right(16.0))
+        .style(|_, _| pick_list::Style {
+            text_color: TEXT_WHITE,
+            placeholder_color: TEXT_GRAY,
+            handle_color: TEXT_WHITE,
+            background: iced::Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.1)),
+            border: Border {
+                color: Color::from_rgba(1.0, 1.0, 1.0, 0.3),
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+        })
+        .into()
+    }
+
+    /// Cards inside the detail view open the quick popup, or replace the page
+    /// when the full-page view is showing.
+    pub fn detail_card_message(&self, media_id: u64) -> Message {
+        if matches!(self.current_page, Page::Detail(_)) && !self.detail_popup_open {
+            Message::NavigateTo(Page::Detail(media_id))
+        } else {
+            Message::OpenDetailPopup(media_id)
+        }
     }
 
     fn view_detail_content_and_cast(&self, data: &DetailPopupData) -> Element<'_, Message> {
@@ -669,11 +907,46 @@ impl Movix {
         }
 
         if !media.overview.is_empty() {
+            let preview = truncate_overview(&media.overview, OVERVIEW_PREVIEW_CHARS);
+            let is_truncated = preview.is_some();
+            let overview = match preview {
+                Some(preview) if !self.detail_overview_expanded => preview,
+                _ => media.overview.clone(),
+            };
+            let mut overview_column = column![text(overview).size(16).color(TEXT_GRAY)].spacing(10);
+            if is_truncated {
+                let label = if self.detail_overview_expanded {
+                    "Show less"
+                } else {
+                    "Read more"
+                };
+                overview_column = overview_column.push(
+                    button(text(label).size(13).color(TEXT_WHITE))
+                        .padding(Padding::new(6.0).left(12.0).right(12.0))
+                        .style(pill_button_style)
+                        .on_press(Message::ToggleOverview),
+                );
+            }
+            items.push(overview_column.into());
+        }
+
+        for (label, job) in [("Directed by", "Director"), ("Created by", "Creator")] {
+            let names: Vec<&str> = data
+                .crew
+                .iter()
+                .filter(|c| c.job == job)
+                .map(|c| c.name.as_str())
+                .collect();
+            if names.is_empty() {
+                continue;
+            }
             items.push(
-                text(media.overview.clone())
-                    .size(16)
-                    .color(TEXT_GRAY)
-                    .into(),
+                row![
+                    text(format!("{}:", label)).size(14).color(TEXT_GRAY),
+                    text(names.join(", ")).size(14).color(TEXT_WHITE)
+                ]
+                .spacing(6)
+                .into(),
             );
         }
 
@@ -699,24 +972,40 @@ impl Movix {
             if !items.is_empty() {
                 items.push(text("•").size(14).color(TEXT_GRAY).into());
             }
-            items.push(
-                text(format_genres(&media_item.genres))
-                    .size(14)
-                    .color(TEXT_GRAY)
-                    .into(),
-            );
+            for genre in &media_item.genres {
+                items.push(
+                    button(text(genre.name.clone()).size(13).color(TEXT_WHITE))
+                        .padding(Padding::new(4.0).left(10.0).right(10.0))
+                        .style(|_theme, status| button::Style {
+                            background: Some(iced::Background::Color(Color::from_rgba(
+                                1.0,
+                                1.0,
+                                1.0,
+                                if matches!(status, button::Status::Hovered) {
+                                    0.2
+                                } else {
+                                    0.1
+                                },
+                            ))),
+                            text_color: TEXT_WHITE,
+                            border: Border {
+                                radius: 12.0.into(),
+                                ..Default::default()
+                            },
+                            shadow: Shadow::default(),
+                            snap: false,
+                        })
+                        .on_press(Message::BrowseGenre(genre.id))
+                        .into(),
+                );
+            }
         }
 
-        if let Some(runtime) = media_item.runtime {
+        if let Some(runtime) = format_media_runtime(media_item) {
             if !items.is_empty() {
                 items.push(text("•").size(14).color(TEXT_GRAY).into());
             }
-            items.push(
-                text(format_runtime(runtime))
-                    .size(14)
-                    .color(TEXT_GRAY)
-                    .into(),
-            );
+            items.push(text(runtime).size(14).color(TEXT_GRAY).into());
         }
 
         if media_item.vote_average > 0.0 {
@@ -737,3 +1026,15 @@ impl Movix {
             .into()
     }
 }
+
+/// Cuts at the last word boundary within `max_chars`, or returns `None` when
+/// the text already fits.
+pub fn truncate_overview(overview: &str, max_chars: usize) -> Option<String> {
+    let (cut, _) = overview.char_indices().nth(max_chars)?;
+    let head = &overview[..cut];
+    let head = head.rfind(char::is_whitespace).map_or(head, |i| &head[..i]);
+    Some(format!(
+        "{}…",
+        head.trim_end_matches(|c: char| c.is_whitespace() || c == ',')
+    ))
+}
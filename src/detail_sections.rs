@@ -4,17 +4,21 @@ use iced::widget::{
 use iced::{Border, Color, Element, Length, Padding, Shadow};
 
 use crate::detail_popup::{
-    format_episode_number, format_full_date, hidden_scrollbar_style, icon, ICON_FILM, ICON_GLOBE,
-    ICON_PERSON_FILL, ICON_PLAY_FILL,
+    format_episode_number, format_full_date, hidden_scrollbar_style, icon, truncate_overview,
+    ICON_FILM, ICON_GLOBE, ICON_PERSON_FILL, ICON_PLAY_FILL,
 };
 use crate::media::{
-    CastMember, Collection, Episode, ExternalIds, Keyword, MediaItem, Message, ProductionCompany,
-    SURFACE_DARK_GRAY, TEXT_GRAY, TEXT_WHITE,
+    CastMember, Collection, Episode, ExternalIds, Keyword, MediaItem, Message, Page,
+    ProductionCompany, SURFACE_DARK_GRAY, TEXT_GRAY, TEXT_WHITE,
 };
 use crate::tmdb::ImageSize;
 use crate::Movix;
 
 const ICON_INFO_CIRCLE: char = '\u{F431}';
+const ICON_CHEVRON_DOWN: char = '\u{F282}';
+const ICON_CHEVRON_UP: char = '\u{F286}';
+const ICON_EYE_SLASH: char = '\u{F340}';
+const EPISODE_OVERVIEW_CHARS: usize = 120;
 
 fn rounded_style(radius: f32, bg: Option<Color>) -> container::Style {
     container::Style {
@@ -27,7 +31,7 @@ fn rounded_style(radius: f32, bg: Option<Color>) -> container::Style {
     }
 }
 
-fn pill_button_style(_theme: &iced::Theme, status: button::Status) -> button::Style {
+pub fn pill_button_style(_theme: &iced::Theme, status: button::Status) -> button::Style {
     let alpha = if matches!(status, button::Status::Hovered) {
         0.15
     } else {
@@ -68,7 +72,7 @@ impl Movix {
             .into()
     }
 
-    fn get_cached_image(
+    pub fn get_cached_image(
         &self,
         path: Option<&String>,
         size: ImageSize,
@@ -77,7 +81,22 @@ impl Movix {
         self.image_cache.get(&url).cloned()
     }
 
-    fn image_or_placeholder<'a>(
+    /// Falls back to the poster, cropped to fit, for titles without a backdrop.
+    pub fn backdrop_url(&self, media_item: &MediaItem) -> Option<String> {
+        let client = self.tmdb_client.as_ref()?;
+        match (&media_item.backdrop_path, &media_item.poster_path) {
+            (Some(path), _) => Some(client.image_url(path, ImageSize::Backdrop)),
+            (None, Some(path)) => Some(client.image_url(path, ImageSize::Poster)),
+            (None, None) => None,
+        }
+    }
+
+    pub fn cached_backdrop(&self, media_item: &MediaItem) -> Option<iced::widget::image::Handle> {
+        let url = self.backdrop_url(media_item)?;
+        self.image_cache.get(&url).cloned()
+    }
+
+    pub fn image_or_placeholder<'a>(
         handle: Option<iced::widget::image::Handle>,
         width: f32,
         height: f32,
@@ -151,7 +170,16 @@ impl Movix {
         ]
         .align_y(iced::Alignment::Center);
 
-        let episodes: Element<Message> = if self.detail_episodes.is_empty() {
+        let episodes: Element<Message> = if self.detail_episodes_loading {
+            let skeletons: Vec<Element<Message>> = (0..5)
+                .map(|_| Self::view_detail_episode_skeleton_card())
+                .collect();
+            Self::horizontal_scroll(
+                Row::with_children(skeletons)
+                    .spacing(12)
+                    .align_y(iced::Alignment::Start),
+            )
+        } else if self.detail_episodes.is_empty() {
             container(text("No episodes available").size(14).color(TEXT_GRAY))
                 .padding(16.0)
                 .into()
@@ -175,15 +203,45 @@ impl Movix {
             .into()
     }
 
+    fn view_detail_episode_skeleton_card() -> Element<'static, Message> {
+        column![
+            container(Space::new().width(160).height(90))
+                .style(|_| rounded_style(4.0, Some(Color::from_rgb(0.15, 0.15, 0.15)))),
+            container(Space::new().width(90).height(13))
+                .style(|_| rounded_style(4.0, Some(Color::from_rgb(0.2, 0.2, 0.2)))),
+            container(Space::new().width(130).height(14))
+                .style(|_| rounded_style(4.0, Some(Color::from_rgb(0.18, 0.18, 0.18)))),
+        ]
+        .spacing(6)
+        .width(Length::Fixed(160.0))
+        .into()
+    }
+
+    /// With spoilers hidden, episodes the user hasn't started show neither
+    /// still nor overview until clicked.
     fn view_detail_episode_card(&self, episode: &Episode) -> Element<'_, Message> {
-        let handle = self.get_cached_image(episode.still_path.as_ref(), ImageSize::Backdrop);
-        let still = Self::image_or_placeholder(
-            handle,
-            160.0,
-            90.0,
-            4.0,
-            icon(ICON_FILM).size(24).color(TEXT_GRAY).into(),
-        );
+        let hide_spoilers = self.settings.hide_episode_spoilers
+            && !self.detail_revealed_episodes.contains(&episode.id)
+            && self.movie_player.get_stored_position(episode.id).is_none();
+
+        let still = if hide_spoilers {
+            Self::image_or_placeholder(
+                None,
+                160.0,
+                90.0,
+                4.0,
+                icon(ICON_EYE_SLASH).size(24).color(TEXT_GRAY).into(),
+            )
+        } else {
+            let handle = self.get_cached_image(episode.still_path.as_ref(), ImageSize::Backdrop);
+            Self::image_or_placeholder(
+                handle,
+                160.0,
+                90.0,
+                4.0,
+                icon(ICON_FILM).size(24).color(TEXT_GRAY).into(),
+            )
+        };
 
         let air_date = episode
             .air_date
@@ -209,19 +267,45 @@ impl Movix {
         )
         .max_width(148.0);
 
-        container(
-            column![still, meta, title]
-                .spacing(6)
-                .width(Length::Fixed(160.0)),
-        )
-        .width(Length::Fixed(160.0))
-        .into()
+        let overview = if hide_spoilers {
+            String::from("Hidden to avoid spoilers")
+        } else {
+            truncate_overview(&episode.overview, EPISODE_OVERVIEW_CHARS)
+                .unwrap_or_else(|| episode.overview.clone())
+        };
+        let mut details = column![still, meta, title]
+            .spacing(6)
+            .width(Length::Fixed(160.0));
+        if !overview.is_empty() {
+            details = details.push(
+                container(
+                    text(overview)
+                        .size(12)
+                        .color(TEXT_GRAY)
+                        .wrapping(text::Wrapping::Word),
+                )
+                .max_width(148.0),
+            );
+        }
+
+        let card = container(details).width(Length::Fixed(160.0));
+        let press = if hide_spoilers {
+            Message::RevealEpisode(episode.id)
+        } else {
+            Message::PlayEpisode(episode.id, episode.season_number, episode.episode_number)
+        };
+        iced::widget::mouse_area(card)
+            .interaction(iced::mouse::Interaction::Pointer)
+            .on_press(press)
+            .into()
     }
 
     pub fn view_detail_cast_section(&self, cast: &[CastMember]) -> Element<'_, Message> {
+        let expanded = self.detail_cast_expanded;
+        let visible = if expanded { cast.len() } else { 4 };
         let list: Vec<Element<Message>> = cast
             .iter()
-            .take(4)
+            .take(visible)
             .map(|m| {
                 let handle = self.get_cached_image(m.profile_path.as_ref(), ImageSize::Poster);
                 let profile = Self::image_or_placeholder(
@@ -231,7 +315,7 @@ impl Movix {
                     25.0,
                     icon(ICON_PERSON_FILL).size(20).color(TEXT_GRAY).into(),
                 );
-                row![
+                let entry = row![
                     profile,
                     column![
                         Self::bold_text(&m.name, 14, TEXT_WHITE),
@@ -240,18 +324,40 @@ impl Movix {
                     .spacing(2)
                 ]
                 .spacing(12)
-                .align_y(iced::Alignment::Center)
-                .into()
+                .align_y(iced::Alignment::Center);
+                iced::widget::mouse_area(entry)
+                    .interaction(iced::mouse::Interaction::Pointer)
+                    .on_press(Message::NavigateTo(Page::Person(m.id)))
+                    .into()
             })
             .collect();
 
-        column![
-            Self::bold_text("Top Cast", 16, TEXT_WHITE),
-            Column::with_children(list).spacing(16)
-        ]
-        .spacing(16)
-        .width(Length::FillPortion(1))
-        .into()
+        let list = Column::with_children(list).spacing(16);
+        let list: Element<Message> = if expanded {
+            scrollable(list)
+                .height(Length::Fixed(320.0))
+                .style(hidden_scrollbar_style)
+                .into()
+        } else {
+            list.into()
+        };
+
+        let mut content = column![Self::bold_text("Top Cast", 16, TEXT_WHITE), list].spacing(16);
+        if cast.len() > 4 {
+            let label = if expanded {
+                "Show less"
+            } else {
+                "See full cast"
+            };
+            content = content.push(
+                button(text(label).size(13).color(TEXT_WHITE))
+                    .padding(Padding::new(6.0).left(12.0).right(12.0))
+                    .style(pill_button_style)
+                    .on_press(Message::ToggleDetailCast),
+            );
+        }
+
+        content.width(Length::FillPortion(1)).into()
     }
 
     pub fn view_detail_collection_section(&self, collection: &Collection) -> Element<'_, Message> {
@@ -346,7 +452,7 @@ impl Movix {
         iced::widget::mouse_area(card)
             .on_enter(Message::DetailHoverCard(Some(media_id)))
             .on_exit(Message::DetailHoverCard(None))
-            .on_press(Message::OpenDetailPopup(media_id))
+            .on_press(self.detail_card_message(media_id))
             .into()
     }
 
@@ -424,12 +530,12 @@ impl Movix {
         iced::widget::mouse_area(card)
             .on_enter(Message::DetailHoverCard(Some(media_id)))
             .on_exit(Message::DetailHoverCard(None))
-            .on_press(Message::OpenDetailPopup(media_id))
+            .on_press(self.detail_card_message(media_id))
             .into()
     }
 
     fn view_card_backdrop(&self, media_item: &MediaItem, w: f32, h: f32) -> Element<'_, Message> {
-        match self.get_cached_image(media_item.backdrop_path.as_ref(), ImageSize::Backdrop) {
+        match self.cached_backdrop(media_item) {
             Some(h_img) => container(
                 iced::widget::image(h_img)
                     .width(Length::Fixed(w))
@@ -529,7 +635,7 @@ impl Movix {
                 snap: false,
             }
         })
-        .on_press(Message::OpenDetailPopup(media_id))
+        .on_press(self.detail_card_message(media_id))
         .into()
     }
 
@@ -537,15 +643,42 @@ impl Movix {
         &self,
         data: &crate::media::DetailPopupData,
     ) -> Element<'_, Message> {
-        let mut sections: Vec<Element<'_, Message>> = vec![
-            self.view_detail_social_links(&data.external_ids),
-            self.view_detail_info_grid(data),
-        ];
-        if !data.keywords.is_empty() {
-            sections.push(self.view_detail_keywords(&data.keywords));
-        }
-        if !data.production_companies.is_empty() {
-            sections.push(self.view_detail_production_companies(&data.production_companies));
+        let expanded = self.detail_advanced_expanded;
+        let toggle = button(
+            row![
+                Self::bold_text("Details", 16, TEXT_WHITE),
+                Space::new().width(Length::Fill),
+                icon(if expanded {
+                    ICON_CHEVRON_UP
+                } else {
+                    ICON_CHEVRON_DOWN
+                })
+                .size(16)
+                .color(TEXT_WHITE)
+            ]
+            .align_y(iced::Alignment::Center),
+        )
+        .width(Length::Fill)
+        .padding(0)
+        .style(|_, _| button::Style {
+            background: None,
+            text_color: TEXT_WHITE,
+            border: Border::default(),
+            shadow: Shadow::default(),
+            snap: false,
+        })
+        .on_press(Message::ToggleDetailAdvanced);
+
+        let mut sections: Vec<Element<'_, Message>> = vec![toggle.into()];
+        if expanded {
+            sections.push(self.view_detail_social_links(&data.external_ids));
+            sections.push(self.view_detail_info_grid(data));
+            if !data.keywords.is_empty() {
+                sections.push(self.view_detail_keywords(&data.keywords));
+            }
+            if !data.production_companies.is_empty() {
+                sections.push(self.view_detail_production_companies(&data.production_companies));
+            }
         }
         container(
             Column::with_children(sections)
@@ -559,22 +692,16 @@ impl Movix {
     }
 
     pub fn view_detail_social_links(&self, ids: &ExternalIds) -> Element<'_, Message> {
-        let mut links: Vec<Element<'_, Message>> = Vec::new();
-        if ids.imdb_id.is_some() {
-            links.push(self.social_link_button("IMDB", None));
-        }
-        if ids.facebook_id.is_some() {
-            links.push(self.social_link_button("Facebook", None));
-        }
-        if ids.twitter_id.is_some() {
-            links.push(self.social_link_button("Twitter", None));
-        }
-        if ids.instagram_id.is_some() {
-            links.push(self.social_link_button("Instagram", None));
-        }
-        if ids.homepage.is_some() {
-            links.push(self.social_link_button("Homepage", Some(ICON_GLOBE)));
-        }
+        let links: Vec<Element<'_, Message>> = [
+            ("IMDB", None, ids.imdb_url()),
+            ("Facebook", None, ids.facebook_url()),
+            ("Twitter", None, ids.twitter_url()),
+            ("Instagram", None, ids.instagram_url()),
+            ("Homepage", Some(ICON_GLOBE), ids.homepage_url()),
+        ]
+        .into_iter()
+        .filter_map(|(label, ic, url)| Some(self.social_link_button(label, ic, url?)))
+        .collect();
 
         if links.is_empty() {
             return Space::new().width(0).height(0).into();
@@ -585,7 +712,12 @@ impl Movix {
             .into()
     }
 
-    fn social_link_button(&self, label: &'static str, ic: Option<char>) -> Element<'_, Message> {
+    fn social_link_button(
+        &self,
+        label: &'static str,
+        ic: Option<char>,
+        url: String,
+    ) -> Element<'_, Message> {
         let content: Element<Message> = match ic {
             Some(c) => row![
                 icon(c).size(14).color(TEXT_WHITE),
@@ -599,7 +731,7 @@ impl Movix {
         button(content)
             .padding(Padding::new(8.0).left(16.0).right(16.0))
             .style(pill_button_style)
-            .on_press(Message::HoverCard(None))
+            .on_press(Message::OpenExternalUrl(url))
             .into()
     }
 
@@ -613,6 +745,12 @@ impl Movix {
         if let Some(ref lang) = media.original_language {
             items.push(("Original Language", lang.to_uppercase()));
         }
+        if !media.production_countries.is_empty() {
+            items.push(("Country", media.production_countries.join(", ")));
+        }
+        if !media.spoken_languages.is_empty() {
+            items.push(("Languages", media.spoken_languages.join(", ")));
+        }
         if let Some(b) = media.budget.filter(|&b| b > 0) {
             items.push(("Budget", crate::detail_popup::format_currency(b)));
         }
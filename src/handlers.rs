@@ -1,15 +1,34 @@
 use iced::Task;
 
 use crate::detail_handlers;
+use crate::hero::{sample_backdrop_color, HERO_HEIGHT};
 use crate::media::{
-    section_id, ApiError, Genre, LoadingState, MediaId, MediaTypeFilter, Message, NavItem, Page,
-    ScrollDirection, SearchFilters, SortOption,
+    is_local_media_id, load_offline_snapshot, save_offline_snapshot, section_id, ApiError,
+    Category, ContentSection, Genre, GenreMatch, GenreScope, LaunchAction, LaunchRequest,
+    LoadingState, MediaId, MediaType, MediaTypeFilter, Message, NavItem, Page, PageScroll,
+    ProfileAction, ScrollDirection, SearchFilters, SearchPage, SectionQuery, SortOption,
+    HERO_SNAPSHOT, MAIN_SCROLL_ID, SECTIONS_SNAPSHOT,
 };
 use crate::player_handlers;
-use crate::tmdb::{fetch_image_bytes, load_hero_content, load_initial_content, ImageSize};
+use crate::settings::{AppSettings, SetupMessage, SetupPage};
+use crate::tmdb::{
+    load_hero_content, load_initial_content, load_section, ImageSize, TmdbClient, WatchHistory,
+};
 use crate::video::{select_best_trailer, TrailerVideo};
 use crate::Movix;
 
+const LOGO_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+const IMAGE_RETRY_BASE_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+const OFFLINE_FAILURE_THRESHOLD: u32 = 3;
+const WHEEL_LINE_PIXELS: f32 = 120.0;
+/// The hero counts as visible until this much of it has scrolled out of view.
+const HERO_VISIBLE_FRACTION: f32 = 0.5;
+const LOADING_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(20);
+/// How close to the bottom of the search results the next page is requested.
+const SEARCH_NEXT_PAGE_DISTANCE: f32 = 600.0;
+/// Filter changes are written once they settle, not on every slider step.
+const SEARCH_PREFERENCES_SAVE_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
 pub fn handle_message(app: &mut Movix, message: Message) -> Task<Message> {
     match message {
         Message::Setup(_) => Task::none(),
@@ -17,6 +36,7 @@ pub fn handle_message(app: &mut Movix, message: Message) -> Task<Message> {
         Message::SearchQueryChanged(query) => handle_search_query_changed(app, query),
         Message::SearchSubmit => handle_search_submit(app),
         Message::SearchResultsLoaded(result) => handle_search_results(app, result),
+        Message::SearchNextPageLoaded(query, result) => handle_search_next_page(app, query, result),
         Message::ToggleProfileMenu => {
             app.profile_menu_open = !app.profile_menu_open;
             Task::none()
@@ -25,12 +45,40 @@ pub fn handle_message(app: &mut Movix, message: Message) -> Task<Message> {
             app.profile_menu_open = false;
             Task::none()
         }
+        Message::ProfileAction(ProfileAction::OpenSettings) => {
+            app.profile_menu_open = false;
+            app.hero_player.pause();
+            app.card_player.stop();
+            let mut setup = SetupPage::from_settings(&app.settings);
+            setup.cache_stats = app.tmdb_client.as_ref().map(TmdbClient::cache_stats);
+            app.setup_page = Some(setup);
+            load_watch_providers(app)
+        }
+        Message::ProfileAction(ProfileAction::OpenFile) => {
+            app.profile_menu_open = false;
+            app.open_file_path = Some(String::new());
+            app.open_file_error = None;
+            Task::none()
+        }
         Message::ProfileAction(_) => {
             app.profile_menu_open = false;
             Task::none()
         }
         Message::PlayContent(id) => player_handlers::handle_play_content(app, id),
+        Message::OpenExternalUrl(url) => detail_handlers::handle_open_external_url(app, &url),
+        Message::PlayEpisode(id, season, episode) => {
+            player_handlers::handle_play_episode(app, id, season, episode)
+        }
         Message::ShowMoreInfo(id) => Task::done(Message::OpenDetailPopup(id)),
+        Message::AddToList(id) => handle_add_to_list(app, id),
+        Message::RetrySection(index) => handle_retry_section(app, index),
+        Message::SectionLoaded(index, query, result) => {
+            handle_section_loaded(app, index, query, result)
+        }
+        Message::RemoveFromList(id) => {
+            app.my_list.remove(id);
+            Task::none()
+        }
         Message::HoverCard(id) => handle_hover_card(app, id),
         Message::HoverCardDelayed(media_id) => handle_hover_card_delayed(app, media_id),
         Message::HoverSection(idx) => {
@@ -42,11 +90,59 @@ pub fn handle_message(app: &mut Movix, message: Message) -> Task<Message> {
         }
         Message::ContentLoaded(result) => handle_content_loaded(app, result),
         Message::HeroLoaded(result) => handle_hero_loaded(app, result),
+        Message::HeroRotateTick => handle_hero_rotate_tick(app),
+        Message::LoadingWatchdogTick => handle_loading_watchdog_tick(app),
+        Message::HeroNext => {
+            let next = (app.hero_index + 1) % app.hero_items.len().max(1);
+            handle_hero_select(app, next)
+        }
+        Message::HeroPrevious => {
+            let len = app.hero_items.len().max(1);
+            handle_hero_select(app, (app.hero_index + len - 1) % len)
+        }
+        Message::HeroSelect(index) => handle_hero_select(app, index),
+        Message::ShuffleHero => handle_shuffle_hero(app),
+        Message::Launch(request) => handle_launch(app, request),
+        Message::LaunchDetailsLoaded(result) => {
+            track_connectivity(app, &*result);
+            match *result {
+                Ok(item) => player_handlers::play_media(app, item.id, item.title, item.media_type),
+                Err(error) => {
+                    app.error_message = Some(format!("{:?}", error));
+                    Task::none()
+                }
+            }
+        }
+        Message::HeroShuffled(result) => handle_hero_shuffled(app, result),
+        Message::HeroColorSampled(url, color) => handle_hero_color_sampled(app, url, color),
+        Message::HeroGradientTick => handle_hero_gradient_tick(app),
         Message::ImageLoaded(url, result) => handle_image_loaded(app, url, result),
+        Message::FetchLogo(media_id, media_type) => handle_fetch_logo(app, media_id, media_type),
         Message::LogoLoaded(media_id, result) => handle_logo_loaded(app, media_id, result),
         Message::LoadImage(url) => handle_load_image(app, url),
+        Message::LoadRequestedImages => Task::batch(
+            app.image_cache
+                .take_requests()
+                .into_iter()
+                .map(|url| Task::done(Message::LoadImage(url))),
+        ),
+        Message::RetryImage(url) => {
+            app.image_cache.clear_pending(&url);
+            handle_load_image(app, url)
+        }
         Message::RetryLoad => handle_retry_load(app),
+        Message::CheckConnectivity => handle_check_connectivity(app),
+        Message::ConnectivityChecked(reachable) => handle_connectivity_checked(app, reachable),
+        Message::ProxyChecked(result) => {
+            app.proxy_error = result.err();
+            Task::none()
+        }
         Message::ScrollSection(idx, dir) => handle_scroll_section(app, idx, dir),
+        Message::WheelScrollSection(idx, delta) => handle_wheel_scroll_section(app, idx, delta),
+        Message::ModifiersChanged(modifiers) => {
+            app.keyboard_modifiers = modifiers;
+            Task::none()
+        }
         Message::AnimateScroll(idx) => handle_animate_scroll(app, idx),
         Message::SectionScrolled(idx, offset) => handle_section_scrolled(app, idx, offset),
         Message::TrailerVideosLoaded(id, result) => handle_trailer_videos_loaded(app, id, result),
@@ -68,7 +164,16 @@ pub fn handle_message(app: &mut Movix, message: Message) -> Task<Message> {
         Message::PauseHeroTrailer => player_handlers::handle_pause_hero_trailer(app),
         Message::ResumeHeroTrailer => player_handlers::handle_resume_hero_trailer(app),
         Message::HeroVisibilityChanged(visible) => handle_hero_visibility(app, visible),
-        Message::MainScrolled(offset) => handle_main_scrolled(app, offset),
+        Message::MainScrolled(offset, viewport_height, content_height) => {
+            app.main_viewport_height = viewport_height;
+            let next_page = load_next_search_page(app, offset + viewport_height, content_height);
+            Task::batch([handle_main_scrolled(app, offset), next_page])
+        }
+        Message::WindowResized(size) => {
+            app.window_height = size.height;
+            Task::none()
+        }
+        Message::PageScroll(scroll) => handle_page_scroll(app, scroll),
         Message::ToggleHeroMute => player_handlers::handle_toggle_hero_mute(app),
         Message::ReplayHeroTrailer => player_handlers::handle_replay_hero_trailer(app),
         Message::HeroVideoEnded => {
@@ -81,29 +186,79 @@ pub fn handle_message(app: &mut Movix, message: Message) -> Task<Message> {
         Message::MoviePlayerStreamResolved(id, result) => {
             player_handlers::handle_movie_stream_resolved(app, id, result)
         }
+        Message::MoviePlayerTrailerResolved(id, result) => {
+            player_handlers::handle_movie_trailer_resolved(app, id, result)
+        }
         Message::MoviePlayerClose => player_handlers::handle_movie_player_close(app),
+        Message::MoviePlayerReportBroken => player_handlers::handle_movie_report_broken(app),
+        Message::MoviePlayerRetry => player_handlers::handle_movie_retry(app),
         Message::MoviePlayerTogglePlay => player_handlers::handle_movie_toggle_play(app),
         Message::MoviePlayerSeek(pos) => player_handlers::handle_movie_seek(app, pos),
         Message::MoviePlayerSeekRelative(delta) => {
             player_handlers::handle_movie_seek_relative(app, delta)
         }
         Message::MoviePlayerSetVolume(vol) => player_handlers::handle_movie_set_volume(app, vol),
+        Message::MoviePlayerSetSpeed(speed) => {
+            app.movie_player.set_speed(speed);
+            Task::none()
+        }
+        Message::MoviePlayerSetQuality(quality) => {
+            player_handlers::handle_movie_set_quality(app, quality)
+        }
+        Message::SaveVolumeTick => player_handlers::handle_save_volume_tick(app),
+        Message::SaveSearchPreferencesTick => handle_save_search_preferences_tick(app),
+        Message::MoviePlayerAdjustVolume(delta) => {
+            let volume = (app.movie_player_volume + delta).clamp(0.0, 1.0);
+            player_handlers::handle_movie_set_volume(app, volume)
+        }
         Message::MoviePlayerToggleMute => player_handlers::handle_movie_toggle_mute(app),
-        Message::MoviePlayerToggleFullscreen => Task::none(),
+        Message::MoviePlayerToggleFullscreen => {
+            player_handlers::handle_movie_toggle_fullscreen(app)
+        }
+        Message::MoviePlayerEscape => player_handlers::handle_movie_escape(app),
+        Message::MoviePlayerSetSubtitle(source) => {
+            player_handlers::handle_movie_set_subtitle(app, source)
+        }
+        Message::MoviePlayerSubtitlesLoaded(source, result) => {
+            player_handlers::handle_movie_subtitles_loaded(app, source, result)
+        }
+        Message::WindowIdentified(id) => {
+            app.window_id = Some(id);
+            Task::none()
+        }
+        Message::MoviePlayerToggleMetrics => player_handlers::handle_movie_toggle_metrics(app),
+        Message::MoviePlayerAutoSave => player_handlers::handle_movie_auto_save(app),
         Message::MoviePlayerFrameTick => {
             player_handlers::handle_movie_frame_tick(app);
             Task::none()
         }
         Message::MoviePlayerShowControls => player_handlers::handle_movie_show_controls(app),
         Message::MoviePlayerHideControls => player_handlers::handle_movie_hide_controls(app),
+        Message::MoviePlayerResumeStored => player_handlers::handle_movie_resume_stored(app),
+        Message::MoviePlayerStartOver => player_handlers::handle_movie_start_over(app),
         Message::OpenDetailPopup(id) => detail_handlers::handle_open_detail_popup(app, id),
         Message::CloseDetailPopup => detail_handlers::handle_close_detail_popup(app),
+        Message::DismissWhatsNew => handle_dismiss_whats_new(app),
+        Message::OpenFilePathChanged(path) => {
+            app.open_file_path = Some(path);
+            app.open_file_error = None;
+            Task::none()
+        }
+        Message::OpenFileSubmit => player_handlers::handle_open_local_file(app),
+        Message::CloseOpenFileDialog => {
+            app.open_file_path = None;
+            app.open_file_error = None;
+            Task::none()
+        }
         Message::DetailDataLoaded(result) => {
             detail_handlers::handle_detail_data_loaded(app, result)
         }
         Message::DetailSelectSeason(season) => {
             detail_handlers::handle_detail_select_season(app, season)
         }
+        Message::SetStreamProvider(id, provider) => {
+            detail_handlers::handle_set_stream_provider(app, id, provider)
+        }
         Message::DetailEpisodesLoaded(result) => {
             detail_handlers::handle_detail_episodes_loaded(app, result)
         }
@@ -115,10 +270,46 @@ pub fn handle_message(app: &mut Movix, message: Message) -> Task<Message> {
         Message::DetailTrailerLoaded(id, result) => {
             detail_handlers::handle_detail_trailer_loaded(app, id, result)
         }
+        Message::DetailSelectVideo(index) => {
+            detail_handlers::handle_detail_select_video(app, index)
+        }
+        Message::DetailVideoLoaded(id, index, result) => {
+            detail_handlers::handle_detail_video_loaded(app, id, index, result)
+        }
+        Message::ToggleDetailAdvanced => {
+            app.detail_advanced_expanded = !app.detail_advanced_expanded;
+            Task::none()
+        }
+        Message::BrowseGenre(genre_id) => handle_browse_genre(app, genre_id),
+        Message::GenreBrowseLoaded(result) => handle_genre_browse_loaded(app, result),
+        Message::RetryGenreBrowse => fetch_genre_browse(app),
+        Message::CloseGenreBrowse => {
+            clear_genre_browse(app);
+            Task::none()
+        }
+        Message::PopularPeopleLoaded(result) => handle_popular_people_loaded(app, result),
+        Message::PopularSuggestionsLoaded(result) => handle_popular_suggestions_loaded(app, result),
+        Message::PersonDetailLoaded(person_id, result) => {
+            handle_person_detail_loaded(app, person_id, result)
+        }
+        Message::DetailImdbRatingLoaded(imdb_id, result) => {
+            detail_handlers::handle_detail_imdb_rating_loaded(app, imdb_id, result)
+        }
+        Message::ToggleDetailCast => detail_handlers::handle_toggle_detail_cast(app),
+        Message::RevealEpisode(episode_id) => {
+            app.detail_revealed_episodes.insert(episode_id);
+            Task::none()
+        }
+        Message::ToggleOverview => {
+            app.detail_overview_expanded = !app.detail_overview_expanded;
+            Task::none()
+        }
         Message::SearchDebounceTriggered => handle_search_debounce_triggered(app),
         Message::ClearSearch => handle_clear_search(app),
         Message::SetMediaTypeFilter(filter) => handle_set_media_type_filter(app, filter),
         Message::SetGenreFilter(genre_id) => handle_set_genre_filter(app, genre_id),
+        Message::ToggleGenreFilter(genre_id) => handle_toggle_genre_filter(app, genre_id),
+        Message::SetGenreMatch(genre_match) => handle_set_genre_match(app, genre_match),
         Message::SetYearFrom(year) => handle_set_year_from(app, year),
         Message::SetYearTo(year) => handle_set_year_to(app, year),
         Message::SetMinRating(rating) => handle_set_min_rating(app, rating),
@@ -129,23 +320,153 @@ pub fn handle_message(app: &mut Movix, message: Message) -> Task<Message> {
 }
 
 fn handle_navigate(app: &mut Movix, page: Page) -> Task<Message> {
+    let was_detail = matches!(app.current_page, Page::Detail(_));
+    if !was_detail {
+        app.previous_page = app.current_page.clone();
+    }
     app.current_page = page.clone();
     app.profile_menu_open = false;
+    clear_genre_browse(app);
     app.header_state.active_nav = match page {
         Page::Home => NavItem::Home,
         Page::Series => NavItem::Series,
         Page::Movies => NavItem::Movies,
         Page::MostRecent => NavItem::MostRecent,
         Page::MyList => NavItem::MyList,
+        Page::People | Page::Person(_) => NavItem::People,
         Page::Detail(_) => app.header_state.active_nav.clone(),
     };
+
+    match page {
+        Page::Detail(media_id) => {
+            let _ = handle_clear_search(app);
+            detail_handlers::open_detail_page(app, media_id)
+        }
+        Page::People | Page::Person(_) => {
+            let _ = handle_clear_search(app);
+            app.hero_player.pause();
+            let close_detail = if was_detail || app.detail_popup_open {
+                detail_handlers::handle_close_detail_popup(app)
+            } else {
+                Task::none()
+            };
+            let load_page = match page {
+                Page::Person(person_id) => load_person_detail(app, person_id),
+                _ => load_popular_people(app),
+            };
+            Task::batch([close_detail, load_page])
+        }
+        Page::MyList => {
+            let close_detail = if was_detail {
+                detail_handlers::handle_close_detail_popup(app)
+            } else {
+                Task::none()
+            };
+            let items = app.my_list.items().to_vec();
+            Task::batch([close_detail, load_search_result_images(app, &items)])
+        }
+        _ if was_detail => detail_handlers::handle_close_detail_popup(app),
+        _ => Task::none(),
+    }
+}
+
+fn handle_add_to_list(app: &mut Movix, media_id: MediaId) -> Task<Message> {
+    if let Some(item) = detail_handlers::lookup_media_item(app, media_id).cloned() {
+        app.my_list.add(item);
+    }
+    Task::none()
+}
+
+fn load_popular_people(app: &Movix) -> Task<Message> {
+    if !app.popular_people.is_empty() {
+        return load_people_images(app);
+    }
+    let Some(client) = app.tmdb_client.clone() else {
+        return Task::none();
+    };
+    Task::perform(
+        async move { client.fetch_popular_people().await },
+        Message::PopularPeopleLoaded,
+    )
+}
+
+fn load_person_detail(app: &mut Movix, person_id: u64) -> Task<Message> {
+    if app.person_detail.as_ref().map(|d| d.person.id) == Some(person_id) {
+        return Task::none();
+    }
+    app.person_detail = None;
+    let Some(client) = app.tmdb_client.clone() else {
+        return Task::none();
+    };
+    Task::perform(
+        async move { client.fetch_person_details(person_id).await },
+        move |result| Message::PersonDetailLoaded(person_id, result),
+    )
+}
+
+fn handle_popular_people_loaded(
+    app: &mut Movix,
+    result: Result<Vec<crate::media::Person>, ApiError>,
+) -> Task<Message> {
+    track_connectivity(app, &result);
+    match result {
+        Ok(people) => {
+            app.popular_people = people;
+            load_people_images(app)
+        }
+        Err(error) => {
+            app.error_message = Some(format!("{:?}", error));
+            Task::none()
+        }
+    }
+}
+
+fn handle_person_detail_loaded(
+    app: &mut Movix,
+    person_id: u64,
+    result: Result<crate::media::PersonDetail, ApiError>,
+) -> Task<Message> {
+    track_connectivity(app, &result);
+    if app.current_page != Page::Person(person_id) {
+        return Task::none();
+    }
+    match result {
+        Ok(detail) => {
+            let profile = profile_image_task(app, detail.person.profile_path.as_ref());
+            let filmography = load_search_result_images(app, &detail.filmography);
+            app.person_detail = Some(detail);
+            Task::batch([profile, filmography])
+        }
+        Err(error) => {
+            app.error_message = Some(format!("{:?}", error));
+            Task::none()
+        }
+    }
+}
+
+fn load_people_images(app: &Movix) -> Task<Message> {
+    Task::batch(
+        app.popular_people
+            .iter()
+            .map(|person| profile_image_task(app, person.profile_path.as_ref())),
+    )
+}
+
+fn profile_image_task(app: &Movix, profile_path: Option<&String>) -> Task<Message> {
+    let (Some(client), Some(path)) = (&app.tmdb_client, profile_path) else {
+        return Task::none();
+    };
+    let url = client.image_url(path, ImageSize::Poster);
+    if app.image_cache.get(&url).is_none() && !app.image_cache.is_pending(&url) {
+        return Task::done(Message::LoadImage(url));
+    }
     Task::none()
 }
 
 fn handle_search_query_changed(app: &mut Movix, query: String) -> Task<Message> {
     app.search_query = query.clone();
 
-    if query.trim().is_empty() {
+    if query.trim().is_empty() && !app.search_filters.has_browse_filters() {
         return Task::done(Message::ClearSearch);
     }
 
@@ -159,13 +480,14 @@ fn handle_search_debounce_triggered(app: &mut Movix) -> Task<Message> {
         return Task::none();
     };
 
-    if timer.elapsed() < std::time::Duration::from_millis(300) {
+    if !app.settings.search_debounce_elapsed(timer.elapsed()) {
         return Task::none();
     }
 
     app.search_debounce_timer = None;
 
-    if app.search_query.trim().is_empty() {
+    let browsing = app.search_query.trim().is_empty();
+    if browsing && !app.search_filters.has_browse_filters() {
         return Task::done(Message::ClearSearch);
     }
 
@@ -174,13 +496,38 @@ fn handle_search_debounce_triggered(app: &mut Movix) -> Task<Message> {
     };
 
     let search_client = client.clone();
+    app.search_page_loading = false;
+    if browsing {
+        let filters = app.search_filters.clone();
+        return Task::perform(
+            async move {
+                let items = search_client.discover(&filters).await?;
+                Ok(SearchPage {
+                    items,
+                    page: 1,
+                    total_pages: 1,
+                })
+            },
+            Message::SearchResultsLoaded,
+        );
+    }
     let query = app.search_query.clone();
     Task::perform(
-        async move { search_client.search(&query).await },
+        async move { search_client.search(&query, 1).await },
         Message::SearchResultsLoaded,
     )
 }
 
+/// Re-applies the filters to the current results. Without a query the
+/// results came from `/discover` for the old filters, so they are fetched
+/// again once the filters settle.
+fn refilter_search_results(app: &mut Movix) {
+    app.filtered_results = app.search_filters.apply(&app.search_results);
+    if app.search_active && app.search_query.trim().is_empty() {
+        app.search_debounce_timer = Some(std::time::Instant::now());
+    }
+}
+
 fn handle_search_submit(app: &mut Movix) -> Task<Message> {
     if app.search_query.is_empty() {
         return Task::none();
@@ -190,24 +537,203 @@ fn handle_search_submit(app: &mut Movix) -> Task<Message> {
     };
     let search_client = client.clone();
     let query = app.search_query.clone();
+    app.search_page_loading = false;
     Task::perform(
-        async move { search_client.search(&query).await },
+        async move { search_client.search(&query, 1).await },
         Message::SearchResultsLoaded,
     )
 }
 
-fn handle_search_results(
+fn handle_search_results(app: &mut Movix, result: Result<SearchPage, ApiError>) -> Task<Message> {
+    track_connectivity(app, &result);
+    match result {
+        Ok(page) => {
+            app.search_page = page.page;
+            app.search_total_pages = page.total_pages;
+            app.search_results = page.items;
+            app.filtered_results = app.search_filters.apply(&app.search_results);
+            let suggestions = if app.filtered_results.is_empty() {
+                load_popular_suggestions(app)
+            } else {
+                Task::none()
+            };
+            Task::batch([
+                load_search_result_images(app, &app.search_results),
+                suggestions,
+            ])
+        }
+        Err(error) => {
+            app.error_message = Some(format!("{:?}", error));
+            Task::none()
+        }
+    }
+}
+
+/// Fetched the first time a search comes back empty; the client caches the
+/// list, so later empty searches reuse it.
+fn load_popular_suggestions(app: &Movix) -> Task<Message> {
+    if !app.popular_suggestions.is_empty() {
+        return Task::none();
+    }
+    let Some(client) = app.tmdb_client.clone() else {
+        return Task::none();
+    };
+    Task::perform(
+        async move { client.fetch_popular().await },
+        Message::PopularSuggestionsLoaded,
+    )
+}
+
+fn handle_popular_suggestions_loaded(
     app: &mut Movix,
     result: Result<Vec<crate::media::MediaItem>, ApiError>,
 ) -> Task<Message> {
+    track_connectivity(app, &result);
+    let Ok(items) = result else {
+        return Task::none();
+    };
+    let shown = app.settings.search_grid_density.cards_per_row();
+    app.popular_suggestions = items.into_iter().take(shown).collect();
+    load_search_result_images(app, &app.popular_suggestions)
+}
+
+/// Only one page is requested at a time, and none past the last.
+fn load_next_search_page(app: &mut Movix, scrolled_to: f32, content_height: f32) -> Task<Message> {
+    let query = app.search_query.trim().to_string();
+    if !app.search_active
+        || query.is_empty()
+        || app.search_page_loading
+        || app.search_page == 0
+        || app.search_page >= app.search_total_pages
+        || scrolled_to < content_height - SEARCH_NEXT_PAGE_DISTANCE
+    {
+        return Task::none();
+    }
+    let Some(client) = app.tmdb_client.clone() else {
+        return Task::none();
+    };
+    app.search_page_loading = true;
+    let page = app.search_page + 1;
+    Task::perform(
+        {
+            let query = query.clone();
+            async move { client.search(&query, page).await }
+        },
+        move |result| Message::SearchNextPageLoaded(query.clone(), result),
+    )
+}
+
+/// Pages for an earlier query are dropped. Titles already listed on an
+/// earlier page are skipped, since results shift between requests.
+fn handle_search_next_page(
+    app: &mut Movix,
+    query: String,
+    result: Result<SearchPage, ApiError>,
+) -> Task<Message> {
+    if query != app.search_query.trim() || !app.search_page_loading {
+        return Task::none();
+    }
+    app.search_page_loading = false;
+    track_connectivity(app, &result);
+    let page = match result {
+        Ok(page) => page,
+        Err(_) => {
+            // Stop paging rather than retrying on every scroll event.
+            app.search_total_pages = app.search_page;
+            return Task::none();
+        }
+    };
+    app.search_page = page.page;
+    app.search_total_pages = page.total_pages;
+    let new_items: Vec<crate::media::MediaItem> = page
+        .items
+        .into_iter()
+        .filter(|item| {
+            !app.search_results.iter().any(|existing| {
+                existing.id == item.id
+                    && std::mem::discriminant(&existing.media_type)
+                        == std::mem::discriminant(&item.media_type)
+            })
+        })
+        .collect();
+    let images = load_search_result_images(app, &new_items);
+    app.search_results.extend(new_items);
+    app.filtered_results = app.search_filters.apply(&app.search_results);
+    images
+}
+
+fn handle_browse_genre(app: &mut Movix, genre_id: u64) -> Task<Message> {
+    if app.tmdb_client.is_none() {
+        return Task::none();
+    }
+
+    let popup_item = app.detail_popup_data.as_ref().map(|d| &d.media_item);
+    let media_type = match popup_item.map(|i| &i.media_type) {
+        Some(MediaType::TvSeries) => "tv",
+        _ => "movie",
+    };
+    let genre = popup_item
+        .and_then(|i| i.genres.iter().find(|g| g.id == genre_id))
+        .or_else(|| app.genre_list.iter().find(|g| g.id == genre_id))
+        .cloned()
+        .unwrap_or(Genre {
+            id: genre_id,
+            name: String::from("Genre"),
+            media_scope: GenreScope::default(),
+        });
+
+    app.browse_genre = Some(genre);
+    app.browse_media_type = media_type;
+    app.browse_results.clear();
+    app.search_active = false;
+    app.search_query.clear();
+    app.search_results.clear();
+    app.filtered_results.clear();
+
+    Task::batch([
+        Task::done(Message::CloseDetailPopup),
+        fetch_genre_browse(app),
+    ])
+}
+
+fn fetch_genre_browse(app: &mut Movix) -> Task<Message> {
+    let (Some(client), Some(genre)) = (app.tmdb_client.clone(), &app.browse_genre) else {
+        return Task::none();
+    };
+    let genre_id = genre.id as u32;
+    let media_type = app.browse_media_type;
+    app.browse_loading = true;
+    app.browse_error = None;
+
+    Task::perform(
+        async move { client.fetch_by_genre(genre_id, media_type).await },
+        Message::GenreBrowseLoaded,
+    )
+}
+
+fn clear_genre_browse(app: &mut Movix) {
+    app.browse_genre = None;
+    app.browse_results.clear();
+    app.browse_loading = false;
+    app.browse_error = None;
+}
+
+fn handle_genre_browse_loaded(
+    app: &mut Movix,
+    result: Result<Vec<crate::media::MediaItem>, ApiError>,
+) -> Task<Message> {
+    track_connectivity(app, &result);
+    if app.browse_genre.is_none() {
+        return Task::none();
+    }
+    app.browse_loading = false;
     match result {
         Ok(items) => {
-            app.search_results = items.clone();
-            app.filtered_results = app.search_filters.apply(&app.search_results);
+            app.browse_results = items.clone();
             load_search_result_images(app, &items)
         }
         Err(error) => {
-            app.error_message = Some(format!("{:?}", error));
+            app.browse_error = Some(format!("{:?}", error));
             Task::none()
         }
     }
@@ -221,21 +747,17 @@ fn load_search_result_images(app: &Movix, items: &[crate::media::MediaItem]) ->
     let mut tasks = Vec::new();
 
     for item in items.iter().take(20) {
-        if let Some(backdrop_path) = &item.backdrop_path {
-            let url = client.image_url(backdrop_path, ImageSize::Backdrop);
+        if let Some(url) = app.backdrop_url(item) {
             if app.image_cache.get(&url).is_none() && !app.image_cache.is_pending(&url) {
                 tasks.push(Task::done(Message::LoadImage(url)));
             }
         }
 
         if item.logo_path.is_none() {
-            let fetch_client = client.clone();
-            let media_id = item.id;
-            let media_type = item.media_type.clone();
-            tasks.push(Task::perform(
-                async move { fetch_client.fetch_media_images(media_id, &media_type).await },
-                move |result| Message::LogoLoaded(media_id, result),
-            ));
+            tasks.push(Task::done(Message::FetchLogo(
+                item.id,
+                item.media_type.clone(),
+            )));
         } else if let Some(logo_path) = &item.logo_path {
             let url = client.image_url(logo_path, ImageSize::Original);
             if app.image_cache.get(&url).is_none() && !app.image_cache.is_pending(&url) {
@@ -243,7 +765,7 @@ fn load_search_result_images(app: &Movix, items: &[crate::media::MediaItem]) ->
             }
         }
 
-        if !app.trailer_cache.contains_key(&item.id) {
+        if app.settings.preload_trailers() && !app.trailer_cache.contains_key(&item.id) {
             let fetch_client = client.clone();
             let media_id = item.id;
             let media_type = item.media_type.clone();
@@ -273,6 +795,7 @@ fn handle_hover_card(app: &mut Movix, id: Option<MediaId>) -> Task<Message> {
         }
         None => {
             app.pending_hover_card = None;
+            app.card_trailer_resolving = None;
             let prev_hovered = app.hovered_card.take();
             if prev_hovered.is_some() {
                 let stop_card = Task::done(Message::StopCardTrailer);
@@ -296,21 +819,39 @@ fn handle_hover_card_delayed(app: &mut Movix, media_id: MediaId) -> Task<Message
     app.hovered_card = Some(media_id);
     let image_task = app.load_hover_card_images(media_id);
     let trailer_task = app.load_trailer_for_hovered_card(media_id);
+    let has_no_trailer = matches!(app.trailer_cache.get(&media_id), Some(None));
+    app.card_trailer_resolving = (!app.is_offline
+        && app.settings.autoplay_trailers()
+        && !has_no_trailer
+        && !app.stream_url_cache.contains_key(&media_id))
+    .then_some(media_id);
     Task::batch([image_task, trailer_task])
 }
 
 fn handle_content_loaded(
     app: &mut Movix,
-    result: Result<Vec<crate::media::ContentSection>, ApiError>,
+    result: Result<Vec<ContentSection>, ApiError>,
 ) -> Task<Message> {
+    track_connectivity(app, &result);
     match result {
         Ok(sections) => {
+            save_offline_snapshot(SECTIONS_SNAPSHOT, sections.clone());
             app.content_sections = sections.clone();
             app.loading_state = LoadingState::Idle;
             let image_task = app.load_content_images(&sections);
             let preload_task = app.preload_trailer_urls(&sections);
             Task::batch([image_task, preload_task])
         }
+        Err(ApiError::Network(_)) if app.content_sections.is_empty() => {
+            let Some(sections) = load_offline_snapshot::<Vec<ContentSection>>(SECTIONS_SNAPSHOT)
+            else {
+                app.loading_state = LoadingState::Error(String::from(
+                    "Could not reach TMDb and no cached content is available",
+                ));
+                return Task::none();
+            };
+            enter_offline_mode(app, sections)
+        }
         Err(error) => {
             app.loading_state = LoadingState::Error(format!("{:?}", error));
             app.error_message = Some(format!("{:?}", error));
@@ -319,16 +860,39 @@ fn handle_content_loaded(
     }
 }
 
+/// Rebuilds the home page from the last saved snapshot. Images come from the
+/// disk cache, and trailers and streaming stay disabled until a connectivity
+/// check succeeds.
+fn enter_offline_mode(app: &mut Movix, sections: Vec<ContentSection>) -> Task<Message> {
+    app.is_offline = true;
+    app.error_message = None;
+    app.loading_state = LoadingState::Idle;
+    app.content_sections = sections.clone();
+
+    let mut tasks = vec![app.load_content_images(&sections)];
+    if app.hero_items.is_empty() {
+        if let Some(items) = load_offline_snapshot(HERO_SNAPSHOT) {
+            tasks.push(apply_hero_items(app, items));
+        }
+    }
+    Task::batch(tasks)
+}
+
 fn handle_hero_loaded(
     app: &mut Movix,
-    result: Box<Result<crate::media::MediaItem, ApiError>>,
+    result: Box<Result<Vec<crate::media::MediaItem>, ApiError>>,
 ) -> Task<Message> {
+    track_connectivity(app, &*result);
     match *result {
-        Ok(item) => {
-            app.hero_content = Some(item.clone());
-            let image_task = app.load_hero_images(&item);
-            let trailer_task = app.load_trailer_for_media(item.id, &item.media_type);
-            Task::batch([image_task, trailer_task])
+        Ok(items) => {
+            save_offline_snapshot(HERO_SNAPSHOT, items.clone());
+            apply_hero_items(app, items)
+        }
+        Err(ApiError::Network(_)) if app.is_offline && app.hero_items.is_empty() => {
+            match load_offline_snapshot(HERO_SNAPSHOT) {
+                Some(items) => apply_hero_items(app, items),
+                None => Task::none(),
+            }
         }
         Err(error) => {
             app.error_message = Some(format!("{:?}", error));
@@ -337,31 +901,260 @@ fn handle_hero_loaded(
     }
 }
 
+fn apply_hero_items(app: &mut Movix, items: Vec<crate::media::MediaItem>) -> Task<Message> {
+    app.hero_items = items;
+    app.hero_index = 0;
+    app.hero_rotated_at = std::time::Instant::now();
+    let image_tasks: Vec<Task<Message>> = app
+        .hero_items
+        .iter()
+        .map(|item| app.load_hero_images(item))
+        .collect();
+    let Some(item) = app.hero_content() else {
+        return Task::none();
+    };
+    let trailer_task = app.load_trailer_for_media(item.id, &item.media_type);
+    let next_trailer_task = app.preload_next_hero_trailer();
+    Task::batch([Task::batch(image_tasks), trailer_task, next_trailer_task])
+}
+
+fn handle_hero_rotate_tick(app: &mut Movix) -> Task<Message> {
+    let interval = std::time::Duration::from_secs(app.settings.hero_rotation_secs);
+    if app.hero_items.len() < 2 || app.hero_rotated_at.elapsed() < interval {
+        return Task::none();
+    }
+    let next = (app.hero_index + 1) % app.hero_items.len();
+    handle_hero_select(app, next)
+}
+
+fn handle_hero_select(app: &mut Movix, index: usize) -> Task<Message> {
+    app.hero_rotated_at = std::time::Instant::now();
+    if index == app.hero_index || index >= app.hero_items.len() {
+        return Task::none();
+    }
+
+    app.hero_index = index;
+    show_current_hero(app)
+}
+
+fn handle_launch(app: &mut Movix, request: LaunchRequest) -> Task<Message> {
+    match request.action {
+        LaunchAction::Open => {
+            detail_handlers::open_detail_popup(app, request.media_id, request.media_type)
+        }
+        LaunchAction::Play => {
+            let Some(client) = &app.tmdb_client else {
+                return Task::none();
+            };
+            let fetch_client = client.clone();
+            Task::perform(
+                async move {
+                    fetch_client
+                        .fetch_full_media_details(request.media_id, &request.media_type)
+                        .await
+                },
+                |r| Message::LaunchDetailsLoaded(Box::new(r)),
+            )
+        }
+    }
+}
+
+fn handle_shuffle_hero(app: &mut Movix) -> Task<Message> {
+    let Some(client) = &app.tmdb_client else {
+        return Task::none();
+    };
+
+    let candidates: Vec<&crate::media::MediaItem> = app
+        .content_sections
+        .iter()
+        .filter(|s| s.category == Category::Trending)
+        .flat_map(|s| &s.items)
+        .filter(|i| i.backdrop_path.is_some())
+        .filter(|i| app.hero_content().map(|h| h.id) != Some(i.id))
+        .collect();
+    if candidates.is_empty() {
+        return Task::done(Message::HeroNext);
+    }
+
+    let seed = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as usize)
+        .unwrap_or(0);
+    let pick = candidates[seed % candidates.len()];
+    let (id, media_type) = (pick.id, pick.media_type.clone());
+
+    let fetch_client = client.clone();
+    Task::perform(
+        async move { fetch_client.fetch_full_media_details(id, &media_type).await },
+        |r| Message::HeroShuffled(Box::new(r)),
+    )
+}
+
+fn handle_hero_shuffled(
+    app: &mut Movix,
+    result: Box<Result<crate::media::MediaItem, ApiError>>,
+) -> Task<Message> {
+    track_connectivity(app, &*result);
+    let Ok(item) = *result else {
+        return Task::none();
+    };
+    app.hero_rotated_at = std::time::Instant::now();
+    match app.hero_items.iter().position(|h| h.id == item.id) {
+        Some(index) => app.hero_index = index,
+        None if app.hero_items.is_empty() => app.hero_items.push(item),
+        None => app.hero_items[app.hero_index] = item,
+    }
+    show_current_hero(app)
+}
+
+fn show_current_hero(app: &mut Movix) -> Task<Message> {
+    app.hero_player.stop();
+    app.hero_video_frame = None;
+    app.hero_ended = false;
+
+    let Some(item) = app.hero_content() else {
+        return Task::none();
+    };
+    let media_id = item.id;
+    let hero_tasks = Task::batch([app.load_hero_images(item), app.preload_next_hero_trailer()]);
+
+    if app.stream_url_cache.contains_key(&media_id) {
+        return Task::batch([hero_tasks, Task::done(Message::ResumeHeroTrailer)]);
+    }
+    if let Some(Some(youtube_id)) = app.trailer_cache.get(&media_id) {
+        let stream_task = app.fetch_trailer_stream_url(media_id, youtube_id.clone());
+        return Task::batch([hero_tasks, stream_task]);
+    }
+    let trailer_task = app.load_trailer_for_media(media_id, &item.media_type);
+    Task::batch([hero_tasks, trailer_task])
+}
+
 fn handle_image_loaded(
     app: &mut Movix,
     url: String,
     result: Result<iced::widget::image::Handle, String>,
 ) -> Task<Message> {
     if let Ok(handle) = result {
+        let sample = sample_hero_backdrop(app, &url, &handle);
         app.image_cache.insert(url, handle);
+        return sample;
+    }
+    if app.is_offline {
+        app.image_cache.clear_pending(&url);
+        return Task::none();
+    }
+
+    let failures = app.image_cache.record_failure(&url);
+    if !app.image_cache.can_retry(&url) {
+        app.image_cache.clear_pending(&url);
+        return Task::none();
+    }
+
+    let delay = IMAGE_RETRY_BASE_DELAY * 2u32.pow(failures - 1);
+    Task::perform(
+        async move {
+            tokio::time::sleep(delay).await;
+            url
+        },
+        Message::RetryImage,
+    )
+}
+
+fn handle_hero_color_sampled(
+    app: &mut Movix,
+    url: String,
+    color: Option<iced::Color>,
+) -> Task<Message> {
+    if let Some(color) = color {
+        app.hero_backdrop_colors.insert(url, color);
     }
     Task::none()
 }
 
+fn handle_hero_gradient_tick(app: &mut Movix) -> Task<Message> {
+    app.hero_gradient.tick();
+    let target = app.hero_gradient_target();
+    app.hero_gradient.retarget(target);
+    Task::none()
+}
+
+/// Backdrops of hero items are sampled off the UI thread as they arrive, so
+/// the gradient color is ready by the time that hero is shown.
+fn sample_hero_backdrop(
+    app: &Movix,
+    url: &str,
+    handle: &iced::widget::image::Handle,
+) -> Task<Message> {
+    let is_hero_backdrop = app
+        .hero_items
+        .iter()
+        .any(|item| app.backdrop_url(item).as_deref() == Some(url));
+    let iced::widget::image::Handle::Bytes(_, bytes) = handle else {
+        return Task::none();
+    };
+    if !is_hero_backdrop || app.hero_backdrop_colors.contains_key(url) {
+        return Task::none();
+    }
+    let bytes = bytes.clone();
+    let url = url.to_string();
+    Task::perform(async move { sample_backdrop_color(&bytes) }, move |color| {
+        Message::HeroColorSampled(url, color)
+    })
+}
+
+fn handle_fetch_logo(app: &mut Movix, media_id: MediaId, media_type: MediaType) -> Task<Message> {
+    let Some(client) = app.tmdb_client.clone() else {
+        return Task::none();
+    };
+    if app.is_offline
+        || app.missing_logos.contains(&media_id)
+        || !app.pending_logo_fetches.insert(media_id)
+    {
+        return Task::none();
+    }
+
+    Task::perform(
+        async move {
+            match client.fetch_media_images(media_id, &media_type).await {
+                Err(_) => {
+                    tokio::time::sleep(LOGO_RETRY_DELAY).await;
+                    client.fetch_media_images(media_id, &media_type).await
+                }
+                result => result,
+            }
+        },
+        move |result| Message::LogoLoaded(media_id, result),
+    )
+}
+
 fn handle_logo_loaded(
     app: &mut Movix,
     media_id: MediaId,
     result: Result<Option<String>, ApiError>,
 ) -> Task<Message> {
-    let Ok(Some(logo_path)) = result else {
-        return Task::none();
-    };
-    for section in &mut app.content_sections {
-        for item in &mut section.items {
-            if item.id == media_id {
-                item.logo_path = Some(logo_path.clone());
-            }
+    app.pending_logo_fetches.remove(&media_id);
+    let logo_path = match result {
+        Ok(Some(logo_path)) => logo_path,
+        Ok(None) => {
+            app.missing_logos.insert(media_id);
+            return Task::none();
         }
+        Err(_) => return Task::none(),
+    };
+    let items = app
+        .content_sections
+        .iter_mut()
+        .flat_map(|s| s.items.iter_mut())
+        .chain(app.search_results.iter_mut())
+        .chain(app.filtered_results.iter_mut())
+        .chain(app.browse_results.iter_mut())
+        .chain(
+            app.person_detail
+                .iter_mut()
+                .flat_map(|d| d.filmography.iter_mut()),
+        );
+    for item in items.filter(|i| i.id == media_id) {
+        item.logo_path = Some(logo_path.clone());
     }
     let Some(client) = &app.tmdb_client else {
         return Task::none();
@@ -374,12 +1167,16 @@ fn handle_logo_loaded(
 }
 
 fn handle_load_image(app: &mut Movix, url: String) -> Task<Message> {
-    if app.image_cache.get(&url).is_some() || app.image_cache.is_pending(&url) {
+    if app.image_cache.get(&url).is_some()
+        || app.image_cache.is_pending(&url)
+        || !app.image_cache.can_retry(&url)
+    {
         return Task::none();
     }
     app.image_cache.mark_pending(url.clone());
     let image_url = url.clone();
     let cache_path = app.image_cache.get_cache_path(&url);
+    let client = app.tmdb_client.clone();
 
     Task::perform(
         async move {
@@ -390,7 +1187,10 @@ fn handle_load_image(app: &mut Movix, url: String) -> Task<Message> {
                     }
                 }
             }
-            let bytes = fetch_image_bytes(image_url.clone()).await;
+            let bytes = match client {
+                Some(client) => client.fetch_image_bytes(&image_url).await,
+                None => Err(String::from("TMDB client is not configured")),
+            };
             (image_url, bytes, cache_path, false)
         },
         |(url, result, cache_path, from_cache)| match result {
@@ -410,21 +1210,210 @@ fn handle_load_image(app: &mut Movix, url: String) -> Task<Message> {
     )
 }
 
+/// Counts consecutive network failures so that repeated `ApiError::Network`
+/// results flip the app into offline mode; any success resets the count.
+pub fn track_connectivity<T>(app: &mut Movix, result: &Result<T, ApiError>) {
+    match result {
+        Ok(_) => {
+            app.network_failures = 0;
+            if app.is_offline {
+                app.is_offline = false;
+                app.image_cache.clear_failures();
+            }
+        }
+        Err(ApiError::Network(_)) => {
+            app.network_failures += 1;
+            if app.network_failures >= OFFLINE_FAILURE_THRESHOLD {
+                app.is_offline = true;
+            }
+        }
+        Err(_) => {}
+    }
+}
+
+pub fn check_proxy(settings: &AppSettings) -> Task<Message> {
+    match settings.proxy() {
+        Some(proxy_url) => Task::perform(
+            crate::proxy::check_reachable(proxy_url.to_string()),
+            Message::ProxyChecked,
+        ),
+        None => Task::none(),
+    }
+}
+
+/// Fetches the provider list with the credentials currently typed into the
+/// settings form, so it also works before the first save.
+pub fn load_watch_providers(app: &mut Movix) -> Task<Message> {
+    let Some(setup) = app.setup_page.as_mut() else {
+        return Task::none();
+    };
+    if setup.api_key.trim().is_empty() {
+        return Task::none();
+    }
+    setup.providers_loading = true;
+    let proxy_url = setup.proxy_url.trim();
+    let client = TmdbClient::new(
+        setup.api_key.trim().to_string(),
+        app.settings.language.clone(),
+        app.settings.region.clone(),
+        &app.settings.effective_user_agent(),
+        (!proxy_url.is_empty()).then_some(proxy_url),
+    );
+    let region = setup.provider_region();
+    Task::perform(
+        async move { client.fetch_watch_providers(&region).await },
+        |result| {
+            Message::Setup(SetupMessage::ProvidersLoaded(
+                result.map_err(|e| format!("{:?}", e)),
+            ))
+        },
+    )
+}
+
+fn handle_check_connectivity(app: &mut Movix) -> Task<Message> {
+    if !app.is_offline {
+        return Task::none();
+    }
+    let Some(client) = app.tmdb_client.clone() else {
+        return Task::none();
+    };
+    Task::perform(
+        async move { client.is_reachable().await },
+        Message::ConnectivityChecked,
+    )
+}
+
+fn handle_connectivity_checked(app: &mut Movix, reachable: bool) -> Task<Message> {
+    if !reachable || !app.is_offline {
+        return Task::none();
+    }
+    app.is_offline = false;
+    app.network_failures = 0;
+    app.image_cache.clear_failures();
+    if matches!(app.loading_state, LoadingState::Error(_)) {
+        return handle_retry_load(app);
+    }
+    show_current_hero(app)
+}
+
+/// A hung request never reports back, so a stalled initial load is turned
+/// into a retryable error instead of leaving the skeletons up forever.
+fn handle_loading_watchdog_tick(app: &mut Movix) -> Task<Message> {
+    let stalled = matches!(app.loading_state, LoadingState::Loading)
+        && app.content_sections.is_empty()
+        && app.loading_started_at.elapsed() >= LOADING_TIMEOUT;
+    if stalled {
+        app.loading_state =
+            LoadingState::Error(String::from("Loading is taking longer than expected"));
+    }
+    Task::none()
+}
+
 fn handle_retry_load(app: &mut Movix) -> Task<Message> {
     app.loading_state = LoadingState::Loading;
+    app.loading_started_at = std::time::Instant::now();
     app.error_message = None;
     let Some(client) = &app.tmdb_client else {
         return Task::none();
     };
 
-    let content_client = client.clone();
-    let hero_client = client.clone();
-    Task::batch([
-        Task::perform(load_initial_content(content_client), Message::ContentLoaded),
-        Task::perform(load_hero_content(hero_client), |r| {
-            Message::HeroLoaded(Box::new(r))
-        }),
-    ])
+    let content_task = load_content(app, client.clone());
+    let hero_task = load_hero(&app.settings, client.clone());
+    Task::batch([content_task, hero_task])
+}
+
+fn watch_history(app: &Movix) -> WatchHistory {
+    let (watched, in_progress) = app
+        .progress_store
+        .try_lock()
+        .map(|store| (store.movie_ids(), store.in_progress_movie_ids()))
+        .unwrap_or_default();
+    let remote_only = |ids: Vec<MediaId>| -> Vec<MediaId> {
+        ids.into_iter()
+            .filter(|id| !is_local_media_id(*id))
+            .collect()
+    };
+    WatchHistory {
+        watched: remote_only(watched),
+        in_progress: remote_only(in_progress),
+    }
+}
+
+pub fn load_content(app: &Movix, client: TmdbClient) -> Task<Message> {
+    Task::perform(
+        load_initial_content(
+            client,
+            app.settings.streaming_providers.clone(),
+            app.settings.watch_region.clone(),
+            app.settings.top_picks_source,
+            watch_history(app),
+        ),
+        Message::ContentLoaded,
+    )
+}
+
+fn handle_retry_section(app: &mut Movix, index: usize) -> Task<Message> {
+    let Some(client) = app.tmdb_client.clone() else {
+        return Task::none();
+    };
+    let history = watch_history(app);
+    let Some(section) = app.content_sections.get_mut(index) else {
+        return Task::none();
+    };
+    let Some(query) = section.query.clone() else {
+        return Task::none();
+    };
+    section.error = None;
+    section.loading = true;
+
+    let region = app.settings.watch_region.clone();
+    Task::perform(
+        async move {
+            let result = load_section(&client, &query, &region, &history).await;
+            (query, result)
+        },
+        move |(query, result)| Message::SectionLoaded(index, query, result),
+    )
+}
+
+/// The page may have been reloaded while the retry was in flight, so the
+/// result only lands if the row at `index` still has the same query.
+fn handle_section_loaded(
+    app: &mut Movix,
+    index: usize,
+    query: SectionQuery,
+    result: Result<Vec<crate::media::MediaItem>, ApiError>,
+) -> Task<Message> {
+    track_connectivity(app, &result);
+    let Some(section) = app
+        .content_sections
+        .get_mut(index)
+        .filter(|s| s.query.as_ref() == Some(&query))
+    else {
+        return Task::none();
+    };
+    section.loading = false;
+    match result {
+        Ok(items) => {
+            section.items = items;
+            let section = section.clone();
+            app.load_content_images(std::slice::from_ref(&section))
+        }
+        Err(error) => {
+            section.error = Some(format!("{:?}", error));
+            Task::none()
+        }
+    }
+}
+
+/// With the hero disabled nothing is fetched, so no hero trailer is ever resolved or played.
+pub fn load_hero(settings: &AppSettings, client: TmdbClient) -> Task<Message> {
+    if !settings.show_hero {
+        return Task::none();
+    }
+    Task::perform(load_hero_content(client), |r| {
+        Message::HeroLoaded(Box::new(r))
+    })
 }
 
 fn handle_scroll_section(
@@ -433,19 +1422,46 @@ fn handle_scroll_section(
     direction: ScrollDirection,
 ) -> Task<Message> {
     let scroll_amount = 500.0;
+    ensure_section_scroll_slots(app, section_index);
+
+    let current_target = app.section_scroll_targets[section_index];
+    let new_target = match direction {
+        ScrollDirection::Left => (current_target - scroll_amount).max(0.0),
+        ScrollDirection::Right => current_target + scroll_amount,
+    };
+    app.section_scroll_targets[section_index] = new_target;
+    Task::done(Message::AnimateScroll(section_index))
+}
+
+fn ensure_section_scroll_slots(app: &mut Movix, section_index: usize) {
     while app.section_scroll_offsets.len() <= section_index {
         app.section_scroll_offsets.push(0.0);
     }
     while app.section_scroll_targets.len() <= section_index {
         app.section_scroll_targets.push(0.0);
     }
+}
 
-    let current_target = app.section_scroll_targets[section_index];
-    let new_target = match direction {
-        ScrollDirection::Left => (current_target - scroll_amount).max(0.0),
-        ScrollDirection::Right => current_target + scroll_amount,
+/// Shift+wheel reports a vertical delta on most platforms and a horizontal one
+/// on others, so whichever axis moved drives the row.
+fn handle_wheel_scroll_section(
+    app: &mut Movix,
+    section_index: usize,
+    delta: iced::mouse::ScrollDelta,
+) -> Task<Message> {
+    let (x, y) = match delta {
+        iced::mouse::ScrollDelta::Lines { x, y } => (x * WHEEL_LINE_PIXELS, y * WHEEL_LINE_PIXELS),
+        iced::mouse::ScrollDelta::Pixels { x, y } => (x, y),
     };
-    app.section_scroll_targets[section_index] = new_target;
+    let amount = if x != 0.0 { x } else { y };
+    if amount == 0.0 {
+        return Task::none();
+    }
+    ensure_section_scroll_slots(app, section_index);
+
+    let max_scroll = app.section_max_scroll(section_index);
+    let target = &mut app.section_scroll_targets[section_index];
+    *target = (*target - amount).clamp(0.0, max_scroll);
     Task::done(Message::AnimateScroll(section_index))
 }
 
@@ -510,13 +1526,16 @@ fn handle_trailer_videos_loaded(
                 let youtube_id = trailer.key.clone();
                 app.trailer_cache.insert(media_id, Some(youtube_id.clone()));
 
-                let is_hero = app.hero_content.as_ref().map(|h| h.id) == Some(media_id);
+                let is_hero = app.hero_content().map(|h| h.id) == Some(media_id);
                 let is_hovered = app.hovered_card == Some(media_id);
                 let is_detail_hovered = app.detail_hovered_card == Some(media_id);
 
                 if is_hero || is_hovered || is_detail_hovered {
                     return app.fetch_trailer_stream_url(media_id, youtube_id);
                 }
+                if !app.settings.preload_trailers() {
+                    return Task::none();
+                }
 
                 let manager = app.trailer_manager.clone();
                 return Task::perform(
@@ -530,15 +1549,18 @@ fn handle_trailer_videos_loaded(
             app.trailer_cache.insert(media_id, None);
         }
     }
+    if app.card_trailer_resolving == Some(media_id) {
+        app.card_trailer_resolving = None;
+    }
     Task::none()
 }
 
 fn handle_hero_visibility(app: &mut Movix, visible: bool) -> Task<Message> {
-    app.hero_visible = visible;
+    app.hero_visible = visible && app.settings.show_hero;
     if app.movie_player_active {
         return Task::none();
     }
-    if !visible {
+    if !app.hero_visible {
         return Task::done(Message::PauseHeroTrailer);
     }
     Task::done(Message::ResumeHeroTrailer)
@@ -546,9 +1568,8 @@ fn handle_hero_visibility(app: &mut Movix, visible: bool) -> Task<Message> {
 
 fn handle_main_scrolled(app: &mut Movix, offset: f32) -> Task<Message> {
     app.main_scroll_offset = offset;
-    let hero_height = 620.0;
     let was_visible = app.hero_visible;
-    app.hero_visible = offset < hero_height * 0.5;
+    app.hero_visible = app.settings.show_hero && offset < HERO_HEIGHT * HERO_VISIBLE_FRACTION;
 
     if app.movie_player_active {
         return Task::none();
@@ -562,58 +1583,148 @@ fn handle_main_scrolled(app: &mut Movix, offset: f32) -> Task<Message> {
     Task::none()
 }
 
+fn handle_page_scroll(app: &mut Movix, scroll: PageScroll) -> Task<Message> {
+    if app.movie_player_active
+        || app.detail_popup_open
+        || app.setup_page.is_some()
+        || app.open_file_path.is_some()
+    {
+        return Task::none();
+    }
+    let id = iced::widget::Id::new(MAIN_SCROLL_ID);
+    let page = app.page_height();
+    match scroll {
+        PageScroll::PageUp => iced::widget::operation::scroll_by(
+            id,
+            iced::widget::scrollable::AbsoluteOffset { x: 0.0, y: -page },
+        ),
+        PageScroll::PageDown => iced::widget::operation::scroll_by(
+            id,
+            iced::widget::scrollable::AbsoluteOffset { x: 0.0, y: page },
+        ),
+        PageScroll::Home => iced::widget::operation::snap_to(
+            id,
+            iced::widget::scrollable::RelativeOffset { x: 0.0, y: 0.0 },
+        ),
+        PageScroll::End => iced::widget::operation::snap_to(
+            id,
+            iced::widget::scrollable::RelativeOffset { x: 0.0, y: 1.0 },
+        ),
+    }
+}
+
 fn handle_clear_search(app: &mut Movix) -> Task<Message> {
     app.search_active = false;
     app.search_query.clear();
     app.search_results.clear();
     app.filtered_results.clear();
-    app.search_filters = SearchFilters::default();
+    app.search_filters.clear_query_filters();
     app.search_debounce_timer = None;
+    app.search_page = 0;
+    app.search_total_pages = 0;
+    app.search_page_loading = false;
+    flush_search_preferences(app);
     Task::none()
 }
 
+/// Selected genres that don't exist for the new media type are dropped, since
+/// they would filter out every result.
 fn handle_set_media_type_filter(app: &mut Movix, filter: MediaTypeFilter) -> Task<Message> {
     app.search_filters.media_type = filter;
-    app.filtered_results = app.search_filters.apply(&app.search_results);
+    let genre_list = &app.genre_list;
+    app.search_filters.genre_ids.retain(|id| {
+        genre_list
+            .iter()
+            .any(|g| g.id == *id && g.media_scope.matches(filter))
+    });
+    refilter_search_results(app);
+    save_search_preferences(app);
     Task::none()
 }
 
 fn handle_set_genre_filter(app: &mut Movix, genre_id: Option<u64>) -> Task<Message> {
-    app.search_filters.genre_id = genre_id;
-    app.filtered_results = app.search_filters.apply(&app.search_results);
+    app.search_filters.genre_ids = genre_id.into_iter().collect();
+    refilter_search_results(app);
+    Task::none()
+}
+
+fn handle_toggle_genre_filter(app: &mut Movix, genre_id: u64) -> Task<Message> {
+    app.search_filters.toggle_genre(genre_id);
+    refilter_search_results(app);
+    Task::none()
+}
+
+fn handle_set_genre_match(app: &mut Movix, genre_match: GenreMatch) -> Task<Message> {
+    app.search_filters.genre_match = genre_match;
+    refilter_search_results(app);
     Task::none()
 }
 
 fn handle_set_year_from(app: &mut Movix, year: Option<u32>) -> Task<Message> {
     app.search_filters.year_from = year;
-    app.filtered_results = app.search_filters.apply(&app.search_results);
+    refilter_search_results(app);
     Task::none()
 }
 
 fn handle_set_year_to(app: &mut Movix, year: Option<u32>) -> Task<Message> {
     app.search_filters.year_to = year;
-    app.filtered_results = app.search_filters.apply(&app.search_results);
+    refilter_search_results(app);
     Task::none()
 }
 
 fn handle_set_min_rating(app: &mut Movix, rating: f32) -> Task<Message> {
-    app.search_filters.min_rating = rating;
-    app.filtered_results = app.search_filters.apply(&app.search_results);
+    app.search_filters.min_rating = (rating * 10.0).round() / 10.0;
+    refilter_search_results(app);
+    save_search_preferences(app);
     Task::none()
 }
 
 fn handle_set_sort_option(app: &mut Movix, sort: SortOption) -> Task<Message> {
     app.search_filters.sort_by = sort;
     app.filtered_results = app.search_filters.apply(&app.search_results);
+    save_search_preferences(app);
     Task::none()
 }
 
 fn handle_reset_filters(app: &mut Movix) -> Task<Message> {
     app.search_filters = SearchFilters::default();
-    app.filtered_results = app.search_filters.apply(&app.search_results);
+    refilter_search_results(app);
+    save_search_preferences(app);
     Task::none()
 }
 
+fn handle_dismiss_whats_new(app: &mut Movix) -> Task<Message> {
+    app.whats_new_open = false;
+    app.settings.last_seen_version = crate::whats_new::APP_VERSION.to_string();
+    let _ = app.settings.save();
+    Task::none()
+}
+
+fn save_search_preferences(app: &mut Movix) {
+    let preferences = app.search_filters.preferences();
+    if app.settings.search_preferences == preferences {
+        return;
+    }
+    app.settings.search_preferences = preferences;
+    app.search_preferences_save_timer = Some(std::time::Instant::now());
+}
+
+fn handle_save_search_preferences_tick(app: &mut Movix) -> Task<Message> {
+    if app
+        .search_preferences_save_timer
+        .is_some_and(|timer| timer.elapsed() >= SEARCH_PREFERENCES_SAVE_DELAY)
+    {
+        flush_search_preferences(app);
+    }
+    Task::none()
+}
+
+fn flush_search_preferences(app: &mut Movix) {
+    if app.search_preferences_save_timer.take().is_some() {
+        let _ = app.settings.save();
+    }
+}
+
 fn handle_genres_loaded(app: &mut Movix, result: Result<Vec<Genre>, ApiError>) -> Task<Message> {
     if let Ok(genres) = result {
         app.genre_list = genres;
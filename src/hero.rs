@@ -8,12 +8,114 @@ use crate::media::{
 use crate::tmdb::ImageSize;
 use crate::Movix;
 
-const HERO_HEIGHT: f32 = 620.0;
+pub const HERO_HEIGHT: f32 = 620.0;
 const ICON_PLAY_FILL: char = '\u{F4F4}';
 const ICON_INFO_CIRCLE: char = '\u{F431}';
 const ICON_VOLUME_UP_FILL: char = '\u{F611}';
 const ICON_VOLUME_MUTE_FILL: char = '\u{F608}';
 const ICON_ARROW_CLOCKWISE: char = '\u{F130}';
+const ICON_CHEVRON_LEFT: char = '\u{F284}';
+const ICON_CHEVRON_RIGHT: char = '\u{F285}';
+const ICON_SHUFFLE: char = '\u{F544}';
+const GRADIENT_TRANSITION: std::time::Duration = std::time::Duration::from_millis(600);
+/// Keeps the sampled color dark enough for white row titles on top of it.
+const GRADIENT_BRIGHTNESS: f32 = 0.45;
+
+/// The color behind the first row, eased from one hero's backdrop color to
+/// the next instead of snapping when the hero changes.
+#[derive(Debug, Clone, Copy)]
+pub struct HeroGradient {
+    from: Color,
+    to: Color,
+    started_at: Option<std::time::Instant>,
+}
+
+impl Default for HeroGradient {
+    fn default() -> Self {
+        Self {
+            from: Color::BLACK,
+            to: Color::BLACK,
+            started_at: None,
+        }
+    }
+}
+
+impl HeroGradient {
+    pub fn current(&self) -> Color {
+        let Some(started_at) = self.started_at else {
+            return self.to;
+        };
+        let t = (started_at.elapsed().as_secs_f32() / GRADIENT_TRANSITION.as_secs_f32()).min(1.0);
+        let eased = t * t * (3.0 - 2.0 * t);
+        let mix = |a: f32, b: f32| a + (b - a) * eased;
+        Color::from_rgba(
+            mix(self.from.r, self.to.r),
+            mix(self.from.g, self.to.g),
+            mix(self.from.b, self.to.b),
+            mix(self.from.a, self.to.a),
+        )
+    }
+
+    pub fn target(&self) -> Color {
+        self.to
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    /// Starts from whatever is on screen, so a change mid-transition
+    /// doesn't jump.
+    pub fn retarget(&mut self, target: Color) {
+        if target == self.to {
+            return;
+        }
+        self.from = self.current();
+        self.to = target;
+        self.started_at = Some(std::time::Instant::now());
+    }
+
+    pub fn tick(&mut self) {
+        if self
+            .started_at
+            .is_some_and(|started_at| started_at.elapsed() >= GRADIENT_TRANSITION)
+        {
+            self.from = self.to;
+            self.started_at = None;
+        }
+    }
+}
+
+/// Averages the bottom third of a backdrop, the part that meets the first
+/// row, from a downscaled copy.
+pub fn sample_backdrop_color(bytes: &[u8]) -> Option<Color> {
+    let thumbnail = image::load_from_memory(bytes)
+        .ok()?
+        .thumbnail(32, 18)
+        .to_rgb8();
+    let (width, height) = thumbnail.dimensions();
+    let bottom = (height * 2 / 3)..height;
+    let mut sum = [0u64; 3];
+    let mut count = 0u64;
+    for y in bottom {
+        for x in 0..width {
+            let pixel = thumbnail.get_pixel(x, y);
+            for (total, channel) in sum.iter_mut().zip(pixel.0) {
+                *total += u64::from(channel);
+            }
+            count += 1;
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+    let channel = |total: u64| (total / count) as f32 / 255.0 * GRADIENT_BRIGHTNESS;
+    Some(Color::from_rgb(
+        channel(sum[0]),
+        channel(sum[1]),
+        channel(sum[2]),
+    ))
+}
 
 fn format_runtime(minutes: u32) -> String {
     let (h, m) = (minutes / 60, minutes % 60);
@@ -33,7 +135,7 @@ fn icon(icon_char: char) -> iced::widget::Text<'static> {
 
 impl Movix {
     pub fn view_hero_section(&self) -> Element<'_, Message> {
-        match &self.hero_content {
+        match self.hero_content() {
             Some(media_item) => self.view_hero_with_content(media_item),
             None => self.view_hero_placeholder(),
         }
@@ -66,13 +168,13 @@ impl Movix {
         let media_id = media_item.id;
         let play_button = self.view_hero_play_button(media_id);
         let more_info_button = self.view_hero_more_info_button(media_id);
-        let video_control = self.view_hero_video_control();
+        let shuffle_button = self.view_hero_shuffle_button();
 
         let button_row = row![
             play_button,
             more_info_button,
             Space::new().width(Length::Fill),
-            video_control
+            shuffle_button
         ]
         .spacing(12)
         .align_y(iced::Alignment::Center);
@@ -124,20 +226,117 @@ impl Movix {
             });
 
         let backdrop_element = self.view_hero_backdrop(media_item);
+        let carousel_controls = self.view_hero_carousel_controls();
+        let video_control = container(self.view_hero_video_control())
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(iced::alignment::Horizontal::Right)
+            .align_y(iced::alignment::Vertical::Bottom)
+            .padding(Padding::new(0.0).right(64.0).bottom(24.0));
 
         iced::widget::stack![
             backdrop_element,
             hero_top_gradient,
             hero_bottom_gradient,
-            hero_left_gradient
+            hero_left_gradient,
+            carousel_controls,
+            video_control
         ]
         .width(Length::Fill)
         .height(Length::Fixed(HERO_HEIGHT))
         .into()
     }
 
+    fn view_hero_carousel_controls(&self) -> Element<'_, Message> {
+        if self.hero_items.len() < 2 {
+            return Space::new().width(0).height(0).into();
+        }
+
+        let arrow = |icon_char: char, message: Message| {
+            button(icon(icon_char).size(16).color(TEXT_WHITE))
+                .padding(Padding::new(6.0))
+                .style(|_theme, status| button::Style {
+                    background: Some(iced::Background::Color(Color::from_rgba(
+                        0.0,
+                        0.0,
+                        0.0,
+                        if matches!(status, button::Status::Hovered) {
+                            0.6
+                        } else {
+                            0.3
+                        },
+                    ))),
+                    text_color: TEXT_WHITE,
+                    border: Border {
+                        radius: 16.0.into(),
+                        ..Default::default()
+                    },
+                    shadow: Shadow::default(),
+                    snap: false,
+                })
+                .on_press(message)
+        };
+
+        let dots: Vec<Element<'_, Message>> = (0..self.hero_items.len())
+            .map(|index| {
+                let active = index == self.hero_index;
+                button(Space::new().width(0).height(0))
+                    .width(Length::Fixed(if active { 24.0 } else { 8.0 }))
+                    .height(Length::Fixed(8.0))
+                    .padding(0)
+                    .style(move |_theme, status| {
+                        let alpha = if active {
+                            1.0
+                        } else if matches!(status, button::Status::Hovered) {
+                            0.7
+                        } else {
+                            0.4
+                        };
+                        button::Style {
+                            background: Some(iced::Background::Color(Color::from_rgba(
+                                1.0, 1.0, 1.0, alpha,
+                            ))),
+                            text_color: TEXT_WHITE,
+                            border: Border {
+                                radius: 4.0.into(),
+                                ..Default::default()
+                            },
+                            shadow: Shadow::default(),
+                            snap: false,
+                        }
+                    })
+                    .on_press(Message::HeroSelect(index))
+                    .into()
+            })
+            .collect();
+
+        let controls = row![
+            arrow(ICON_CHEVRON_LEFT, Message::HeroPrevious),
+            row(dots).spacing(6).align_y(iced::Alignment::Center),
+            arrow(ICON_CHEVRON_RIGHT, Message::HeroNext)
+        ]
+        .spacing(12)
+        .align_y(iced::Alignment::Center);
+
+        container(controls)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(iced::alignment::Horizontal::Center)
+            .align_y(iced::alignment::Vertical::Bottom)
+            .padding(Padding::new(0.0).bottom(32.0))
+            .into()
+    }
+
     pub fn get_hero_gradient_color(&self) -> Color {
-        Color::BLACK
+        self.hero_gradient.current()
+    }
+
+    /// Black until the current hero's backdrop has been sampled.
+    pub fn hero_gradient_target(&self) -> Color {
+        self.hero_content()
+            .and_then(|item| self.backdrop_url(item))
+            .and_then(|url| self.hero_backdrop_colors.get(&url).copied())
+            .unwrap_or(Color::BLACK)
     }
 
     pub fn view_hero_title(&self, media_item: &MediaItem) -> Element<'_, Message> {
@@ -149,6 +348,7 @@ impl Movix {
         };
         let logo_url = client.image_url(logo_path, ImageSize::Original);
         let Some(handle) = self.image_cache.get(&logo_url) else {
+            self.image_cache.request(&logo_url);
             return self.view_hero_title_text(media_item);
         };
         iced::widget::image(handle.clone())
@@ -235,6 +435,7 @@ impl Movix {
         };
         let image_url = client.image_url(backdrop_path, ImageSize::Backdrop);
         let Some(handle) = self.image_cache.get(&image_url) else {
+            self.image_cache.request(&image_url);
             return self.view_hero_backdrop_placeholder();
         };
         iced::widget::image(handle.clone())
@@ -317,6 +518,40 @@ impl Movix {
         .into()
     }
 
+    fn view_hero_shuffle_button(&self) -> Element<'_, Message> {
+        button(
+            container(icon(ICON_SHUFFLE).size(20).color(TEXT_WHITE))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .center_x(Length::Fill)
+                .center_y(Length::Fill),
+        )
+        .width(Length::Fixed(44.0))
+        .height(Length::Fixed(44.0))
+        .padding(0)
+        .style(|_theme, status| {
+            let bg_alpha = match status {
+                button::Status::Hovered => 0.6,
+                _ => 0.4,
+            };
+            button::Style {
+                background: Some(iced::Background::Color(Color::from_rgba(
+                    0.0, 0.0, 0.0, bg_alpha,
+                ))),
+                text_color: TEXT_WHITE,
+                border: Border {
+                    color: Color::from_rgba(1.0, 1.0, 1.0, 0.3),
+                    width: 1.0,
+                    radius: 8.0.into(),
+                },
+                shadow: Shadow::default(),
+                snap: false,
+            }
+        })
+        .on_press(Message::ShuffleHero)
+        .into()
+    }
+
     pub fn view_hero_video_control(&self) -> Element<'_, Message> {
         let has_video = self.hero_video_frame.is_some();
         if !has_video {
@@ -0,0 +1,98 @@
+use std::path::{Path, PathBuf};
+
+pub struct InstanceLock {
+    path: PathBuf,
+}
+
+impl InstanceLock {
+    fn lock_path() -> Option<PathBuf> {
+        #[cfg(target_os = "windows")]
+        {
+            std::env::var("LOCALAPPDATA")
+                .ok()
+                .map(|appdata| PathBuf::from(appdata).join("movix").join("movix.lock"))
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            std::env::var("HOME").ok().map(|home| {
+                PathBuf::from(home)
+                    .join(".local")
+                    .join("share")
+                    .join("movix")
+                    .join("movix.lock")
+            })
+        }
+    }
+
+    pub fn acquire() -> Result<Option<Self>, String> {
+        let Some(path) = Self::lock_path() else {
+            return Ok(None);
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+
+        for _ in 0..2 {
+            match create_with_pid(&path) {
+                Ok(()) => return Ok(Some(Self { path })),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let owner = std::fs::read_to_string(&path)
+                        .ok()
+                        .and_then(|s| s.trim().parse::<u32>().ok());
+                    match owner {
+                        Some(pid) if is_process_running(pid) => {
+                            return Err(format!("Movix is already running (pid {})", pid));
+                        }
+                        _ => {
+                            let _ = std::fs::remove_file(&path);
+                        }
+                    }
+                }
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+        Err(String::from("Could not acquire the instance lock"))
+    }
+}
+
+/// Creates the lock file with our pid already in it. The pid goes into a
+/// private file first and is then linked into place, which fails atomically if
+/// the lock exists, so another instance never sees a lock without an owner.
+fn create_with_pid(path: &Path) -> std::io::Result<()> {
+    let staging = path.with_extension(format!("lock.{}", std::process::id()));
+    std::fs::write(&staging, std::process::id().to_string())?;
+    let linked = std::fs::hard_link(&staging, path);
+    let _ = std::fs::remove_file(&staging);
+    linked
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn is_process_running(pid: u32) -> bool {
+    PathBuf::from(format!("/proc/{}", pid)).exists()
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn is_process_running(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "windows")]
+fn is_process_running(pid: u32) -> bool {
+    std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).contains(&pid.to_string()))
+        .unwrap_or(false)
+}
@@ -1,5 +1,6 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod audio;
 mod cards;
 mod components;
 mod detail_handlers;
@@ -7,14 +8,21 @@ mod detail_popup;
 mod detail_sections;
 mod handlers;
 mod hero;
+mod instance;
 mod media;
 mod movie_player;
+mod my_list;
+mod omdb;
+mod people;
 mod player_handlers;
+mod proxy;
 mod search;
 mod settings;
 mod streaming;
+mod subtitles;
 mod tmdb;
 mod video;
+mod whats_new;
 
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -22,20 +30,36 @@ use tokio::sync::Mutex;
 use iced::widget::container;
 use iced::{Element, Font, Length, Size, Subscription, Task, Theme};
 
+use hero::HeroGradient;
+use instance::InstanceLock;
 use media::{
-    ContentSection, DetailPopupData, Episode, Genre, HeaderState, ImageCache, LoadingState,
-    MediaId, MediaItem, Message, Page, SearchFilters, BACKGROUND_BLACK,
+    ContentSection, DetailPopupData, Episode, Genre, HeaderState, ImageCache, LaunchRequest,
+    LoadingState, MediaId, MediaItem, Message, Page, PageScroll, Person, PersonDetail,
+    SearchFilters, BACKGROUND_BLACK, LAUNCH_USAGE,
 };
-use movie_player::{MoviePlayer, PlaybackProgressStore};
-use settings::{AppSettings, SetupPage};
-use tmdb::{load_genres, load_hero_content, load_initial_content, TmdbClient};
-use video::{TrailerManager, VideoPlayer};
+use movie_player::{MoviePlayer, PlaybackMetrics, PlaybackProgressStore, ResumePrompt};
+use my_list::MyListStore;
+use omdb::OmdbClient;
+use settings::{AppSettings, SetupMessage, SetupPage};
+use tmdb::{load_genres, TmdbClient};
+use video::{TrailerManager, VideoPlayer, CARD_TRAILER_SIZE, DETAIL_TRAILER_SIZE};
+
+const WINDOW_WIDTH: f32 = 1280.0;
+const WINDOW_HEIGHT: f32 = 720.0;
+const PROGRESS_AUTOSAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
 
 pub struct Movix {
     pub setup_page: Option<SetupPage>,
     pub current_page: Page,
+    pub previous_page: Page,
     pub header_state: HeaderState,
-    pub hero_content: Option<MediaItem>,
+    pub settings: AppSettings,
+    pub hero_items: Vec<MediaItem>,
+    pub hero_index: usize,
+    pub hero_rotated_at: std::time::Instant,
+    pub hero_gradient: HeroGradient,
+    /// Sampled colors of hero backdrops, keyed by image URL.
+    pub hero_backdrop_colors: std::collections::HashMap<String, iced::Color>,
     pub content_sections: Vec<ContentSection>,
     pub search_query: String,
     pub search_results: Vec<MediaItem>,
@@ -45,17 +69,27 @@ pub struct Movix {
     pub image_cache: ImageCache,
     pub hovered_card: Option<MediaId>,
     pub pending_hover_card: Option<MediaId>,
+    pub card_trailer_resolving: Option<MediaId>,
     pub hovered_section: Option<usize>,
+    pub keyboard_modifiers: iced::keyboard::Modifiers,
     pub section_scroll_offsets: Vec<f32>,
     pub section_scroll_targets: Vec<f32>,
     pub tmdb_client: Option<TmdbClient>,
+    pub omdb_client: Option<OmdbClient>,
     pub trailer_manager: TrailerManager,
     pub hero_player: VideoPlayer,
     pub card_player: VideoPlayer,
     pub trailer_cache: std::collections::HashMap<MediaId, Option<String>>,
     pub stream_url_cache: std::collections::HashMap<MediaId, String>,
+    pub pending_logo_fetches: std::collections::HashSet<MediaId>,
+    pub missing_logos: std::collections::HashSet<MediaId>,
+    pub is_offline: bool,
+    pub proxy_error: Option<String>,
+    pub network_failures: u32,
     pub hero_visible: bool,
     pub main_scroll_offset: f32,
+    pub main_viewport_height: f32,
+    pub window_height: f32,
     pub hero_video_frame: Option<iced::widget::image::Handle>,
     pub card_video_frame: Option<iced::widget::image::Handle>,
     pub hero_muted: bool,
@@ -64,6 +98,8 @@ pub struct Movix {
     pub movie_player_active: bool,
     pub movie_player_media_id: Option<MediaId>,
     pub movie_player_title: Option<String>,
+    /// Season and episode when the player shows a series episode.
+    pub movie_player_episode: Option<(u32, u32)>,
     pub movie_player_frame: Option<iced::widget::image::Handle>,
     pub movie_player_controls_visible: bool,
     pub movie_player_controls_timer: Option<std::time::Instant>,
@@ -71,15 +107,47 @@ pub struct Movix {
     pub movie_player_position: f64,
     pub movie_player_duration: f64,
     pub movie_player_volume: f64,
+    pub movie_player_volume_changed_at: Option<std::time::Instant>,
     pub movie_player_muted: bool,
     pub movie_player_playing: bool,
     pub movie_player_error: Option<String>,
+    pub movie_player_error_retryable: bool,
+    pub movie_player_trailer_only: bool,
+    pub movie_player_stream_provider: Option<String>,
+    /// Variants of the current stream, best first, for the quality picker.
+    pub movie_player_stream_variants: Vec<crate::streaming::StreamVariant>,
+    pub movie_player_stream_quality: Option<u32>,
+    pub movie_player_resume_prompt: Option<ResumePrompt>,
+    pub movie_player_fullscreen: bool,
+    /// Subtitle track available for the current title, whether or not it
+    /// is turned on.
+    pub movie_player_subtitle_source: Option<String>,
+    /// Looked up the first time the window mode is changed.
+    pub window_id: Option<iced::window::Id>,
+    /// Stored position to jump to once the decoder reports a duration.
+    pub movie_player_pending_seek: Option<f64>,
+    pub movie_player_metrics_visible: bool,
+    pub movie_player_metrics: PlaybackMetrics,
+    pub stream_blacklist: std::collections::HashMap<(String, MediaId), std::time::Instant>,
+    pub stream_provider_names: Vec<String>,
+    pub whats_new_open: bool,
+    pub open_file_path: Option<String>,
+    pub open_file_error: Option<String>,
     pub progress_store: Arc<Mutex<PlaybackProgressStore>>,
+    pub my_list: MyListStore,
     pub detail_popup_open: bool,
     pub detail_popup_media_id: Option<MediaId>,
     pub detail_popup_data: Option<DetailPopupData>,
     pub detail_selected_season: Option<u32>,
     pub detail_episodes: Vec<Episode>,
+    pub detail_episodes_loading: bool,
+    pub detail_advanced_expanded: bool,
+    pub detail_cast_expanded: bool,
+    pub detail_overview_expanded: bool,
+    pub detail_revealed_episodes: std::collections::HashSet<u64>,
+    /// Index into `DetailPopupData::videos` of the video in the detail player.
+    pub detail_video_index: Option<usize>,
+    pub detail_imdb_rating: Option<String>,
     pub detail_hovered_card: Option<MediaId>,
     pub pending_detail_hover_card: Option<MediaId>,
     pub detail_player: VideoPlayer,
@@ -87,8 +155,27 @@ pub struct Movix {
     pub search_active: bool,
     pub search_filters: SearchFilters,
     pub filtered_results: Vec<MediaItem>,
+    pub browse_genre: Option<Genre>,
+    pub browse_results: Vec<MediaItem>,
+    /// TMDb path segment ("movie" or "tv") the browse page lists.
+    pub browse_media_type: &'static str,
+    pub browse_loading: bool,
+    pub browse_error: Option<String>,
+    pub popular_people: Vec<Person>,
+    /// Popular titles suggested when a search finds nothing.
+    pub popular_suggestions: Vec<MediaItem>,
+    pub person_detail: Option<PersonDetail>,
     pub genre_list: Vec<Genre>,
     pub search_debounce_timer: Option<std::time::Instant>,
+    /// Last page of `search_results` loaded, and how many the query has.
+    pub search_page: u32,
+    pub search_total_pages: u32,
+    pub search_page_loading: bool,
+    /// Set when the player volume changed and hasn't been written to disk.
+    pub volume_save_timer: Option<std::time::Instant>,
+    pub search_preferences_save_timer: Option<std::time::Instant>,
+    pub loading_started_at: std::time::Instant,
+    pub pending_launch: Option<LaunchRequest>,
 }
 
 impl Default for Movix {
@@ -97,8 +184,14 @@ impl Default for Movix {
         Self {
             setup_page: None,
             current_page: Page::Home,
+            previous_page: Page::Home,
             header_state: HeaderState::default(),
-            hero_content: None,
+            settings: AppSettings::default(),
+            hero_items: Vec::new(),
+            hero_index: 0,
+            hero_rotated_at: std::time::Instant::now(),
+            hero_gradient: HeroGradient::default(),
+            hero_backdrop_colors: std::collections::HashMap::new(),
             content_sections: Vec::new(),
             search_query: String::new(),
             search_results: Vec::new(),
@@ -108,17 +201,27 @@ impl Default for Movix {
             image_cache: ImageCache::new(),
             hovered_card: None,
             pending_hover_card: None,
+            card_trailer_resolving: None,
             hovered_section: None,
+            keyboard_modifiers: iced::keyboard::Modifiers::default(),
             section_scroll_offsets: Vec::new(),
             section_scroll_targets: Vec::new(),
             tmdb_client: None,
+            omdb_client: None,
             trailer_manager: TrailerManager::new(),
-            hero_player: VideoPlayer::new().expect("Failed to init hero player"),
-            card_player: VideoPlayer::new().expect("Failed to init card player"),
+            hero_player: VideoPlayer::new(CARD_TRAILER_SIZE).expect("Failed to init hero player"),
+            card_player: VideoPlayer::new(CARD_TRAILER_SIZE).expect("Failed to init card player"),
             trailer_cache: std::collections::HashMap::new(),
             stream_url_cache: std::collections::HashMap::new(),
+            pending_logo_fetches: std::collections::HashSet::new(),
+            missing_logos: std::collections::HashSet::new(),
+            is_offline: false,
+            proxy_error: None,
+            network_failures: 0,
             hero_visible: true,
             main_scroll_offset: 0.0,
+            main_viewport_height: 0.0,
+            window_height: WINDOW_HEIGHT,
             hero_video_frame: None,
             card_video_frame: None,
             hero_muted: false,
@@ -128,6 +231,7 @@ impl Default for Movix {
             movie_player_active: false,
             movie_player_media_id: None,
             movie_player_title: None,
+            movie_player_episode: None,
             movie_player_frame: None,
             movie_player_controls_visible: true,
             movie_player_controls_timer: None,
@@ -135,36 +239,79 @@ impl Default for Movix {
             movie_player_position: 0.0,
             movie_player_duration: 0.0,
             movie_player_volume: 1.0,
+            movie_player_volume_changed_at: None,
+            movie_player_metrics_visible: false,
+            movie_player_metrics: PlaybackMetrics::default(),
             movie_player_muted: false,
             movie_player_playing: false,
             movie_player_error: None,
+            movie_player_error_retryable: false,
+            movie_player_trailer_only: false,
+            movie_player_stream_provider: None,
+            movie_player_stream_variants: Vec::new(),
+            movie_player_stream_quality: None,
+            movie_player_resume_prompt: None,
+            movie_player_fullscreen: false,
+            movie_player_subtitle_source: None,
+            window_id: None,
+            movie_player_pending_seek: None,
+            stream_blacklist: std::collections::HashMap::new(),
+            stream_provider_names: streaming::default_provider_names(),
+            whats_new_open: false,
+            open_file_path: None,
+            open_file_error: None,
             progress_store,
+            my_list: MyListStore::new(),
             detail_popup_open: false,
             detail_popup_media_id: None,
             detail_popup_data: None,
             detail_selected_season: None,
             detail_episodes: Vec::new(),
+            detail_episodes_loading: false,
+            detail_advanced_expanded: false,
+            detail_cast_expanded: false,
+            detail_overview_expanded: false,
+            detail_revealed_episodes: std::collections::HashSet::new(),
+            detail_video_index: None,
+            detail_imdb_rating: None,
             detail_hovered_card: None,
             pending_detail_hover_card: None,
-            detail_player: VideoPlayer::new().expect("Failed to init detail player"),
+            detail_player: VideoPlayer::new(DETAIL_TRAILER_SIZE)
+                .expect("Failed to init detail player"),
             detail_video_frame: None,
             search_active: false,
             search_filters: SearchFilters::default(),
             filtered_results: Vec::new(),
+            browse_genre: None,
+            browse_results: Vec::new(),
+            browse_media_type: "movie",
+            browse_loading: false,
+            browse_error: None,
+            popular_people: Vec::new(),
+            popular_suggestions: Vec::new(),
+            person_detail: None,
             genre_list: Vec::new(),
             search_debounce_timer: None,
+            search_page: 0,
+            search_total_pages: 0,
+            search_page_loading: false,
+            volume_save_timer: None,
+            search_preferences_save_timer: None,
+            loading_started_at: std::time::Instant::now(),
+            pending_launch: None,
         }
     }
 }
 
 impl Movix {
-    fn new() -> (Self, Task<Message>) {
+    fn new(launch: Option<LaunchRequest>) -> (Self, Task<Message>) {
         let settings = match AppSettings::load() {
             Some(s) if s.is_valid() => s,
             _ => {
                 return (
                     Self {
                         setup_page: Some(SetupPage::default()),
+                        pending_launch: launch,
                         ..Default::default()
                     },
                     Task::none(),
@@ -172,47 +319,152 @@ impl Movix {
             }
         };
 
+        let mut trailer_manager = TrailerManager::new();
+        trailer_manager.set_proxy(settings.proxy());
+        let proxy_check = handlers::check_proxy(&settings);
+
         let client = TmdbClient::from_settings(&settings);
-        let content_client = client.clone();
-        let hero_client = client.clone();
         let genres_client = client.clone();
-        let load_content =
-            Task::perform(load_initial_content(content_client), Message::ContentLoaded);
-        let load_hero = Task::perform(load_hero_content(hero_client), |r| {
-            Message::HeroLoaded(Box::new(r))
-        });
+        let load_hero = handlers::load_hero(&settings, client.clone());
         let load_genres = Task::perform(load_genres(genres_client), Message::GenresLoaded);
+        let launch_task = match launch {
+            Some(request) => Task::done(Message::Launch(request)),
+            None => Task::none(),
+        };
+
+        let mut image_cache = ImageCache::new();
+        image_cache.set_disk_cache_enabled(settings.image_disk_cache);
+        image_cache.set_max_entries(settings.image_cache_entries);
+        image_cache.enforce_disk_budget(settings.image_disk_budget_bytes());
+        client.prune_disk_cache();
+
+        let mut app = Self {
+            tmdb_client: Some(client.clone()),
+            image_cache,
+            omdb_client: OmdbClient::from_settings(&settings),
+            trailer_manager,
+            hero_muted: settings.hero_start_muted,
+            hero_visible: settings.show_hero,
+            whats_new_open: whats_new::should_show(&settings.last_seen_version),
+            search_filters: SearchFilters::from_preferences(settings.search_preferences),
+            settings,
+            ..Default::default()
+        };
+        app.apply_player_settings();
+        let load_content = handlers::load_content(&app, client);
 
         (
-            Self {
-                tmdb_client: Some(client),
-                ..Default::default()
-            },
-            Task::batch([load_content, load_hero, load_genres]),
+            app,
+            Task::batch([
+                load_content,
+                load_hero,
+                load_genres,
+                launch_task,
+                proxy_check,
+            ]),
         )
     }
 
+    fn apply_player_settings(&mut self) {
+        let audio = self.settings.audio_config();
+        self.hero_player.set_audio(audio.clone());
+        self.card_player.set_audio(audio.clone());
+        self.detail_player.set_audio(audio.clone());
+        self.movie_player.set_audio(audio);
+        let proxy_url = self.settings.proxy();
+        self.hero_player.set_proxy(proxy_url);
+        self.card_player.set_proxy(proxy_url);
+        self.detail_player.set_proxy(proxy_url);
+        self.movie_player.set_proxy(proxy_url);
+        let (width, height) = self.settings.movie_decode_size();
+        self.movie_player.set_target_size(width, height);
+        self.apply_stored_volume();
+        self.detail_player
+            .set_target_size(self.settings.detail_trailer_size());
+    }
+
+    /// Restores the last movie player volume and mute state, and carries the
+    /// volume over to the trailer players.
+    pub fn apply_stored_volume(&mut self) {
+        let volume = self.settings.player_volume.clamp(0.0, 1.0);
+        self.movie_player.set_volume(volume);
+        self.movie_player.set_muted(self.settings.player_muted);
+        self.movie_player_volume = volume;
+        self.movie_player_muted = self.settings.player_muted;
+        for player in [
+            &mut self.hero_player,
+            &mut self.card_player,
+            &mut self.detail_player,
+        ] {
+            player.set_volume(volume as f32);
+        }
+    }
+
     fn initialize_with_settings(&mut self, settings: AppSettings) -> Task<Message> {
+        self.trailer_manager.set_proxy(settings.proxy());
+        self.proxy_error = None;
+        let proxy_check = handlers::check_proxy(&settings);
+
         let client = TmdbClient::from_settings(&settings);
         self.tmdb_client = Some(client.clone());
+        self.omdb_client = OmdbClient::from_settings(&settings);
+        self.image_cache
+            .set_disk_cache_enabled(settings.image_disk_cache);
+        self.image_cache
+            .set_max_entries(settings.image_cache_entries);
+        self.hero_muted = settings.hero_start_muted;
+        self.hero_visible = settings.show_hero;
+        if !settings.show_hero {
+            self.hero_player.stop();
+            self.hero_video_frame = None;
+            self.hero_items.clear();
+        }
+        self.search_filters = SearchFilters::from_preferences(settings.search_preferences);
+        self.settings = settings;
+        self.apply_player_settings();
         self.setup_page = None;
         self.loading_state = LoadingState::Loading;
+        self.loading_started_at = std::time::Instant::now();
 
-        let content_client = client.clone();
-        let hero_client = client.clone();
+        let load_content = handlers::load_content(self, client.clone());
+        let load_hero = handlers::load_hero(&self.settings, client.clone());
         let genres_client = client;
 
         Task::batch([
-            Task::perform(load_initial_content(content_client), Message::ContentLoaded),
-            Task::perform(load_hero_content(hero_client), |r| {
-                Message::HeroLoaded(Box::new(r))
-            }),
+            load_content,
+            load_hero,
             Task::perform(load_genres(genres_client), Message::GenresLoaded),
+            match self.pending_launch.take() {
+                Some(request) => Task::done(Message::Launch(request)),
+                None => Task::none(),
+            },
+            proxy_check,
         ])
     }
 
+    pub fn hero_content(&self) -> Option<&MediaItem> {
+        self.hero_items.get(self.hero_index)
+    }
+
+    /// The main scroll only reports its viewport once it has been scrolled, so
+    /// until then the window height stands in for it.
+    pub fn page_height(&self) -> f32 {
+        if self.main_viewport_height > 0.0 {
+            self.main_viewport_height
+        } else {
+            self.window_height
+        }
+    }
+
     fn update(&mut self, message: Message) -> Task<Message> {
         if let Message::Setup(setup_msg) = message {
+            if matches!(setup_msg, SetupMessage::Cancel) {
+                self.setup_page = None;
+                return Task::done(Message::ResumeHeroTrailer);
+            }
+            if matches!(setup_msg, SetupMessage::LoadProviders) {
+                return handlers::load_watch_providers(self);
+            }
             if let Some(ref mut setup) = self.setup_page {
                 if let Some(settings) = setup.update(setup_msg) {
                     return self.initialize_with_settings(settings);
@@ -247,9 +499,31 @@ impl Movix {
                 ..Default::default()
             });
 
+        if self.whats_new_open {
+            return iced::widget::stack![main_content, self.view_whats_new_overlay()]
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into();
+        }
+
+        if self.open_file_path.is_some() {
+            return iced::widget::stack![main_content, self.view_open_file_overlay()]
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into();
+        }
+
         if self.detail_popup_open {
             let popup_overlay = self.view_detail_popup_overlay();
-            return iced::widget::stack![main_content, popup_overlay]
+            let mut layers = iced::widget::stack![main_content, popup_overlay];
+            if self.is_offline {
+                layers = layers.push(self.view_offline_banner());
+            }
+            return layers.width(Length::Fill).height(Length::Fill).into();
+        }
+
+        if self.is_offline {
+            return iced::widget::stack![main_content, self.view_offline_banner()]
                 .width(Length::Fill)
                 .height(Length::Fill)
                 .into();
@@ -268,7 +542,13 @@ impl Movix {
         let detail_playing = self.detail_player.is_playing();
         let movie_playing = self.movie_player_active && self.movie_player.has_pipeline();
 
-        let mut subs = Vec::new();
+        let mut subs = vec![
+            iced::event::listen_with(keyboard_event),
+            iced::window::resize_events().map(|(_, size)| Message::WindowResized(size)),
+        ];
+        if self.movie_player_active {
+            subs.push(iced::event::listen_with(movie_player_key_event));
+        }
         if hero_playing && !self.movie_player_active && !self.detail_popup_open {
             subs.push(
                 iced::time::every(std::time::Duration::from_millis(33))
@@ -287,14 +567,75 @@ impl Movix {
                     .map(|_| Message::DetailFrameTick),
             );
         }
+        let hero_rotating = self.hero_items.len() > 1
+            && self.settings.hero_rotation_secs > 0
+            && self.hero_visible
+            && !hero_playing
+            && !self.search_active
+            && self.hovered_card.is_none()
+            && !self.movie_player_active
+            && !self.detail_popup_open
+            && !self.current_page.hides_hero();
+        if hero_rotating {
+            subs.push(
+                iced::time::every(std::time::Duration::from_secs(1))
+                    .map(|_| Message::HeroRotateTick),
+            );
+        }
+        if self.hero_gradient.is_animating()
+            || self.hero_gradient.target() != self.hero_gradient_target()
+        {
+            subs.push(
+                iced::time::every(std::time::Duration::from_millis(16))
+                    .map(|_| Message::HeroGradientTick),
+            );
+        }
+        let loading_stalled_check = matches!(self.loading_state, LoadingState::Loading)
+            && self.content_sections.is_empty()
+            && self.setup_page.is_none();
+        if loading_stalled_check {
+            subs.push(
+                iced::time::every(std::time::Duration::from_secs(1))
+                    .map(|_| Message::LoadingWatchdogTick),
+            );
+        }
+        if self.image_cache.has_requests() {
+            subs.push(
+                iced::time::every(std::time::Duration::from_millis(100))
+                    .map(|_| Message::LoadRequestedImages),
+            );
+        }
+        if self.is_offline {
+            subs.push(
+                iced::time::every(std::time::Duration::from_secs(10))
+                    .map(|_| Message::CheckConnectivity),
+            );
+        }
         if movie_playing {
             subs.push(
                 iced::time::every(std::time::Duration::from_millis(16))
                     .map(|_| Message::MoviePlayerFrameTick),
             );
         }
+        if movie_playing && self.movie_player_playing && !self.movie_player_trailer_only {
+            subs.push(
+                iced::time::every(PROGRESS_AUTOSAVE_INTERVAL).map(|_| Message::MoviePlayerAutoSave),
+            );
+        }
+        if self.volume_save_timer.is_some() {
+            subs.push(
+                iced::time::every(std::time::Duration::from_millis(250))
+                    .map(|_| Message::SaveVolumeTick),
+            );
+        }
+        if self.search_preferences_save_timer.is_some() {
+            subs.push(
+                iced::time::every(std::time::Duration::from_millis(250))
+                    .map(|_| Message::SaveSearchPreferencesTick),
+            );
+        }
         if let Some(timer) = self.search_debounce_timer {
-            if timer.elapsed() >= std::time::Duration::from_millis(300) {
+            if self.settings.search_debounce_elapsed(timer.elapsed()) {
                 subs.push(
                     iced::time::every(std::time::Duration::from_millis(50))
                         .map(|_| Message::SearchDebounceTriggered),
@@ -310,13 +651,100 @@ impl Movix {
     }
 }
 
+fn keyboard_event(
+    event: iced::Event,
+    status: iced::event::Status,
+    _window: iced::window::Id,
+) -> Option<Message> {
+    match event {
+        iced::Event::Keyboard(iced::keyboard::Event::ModifiersChanged(modifiers)) => {
+            Some(Message::ModifiersChanged(modifiers))
+        }
+        iced::Event::Keyboard(iced::keyboard::Event::KeyPressed { key, modifiers, .. })
+            if status == iced::event::Status::Ignored
+                && !modifiers.command()
+                && !modifiers.alt()
+                && key.as_ref() == iced::keyboard::Key::Character("d") =>
+        {
+            Some(Message::MoviePlayerToggleMetrics)
+        }
+        iced::Event::Keyboard(iced::keyboard::Event::KeyPressed {
+            key: iced::keyboard::Key::Named(named),
+            ..
+        }) if status == iced::event::Status::Ignored => {
+            use iced::keyboard::key::Named;
+            let scroll = match named {
+                Named::Escape => return Some(Message::MoviePlayerEscape),
+                Named::PageUp => PageScroll::PageUp,
+                Named::PageDown => PageScroll::PageDown,
+                Named::Home => PageScroll::Home,
+                Named::End => PageScroll::End,
+                _ => return None,
+            };
+            Some(Message::PageScroll(scroll))
+        }
+        _ => None,
+    }
+}
+
+/// Escape is left to `keyboard_event`, which handles it whether or not the
+/// player is open.
+fn movie_player_key_event(
+    event: iced::Event,
+    status: iced::event::Status,
+    _window: iced::window::Id,
+) -> Option<Message> {
+    use iced::keyboard::key::Named;
+    use iced::keyboard::Key;
+
+    let iced::Event::Keyboard(iced::keyboard::Event::KeyPressed { key, modifiers, .. }) = event
+    else {
+        return None;
+    };
+    if status == iced::event::Status::Captured || modifiers.command() || modifiers.alt() {
+        return None;
+    }
+    match key.as_ref() {
+        Key::Named(Named::Space) => Some(Message::MoviePlayerTogglePlay),
+        Key::Named(Named::ArrowLeft) => Some(Message::MoviePlayerSeekRelative(-10.0)),
+        Key::Named(Named::ArrowRight) => Some(Message::MoviePlayerSeekRelative(10.0)),
+        Key::Named(Named::ArrowUp) => Some(Message::MoviePlayerAdjustVolume(0.1)),
+        Key::Named(Named::ArrowDown) => Some(Message::MoviePlayerAdjustVolume(-0.1)),
+        Key::Character(c) if c.eq_ignore_ascii_case("m") => Some(Message::MoviePlayerToggleMute),
+        Key::Character(c) if c.eq_ignore_ascii_case("f") => {
+            Some(Message::MoviePlayerToggleFullscreen)
+        }
+        _ => None,
+    }
+}
+
 fn main() -> iced::Result {
-    iced::application(Movix::new, Movix::update, Movix::view)
-        .title("Movix")
-        .theme(Movix::theme)
-        .window_size(Size::new(1280.0, 720.0))
-        .font(iced_fonts::BOOTSTRAP_FONT_BYTES)
-        .default_font(Font::DEFAULT)
-        .subscription(Movix::subscription)
-        .run()
+    let launch = match LaunchRequest::from_args(std::env::args()) {
+        Ok(launch) => launch,
+        Err(e) => {
+            eprintln!("movix: {}\n{}", e, LAUNCH_USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let _instance_lock = match InstanceLock::acquire() {
+        Ok(lock) => lock,
+        Err(e) => {
+            eprintln!("movix: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    iced::application(
+        move || Movix::new(launch.clone()),
+        Movix::update,
+        Movix::view,
+    )
+    .title("Movix")
+    .theme(Movix::theme)
+    .window_size(Size::new(WINDOW_WIDTH, WINDOW_HEIGHT))
+    .font(iced_fonts::BOOTSTRAP_FONT_BYTES)
+    .default_font(Font::DEFAULT)
+    .subscription(Movix::subscription)
+    .run()
 }
@@ -1,16 +1,21 @@
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use iced::widget::image::Handle;
 use iced::Color;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-fn simple_hash(s: &str) -> String {
+fn djb2(s: &str) -> u64 {
     let mut hash: u64 = 5381;
     for byte in s.bytes() {
         hash = hash.wrapping_mul(33).wrapping_add(byte as u64);
     }
-    format!("{:016x}", hash)
+    hash
+}
+
+pub fn simple_hash(s: &str) -> String {
+    format!("{:016x}", djb2(s))
 }
 
 fn get_cache_dir() -> Option<PathBuf> {
@@ -40,7 +45,9 @@ pub const NETFLIX_RED: Color = Color::from_rgb(0.898, 0.035, 0.078);
 pub const TEXT_WHITE: Color = Color::from_rgb(1.0, 1.0, 1.0);
 pub const TEXT_GRAY: Color = Color::from_rgb(0.702, 0.702, 0.702);
 
-pub const SECTION_IDS: [&str; 10] = [
+/// One per home row: Continue Watching, the seven core rows and up to five
+/// streaming provider rows.
+pub const SECTION_IDS: [&str; 13] = [
     "section-0",
     "section-1",
     "section-2",
@@ -51,14 +58,31 @@ pub const SECTION_IDS: [&str; 10] = [
     "section-7",
     "section-8",
     "section-9",
+    "section-10",
+    "section-11",
+    "section-12",
 ];
 
+pub const MAIN_SCROLL_ID: &str = "main-scroll";
+
 pub fn section_id(index: usize) -> Option<&'static str> {
     SECTION_IDS.get(index).copied()
 }
 
 pub type MediaId = u64;
 
+const LOCAL_MEDIA_ID_BIT: MediaId = 1 << 63;
+
+/// Local files get an id derived from their path with the top bit set, which
+/// keeps them clear of TMDb ids while still keying playback progress.
+pub fn local_media_id(path: &str) -> MediaId {
+    djb2(path) | LOCAL_MEDIA_ID_BIT
+}
+
+pub fn is_local_media_id(id: MediaId) -> bool {
+    id & LOCAL_MEDIA_ID_BIT != 0
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Page {
     Home,
@@ -66,9 +90,21 @@ pub enum Page {
     Movies,
     MostRecent,
     MyList,
+    People,
+    Person(u64),
     Detail(MediaId),
 }
 
+impl Page {
+    /// Pages without the hero banner, where hero trailers must stay paused.
+    pub fn hides_hero(&self) -> bool {
+        matches!(
+            self,
+            Page::MyList | Page::People | Page::Person(_) | Page::Detail(_)
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum NavItem {
     Home,
@@ -76,6 +112,7 @@ pub enum NavItem {
     Movies,
     MostRecent,
     MyList,
+    People,
 }
 
 #[derive(Debug, Clone)]
@@ -85,19 +122,41 @@ pub enum LoadingState {
     Error(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MediaType {
     Movie,
     TvSeries,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Genre {
     pub id: u64,
     pub name: String,
+    /// Which of TMDb's genre lists the genre appears in.
+    #[serde(default)]
+    pub media_scope: GenreScope,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GenreScope {
+    Movie,
+    Tv,
+    #[default]
+    Both,
+}
+
+impl GenreScope {
+    pub fn matches(self, filter: MediaTypeFilter) -> bool {
+        match (self, filter) {
+            (GenreScope::Both, _) | (_, MediaTypeFilter::All) => true,
+            (GenreScope::Movie, MediaTypeFilter::Movies) => true,
+            (GenreScope::Tv, MediaTypeFilter::TvSeries) => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum MediaTypeFilter {
     #[default]
     All,
@@ -116,6 +175,24 @@ impl std::fmt::Display for MediaTypeFilter {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GenreMatch {
+    /// Items with at least one of the selected genres (OR).
+    #[default]
+    Any,
+    /// Items with every selected genre (AND).
+    All,
+}
+
+impl std::fmt::Display for GenreMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenreMatch::Any => write!(f, "Any"),
+            GenreMatch::All => write!(f, "All"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum SortOption {
     #[default]
     Popularity,
@@ -138,14 +215,62 @@ impl std::fmt::Display for SortOption {
 #[derive(Debug, Clone, Default)]
 pub struct SearchFilters {
     pub media_type: MediaTypeFilter,
-    pub genre_id: Option<u64>,
+    pub genre_ids: Vec<u64>,
+    pub genre_match: GenreMatch,
     pub year_from: Option<u32>,
     pub year_to: Option<u32>,
     pub min_rating: f32,
     pub sort_by: SortOption,
 }
 
+/// The filters that reflect a standing preference rather than a single query.
+/// They survive clearing the search and are persisted in the settings.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct SearchPreferences {
+    #[serde(default)]
+    pub media_type: MediaTypeFilter,
+    #[serde(default)]
+    pub min_rating: f32,
+    #[serde(default)]
+    pub sort_by: SortOption,
+}
+
+fn rating_tenths(rating: f32) -> i32 {
+    (rating * 10.0).round() as i32
+}
+
 impl SearchFilters {
+    pub fn from_preferences(preferences: SearchPreferences) -> Self {
+        Self {
+            media_type: preferences.media_type,
+            min_rating: preferences.min_rating,
+            sort_by: preferences.sort_by,
+            ..Default::default()
+        }
+    }
+
+    pub fn preferences(&self) -> SearchPreferences {
+        SearchPreferences {
+            media_type: self.media_type,
+            min_rating: self.min_rating,
+            sort_by: self.sort_by,
+        }
+    }
+
+    /// Genres or a year range are enough to browse by without a query.
+    pub fn has_browse_filters(&self) -> bool {
+        !self.genre_ids.is_empty() || self.year_from.is_some() || self.year_to.is_some()
+    }
+
+    pub fn year_range(&self) -> (Option<u32>, Option<u32>) {
+        self.normalized_year_range()
+    }
+
+    /// Resets the query-specific filters (genres and year range).
+    pub fn clear_query_filters(&mut self) {
+        *self = Self::from_preferences(self.preferences());
+    }
+
     pub fn apply(&self, items: &[MediaItem]) -> Vec<MediaItem> {
         let mut filtered: Vec<MediaItem> = items
             .iter()
@@ -172,9 +297,20 @@ impl SearchFilters {
     }
 
     fn matches_genre(&self, item: &MediaItem) -> bool {
-        match self.genre_id {
-            None => true,
-            Some(id) => item.genres.iter().any(|g| g.id == id),
+        let has_genre = |id: &u64| item.genres.iter().any(|g| g.id == *id);
+        match self.genre_match {
+            _ if self.genre_ids.is_empty() => true,
+            GenreMatch::Any => self.genre_ids.iter().any(has_genre),
+            GenreMatch::All => self.genre_ids.iter().all(has_genre),
+        }
+    }
+
+    pub fn toggle_genre(&mut self, genre_id: u64) {
+        match self.genre_ids.iter().position(|id| *id == genre_id) {
+            Some(index) => {
+                self.genre_ids.remove(index);
+            }
+            None => self.genre_ids.push(genre_id),
         }
     }
 
@@ -202,8 +338,10 @@ impl SearchFilters {
         }
     }
 
+    /// Compares in tenths, the precision ratings are displayed with, so an item
+    /// shown as 8.0 always passes an 8.0+ filter regardless of float drift.
     fn matches_rating(&self, item: &MediaItem) -> bool {
-        item.vote_average >= self.min_rating
+        rating_tenths(item.vote_average) >= rating_tenths(self.min_rating)
     }
 
     fn sort(&self, items: &mut [MediaItem]) {
@@ -225,7 +363,7 @@ impl SearchFilters {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CastMember {
     pub id: u64,
     pub name: String,
@@ -234,7 +372,15 @@ pub struct CastMember {
     pub order: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrewMember {
+    pub id: u64,
+    pub name: String,
+    pub job: String,
+    pub profile_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Collection {
     pub id: u64,
     pub name: String,
@@ -243,7 +389,7 @@ pub struct Collection {
     pub parts: Vec<MediaItem>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ExternalIds {
     pub imdb_id: Option<String>,
     pub facebook_id: Option<String>,
@@ -252,13 +398,49 @@ pub struct ExternalIds {
     pub homepage: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+/// TMDb stores social ids as bare handles, sometimes with a leading `@`, but
+/// an id that is already a full URL is used as is.
+fn profile_url(base: &str, id: Option<&String>) -> Option<String> {
+    let id = id?.trim().trim_start_matches('@');
+    if id.is_empty() {
+        return None;
+    }
+    if id.starts_with("http://") || id.starts_with("https://") {
+        return Some(id.to_string());
+    }
+    Some(format!("{}{}", base, id))
+}
+
+impl ExternalIds {
+    pub fn imdb_url(&self) -> Option<String> {
+        profile_url("https://www.imdb.com/title/", self.imdb_id.as_ref())
+    }
+
+    pub fn facebook_url(&self) -> Option<String> {
+        profile_url("https://www.facebook.com/", self.facebook_id.as_ref())
+    }
+
+    pub fn twitter_url(&self) -> Option<String> {
+        profile_url("https://x.com/", self.twitter_id.as_ref())
+    }
+
+    pub fn instagram_url(&self) -> Option<String> {
+        profile_url("https://www.instagram.com/", self.instagram_id.as_ref())
+    }
+
+    /// Homepages are usually complete URLs; a bare domain gets `https://`.
+    pub fn homepage_url(&self) -> Option<String> {
+        profile_url("https://", self.homepage.as_ref())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Keyword {
     pub id: u64,
     pub name: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProductionCompany {
     pub id: u64,
     pub name: String,
@@ -266,7 +448,7 @@ pub struct ProductionCompany {
     pub origin_country: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Season {
     pub id: u64,
     pub season_number: u32,
@@ -276,7 +458,7 @@ pub struct Season {
     pub poster_path: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Episode {
     pub id: u64,
     pub episode_number: u32,
@@ -290,21 +472,45 @@ pub struct Episode {
 }
 
 #[derive(Debug, Clone)]
+pub struct Person {
+    pub id: u64,
+    pub name: String,
+    pub profile_path: Option<String>,
+    pub known_for_department: String,
+    pub known_for: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PersonDetail {
+    pub person: Person,
+    pub biography: String,
+    pub birthday: Option<String>,
+    pub place_of_birth: Option<String>,
+    pub filmography: Vec<MediaItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetailPopupData {
     pub media_item: MediaItem,
     pub cast: Vec<CastMember>,
+    pub crew: Vec<CrewMember>,
     pub collection: Option<Collection>,
     pub similar: Vec<MediaItem>,
     pub external_ids: ExternalIds,
     pub keywords: Vec<Keyword>,
     pub production_companies: Vec<ProductionCompany>,
     pub seasons: Vec<Season>,
+    /// Playable YouTube videos (trailers, teasers, featurettes) for the picker.
+    pub videos: Vec<crate::video::TrailerVideo>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaItem {
     pub id: MediaId,
     pub title: String,
+    /// Title in the original language, used when the localized title is missing.
+    #[serde(default)]
+    pub original_title: Option<String>,
     pub overview: String,
     pub poster_path: Option<String>,
     pub backdrop_path: Option<String>,
@@ -313,6 +519,9 @@ pub struct MediaItem {
     pub vote_average: f32,
     pub release_date: Option<String>,
     pub runtime: Option<u32>,
+    /// Shortest and longest episode length, for series whose episodes vary.
+    #[serde(default)]
+    pub runtime_range: Option<(u32, u32)>,
     pub certification: Option<String>,
     pub tagline: Option<String>,
     pub genres: Vec<Genre>,
@@ -320,13 +529,16 @@ pub struct MediaItem {
     pub revenue: Option<u64>,
     pub status: Option<String>,
     pub original_language: Option<String>,
+    pub production_countries: Vec<String>,
+    pub spoken_languages: Vec<String>,
     pub collection_id: Option<u64>,
     pub number_of_episodes: Option<u32>,
     pub number_of_seasons: Option<u32>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Category {
+    ContinueWatching,
     Trending,
     TopRated,
     MostRecent,
@@ -335,13 +547,43 @@ pub enum Category {
     Drama,
     Series,
     Recommended,
+    NowPlaying,
+    Upcoming,
+    StreamingProvider(u64),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchProvider {
+    pub id: u64,
+    pub name: String,
+}
+
+/// The request a home row was built from, so a failed row can be retried alone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SectionQuery {
+    ContinueWatching,
+    TopPicks(TopPicksSource),
+    TopRatedMovies,
+    NowPlaying,
+    Upcoming,
+    TopRatedSeries,
+    Genre(u32),
+    Provider(u64),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContentSection {
     pub title: String,
     pub category: Category,
     pub items: Vec<MediaItem>,
+    #[serde(default)]
+    pub query: Option<SectionQuery>,
+    /// Set when the row failed to load; it then has no items.
+    #[serde(skip)]
+    pub error: Option<String>,
+    /// Set while a retry of the row is in flight.
+    #[serde(skip)]
+    pub loading: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -359,33 +601,144 @@ impl Default for HeaderState {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+pub const SECTIONS_SNAPSHOT: &str = "home_sections.json";
+pub const HERO_SNAPSHOT: &str = "hero_items.json";
+
+fn snapshot_path(name: &str) -> Option<PathBuf> {
+    get_cache_dir()?.parent().map(|dir| dir.join(name))
+}
+
+/// Where `TmdbClient` keeps API responses between sessions.
+pub fn api_cache_dir() -> Option<PathBuf> {
+    get_cache_dir()?.parent().map(|dir| dir.join("api"))
+}
+
+/// Persists the last successfully loaded home page data so it can be shown
+/// again when TMDb is unreachable. Serializing and writing happen on a
+/// background thread, off the update loop.
+pub fn save_offline_snapshot<T: Serialize + Send + 'static>(name: &str, data: T) {
+    let Some(path) = snapshot_path(name) else {
+        return;
+    };
+    std::thread::spawn(move || {
+        if let Ok(json) = serde_json::to_string(&data) {
+            let _ = std::fs::write(path, json);
+        }
+    });
+}
+
+pub fn load_offline_snapshot<T: for<'de> Deserialize<'de>>(name: &str) -> Option<T> {
+    let content = std::fs::read_to_string(snapshot_path(name)?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+const MAX_IMAGE_LOAD_ATTEMPTS: u32 = 3;
+/// Room for a full home page: up to 13 rows of 40 posters, plus the hero's
+/// backdrops and logos and the images of hovered cards.
+pub const DEFAULT_IMAGE_CACHE_ENTRIES: usize = 800;
+pub const DEFAULT_IMAGE_DISK_BUDGET_MB: u64 = 500;
+
+/// Decoded handles are kept in memory up to `max_entries`, dropping the least
+/// recently used first. Dropped images stay on disk, so showing them again
+/// only costs a disk read.
+#[derive(Debug, Clone)]
 pub struct ImageCache {
-    cache: HashMap<String, Handle>,
+    /// Each handle with the access tick it was last used at.
+    cache: HashMap<String, (Handle, Cell<u64>)>,
+    access_tick: Cell<u64>,
+    max_entries: usize,
     pending: HashSet<String>,
+    /// Images a view found missing while drawing, waiting to be loaded.
+    requested: RefCell<HashSet<String>>,
+    failures: HashMap<String, u32>,
     cache_directory: Option<PathBuf>,
 }
 
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ImageCache {
+    /// Starts memory-only; the disk cache is switched on from the settings with
+    /// `set_disk_cache_enabled`.
     pub fn new() -> Self {
-        let cache_directory = get_cache_dir();
-        if let Some(ref dir) = cache_directory {
-            let _ = std::fs::create_dir_all(dir);
-        }
         Self {
             cache: HashMap::new(),
+            access_tick: Cell::new(0),
+            max_entries: DEFAULT_IMAGE_CACHE_ENTRIES,
             pending: HashSet::new(),
-            cache_directory,
+            requested: RefCell::new(HashSet::new()),
+            failures: HashMap::new(),
+            cache_directory: None,
+        }
+    }
+
+    pub fn set_max_entries(&mut self, max_entries: usize) {
+        self.max_entries = max_entries.max(1);
+        self.evict_to_capacity();
+    }
+
+    fn next_tick(&self) -> u64 {
+        let tick = self.access_tick.get() + 1;
+        self.access_tick.set(tick);
+        tick
+    }
+
+    fn evict_to_capacity(&mut self) {
+        while self.cache.len() > self.max_entries {
+            let Some(oldest) = self
+                .cache
+                .iter()
+                .min_by_key(|(_, (_, last_used))| last_used.get())
+                .map(|(url, _)| url.clone())
+            else {
+                break;
+            };
+            self.cache.remove(&oldest);
+        }
+    }
+
+    /// With the disk cache off, images are always fetched fresh and only kept
+    /// in memory for the session.
+    pub fn set_disk_cache_enabled(&mut self, enabled: bool) {
+        self.cache_directory = if enabled { get_cache_dir() } else { None };
+        if let Some(ref dir) = self.cache_directory {
+            let _ = std::fs::create_dir_all(dir);
         }
     }
 
+    /// Views look images up on every draw, so this is what marks an image as
+    /// recently used.
     pub fn get(&self, url: &str) -> Option<&Handle> {
-        self.cache.get(url)
+        let (handle, last_used) = self.cache.get(url)?;
+        last_used.set(self.next_tick());
+        Some(handle)
     }
 
     pub fn insert(&mut self, url: String, handle: Handle) {
         self.pending.remove(&url);
-        self.cache.insert(url, handle);
+        self.failures.remove(&url);
+        let tick = self.next_tick();
+        self.cache.insert(url, (handle, Cell::new(tick)));
+        self.evict_to_capacity();
+    }
+
+    /// Lets a view that drew a placeholder ask for the image, so one evicted
+    /// while still on screen is loaded again.
+    pub fn request(&self, url: &str) {
+        if !self.cache.contains_key(url) && !self.pending.contains(url) && self.can_retry(url) {
+            self.requested.borrow_mut().insert(url.to_string());
+        }
+    }
+
+    pub fn has_requests(&self) -> bool {
+        !self.requested.borrow().is_empty()
+    }
+
+    pub fn take_requests(&self) -> Vec<String> {
+        self.requested.borrow_mut().drain().collect()
     }
 
     pub fn is_pending(&self, url: &str) -> bool {
@@ -396,11 +749,76 @@ impl ImageCache {
         self.pending.insert(url);
     }
 
+    pub fn clear_pending(&mut self, url: &str) {
+        self.pending.remove(url);
+    }
+
+    /// Records a failed load and returns how many times the URL has failed.
+    pub fn record_failure(&mut self, url: &str) -> u32 {
+        let count = self.failures.entry(url.to_string()).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    /// Failures while offline say nothing about the image, so they are
+    /// forgotten once the connection is back.
+    pub fn clear_failures(&mut self) {
+        self.failures.clear();
+    }
+
+    pub fn can_retry(&self, url: &str) -> bool {
+        self.failures.get(url).copied().unwrap_or(0) < MAX_IMAGE_LOAD_ATTEMPTS
+    }
+
     pub fn get_cache_path(&self, url: &str) -> Option<PathBuf> {
         self.cache_directory
             .as_ref()
             .map(|dir| dir.join(simple_hash(url)))
     }
+
+    /// Trims the disk cache down to `max_bytes` on a background thread so the
+    /// directory scan doesn't hold up the first frame.
+    pub fn enforce_disk_budget(&self, max_bytes: u64) {
+        let Some(directory) = self.cache_directory.clone() else {
+            return;
+        };
+        std::thread::spawn(move || {
+            let _ = prune_directory(&directory, max_bytes);
+        });
+    }
+}
+
+/// Deletes the least recently written files in `directory` until the rest fit
+/// in `max_bytes`, returning how many were removed.
+fn prune_directory(directory: &Path, max_bytes: u64) -> std::io::Result<usize> {
+    let mut files = Vec::new();
+    let mut total: u64 = 0;
+    for entry in std::fs::read_dir(directory)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        let modified = metadata.modified().unwrap_or(std::time::UNIX_EPOCH);
+        total += metadata.len();
+        files.push((modified, metadata.len(), entry.path()));
+    }
+    if total <= max_bytes {
+        return Ok(0);
+    }
+
+    files.sort_by_key(|(modified, _, _)| *modified);
+    let mut removed = 0;
+    for (_, size, path) in files {
+        if total <= max_bytes {
+            break;
+        }
+        if std::fs::remove_file(&path).is_ok() {
+            total = total.saturating_sub(size);
+            removed += 1;
+        }
+    }
+    Ok(removed)
 }
 
 #[derive(Debug, Clone)]
@@ -415,9 +833,160 @@ pub enum ApiError {
 pub enum ProfileAction {
     OpenSettings,
     OpenProfileSettings,
+    OpenFile,
     SwitchProfile(u64),
 }
 
+#[derive(Debug, Clone)]
+pub enum LaunchAction {
+    Open,
+    Play,
+}
+
+#[derive(Debug, Clone)]
+pub struct LaunchRequest {
+    pub action: LaunchAction,
+    pub media_type: MediaType,
+    pub media_id: MediaId,
+}
+
+pub const LAUNCH_USAGE: &str = "usage: movix [--open <movie|tv>:<id> | --play <movie|tv>:<id>]";
+
+impl LaunchRequest {
+    pub fn from_args(args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut args = args.skip(1);
+        let mut request = None;
+        while let Some(arg) = args.next() {
+            let action = match arg.as_str() {
+                "--open" => LaunchAction::Open,
+                "--play" => LaunchAction::Play,
+                _ => return Err(format!("Unknown argument '{}'", arg)),
+            };
+            if request.is_some() {
+                return Err(String::from(
+                    "Only one --open or --play target can be given",
+                ));
+            }
+            let target = args
+                .next()
+                .ok_or_else(|| format!("{} expects a target like movie:12345", arg))?;
+            request = Some(Self::parse_target(action, &target)?);
+        }
+        Ok(request)
+    }
+
+    fn parse_target(action: LaunchAction, target: &str) -> Result<Self, String> {
+        let (kind, id) = target.split_once(':').ok_or_else(|| {
+            format!(
+                "Invalid target '{}', expected movie:<id> or tv:<id>",
+                target
+            )
+        })?;
+        let media_type = match kind {
+            "movie" => MediaType::Movie,
+            "tv" | "series" => MediaType::TvSeries,
+            _ => return Err(format!("Unknown media type '{}'", kind)),
+        };
+        let media_id = id
+            .parse::<MediaId>()
+            .map_err(|_| format!("Invalid TMDB id '{}'", id))?;
+        Ok(Self {
+            action,
+            media_type,
+            media_id,
+        })
+    }
+}
+
+/// What a single click on a media card does. Double-clicking a card always plays it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CardClickAction {
+    /// Open the quick detail popup over the current page.
+    #[default]
+    OpenPopup,
+    /// Start playback immediately.
+    Play,
+    /// Navigate to the full-page detail view.
+    OpenPage,
+}
+
+impl CardClickAction {
+    pub fn message(self, media_id: MediaId) -> Message {
+        match self {
+            CardClickAction::OpenPopup => Message::OpenDetailPopup(media_id),
+            CardClickAction::Play => Message::PlayContent(media_id),
+            CardClickAction::OpenPage => Message::NavigateTo(Page::Detail(media_id)),
+        }
+    }
+}
+
+/// How tightly search and browse results are packed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GridDensity {
+    #[default]
+    Comfortable,
+    Compact,
+}
+
+impl GridDensity {
+    pub fn cards_per_row(self) -> usize {
+        match self {
+            GridDensity::Comfortable => 4,
+            GridDensity::Compact => 5,
+        }
+    }
+
+    pub fn spacing(self) -> f32 {
+        match self {
+            GridDensity::Comfortable => 16.0,
+            GridDensity::Compact => 8.0,
+        }
+    }
+
+    /// Card width and height, both keeping the 16:9 backdrop ratio.
+    pub fn card_size(self) -> (f32, f32) {
+        match self {
+            GridDensity::Comfortable => (276.0, 155.0),
+            GridDensity::Compact => (204.0, 115.0),
+        }
+    }
+}
+
+/// Where the first home row, "Top Picks", gets its titles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TopPicksSource {
+    #[default]
+    Trending,
+    /// Recommendations seeded by titles with saved playback progress.
+    Recommended,
+    /// Popular movies in one TMDb genre.
+    Genre(u32),
+    /// Titles with saved playback progress.
+    ContinueWatching,
+}
+
+/// How a hovered card in a row grows into the wider backdrop card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CardExpandStyle {
+    /// Swap the card for the backdrop card, pushing its neighbours aside.
+    #[default]
+    Replace,
+    /// Draw the backdrop card above the row, covering its neighbours.
+    Overlay,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum PageScroll {
+    PageUp,
+    PageDown,
+    Home,
+    End,
+}
+
 #[derive(Debug, Clone)]
 pub enum ScrollDirection {
     Left,
@@ -430,22 +999,45 @@ pub enum Message {
     NavigateTo(Page),
     SearchQueryChanged(String),
     SearchSubmit,
-    SearchResultsLoaded(Result<Vec<MediaItem>, ApiError>),
+    SearchResultsLoaded(Result<SearchPage, ApiError>),
+    /// A further page for the query it was requested with.
+    SearchNextPageLoaded(String, Result<SearchPage, ApiError>),
     ToggleProfileMenu,
     CloseProfileMenu,
     ProfileAction(ProfileAction),
     PlayContent(MediaId),
+    OpenExternalUrl(String),
+    PlayEpisode(MediaId, u32, u32),
     ShowMoreInfo(MediaId),
     HoverCard(Option<MediaId>),
     HoverCardDelayed(MediaId),
     HoverSection(Option<usize>),
     ContentLoaded(Result<Vec<ContentSection>, ApiError>),
-    HeroLoaded(Box<Result<MediaItem, ApiError>>),
+    HeroLoaded(Box<Result<Vec<MediaItem>, ApiError>>),
+    HeroRotateTick,
+    LoadingWatchdogTick,
+    HeroNext,
+    HeroPrevious,
+    HeroSelect(usize),
+    ShuffleHero,
+    Launch(LaunchRequest),
+    LaunchDetailsLoaded(Box<Result<MediaItem, ApiError>>),
+    HeroShuffled(Box<Result<MediaItem, ApiError>>),
+    HeroColorSampled(String, Option<iced::Color>),
+    HeroGradientTick,
     ImageLoaded(String, Result<Handle, String>),
     LoadImage(String),
+    LoadRequestedImages,
+    RetryImage(String),
+    FetchLogo(MediaId, MediaType),
     LogoLoaded(MediaId, Result<Option<String>, ApiError>),
     RetryLoad,
+    CheckConnectivity,
+    ConnectivityChecked(bool),
+    ProxyChecked(Result<(), String>),
     ScrollSection(usize, ScrollDirection),
+    WheelScrollSection(usize, iced::mouse::ScrollDelta),
+    ModifiersChanged(iced::keyboard::Modifiers),
     SectionScrolled(usize, f32),
     AnimateScroll(usize),
     TrailerVideosLoaded(MediaId, Result<Vec<crate::video::TrailerVideo>, ApiError>),
@@ -460,7 +1052,10 @@ pub enum Message {
     PauseHeroTrailer,
     ResumeHeroTrailer,
     HeroVisibilityChanged(bool),
-    MainScrolled(f32),
+    /// Offset, viewport height and content height of the main scrollable.
+    MainScrolled(f32, f32, f32),
+    WindowResized(iced::Size),
+    PageScroll(PageScroll),
     ToggleHeroMute,
     ReplayHeroTrailer,
     HeroVideoEnded,
@@ -470,25 +1065,69 @@ pub enum Message {
     MoviePlayerSeek(f64),
     MoviePlayerSeekRelative(f64),
     MoviePlayerSetVolume(f64),
+    MoviePlayerAdjustVolume(f64),
+    MoviePlayerSetSpeed(f32),
+    MoviePlayerSetQuality(Option<u32>),
+    SaveVolumeTick,
+    SaveSearchPreferencesTick,
     MoviePlayerToggleMute,
     MoviePlayerToggleFullscreen,
+    MoviePlayerEscape,
+    MoviePlayerSetSubtitle(Option<String>),
+    MoviePlayerSubtitlesLoaded(String, Result<Vec<crate::subtitles::SubtitleCue>, String>),
+    WindowIdentified(iced::window::Id),
+    MoviePlayerToggleMetrics,
     MoviePlayerFrameTick,
-    MoviePlayerStreamResolved(MediaId, Result<String, String>),
+    MoviePlayerAutoSave,
+    MoviePlayerStreamResolved(
+        MediaId,
+        Result<crate::streaming::ResolvedStream, crate::streaming::StreamError>,
+    ),
+    MoviePlayerRetry,
+    MoviePlayerReportBroken,
+    MoviePlayerTrailerResolved(MediaId, Result<String, String>),
     MoviePlayerShowControls,
     MoviePlayerHideControls,
+    MoviePlayerResumeStored,
+    MoviePlayerStartOver,
     OpenDetailPopup(MediaId),
     CloseDetailPopup,
+    AddToList(MediaId),
+    RemoveFromList(MediaId),
+    RetrySection(usize),
+    SectionLoaded(usize, SectionQuery, Result<Vec<MediaItem>, ApiError>),
+    DismissWhatsNew,
+    OpenFilePathChanged(String),
+    OpenFileSubmit,
+    CloseOpenFileDialog,
     DetailDataLoaded(Box<Result<DetailPopupData, ApiError>>),
     DetailSelectSeason(Option<u32>),
+    SetStreamProvider(MediaId, Option<String>),
     DetailEpisodesLoaded(Result<Vec<Episode>, ApiError>),
     DetailHoverCard(Option<MediaId>),
     DetailHoverCardDelayed(MediaId),
     DetailFrameTick,
     DetailTrailerLoaded(MediaId, Result<String, String>),
+    DetailSelectVideo(usize),
+    DetailVideoLoaded(MediaId, usize, Result<String, String>),
+    ToggleDetailAdvanced,
+    ToggleDetailCast,
+    ToggleOverview,
+    RevealEpisode(u64),
+    DetailImdbRatingLoaded(String, Result<Option<String>, ApiError>),
+    BrowseGenre(u64),
+    GenreBrowseLoaded(Result<Vec<MediaItem>, ApiError>),
+    RetryGenreBrowse,
+    CloseGenreBrowse,
+    PopularPeopleLoaded(Result<Vec<Person>, ApiError>),
+    PopularSuggestionsLoaded(Result<Vec<MediaItem>, ApiError>),
+    PersonDetailLoaded(u64, Result<PersonDetail, ApiError>),
     SearchDebounceTriggered,
     ClearSearch,
     SetMediaTypeFilter(MediaTypeFilter),
     SetGenreFilter(Option<u64>),
+    ToggleGenreFilter(u64),
+    SetGenreMatch(GenreMatch),
     SetYearFrom(Option<u32>),
     SetYearTo(Option<u32>),
     SetMinRating(f32),
@@ -500,43 +1139,128 @@ pub enum Message {
 #[derive(Debug, Clone, Deserialize)]
 pub struct TmdbMediaResult {
     pub id: u64,
+    #[serde(default, deserialize_with = "lenient")]
     pub title: Option<String>,
+    #[serde(default, deserialize_with = "lenient")]
     pub name: Option<String>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
+    pub original_title: Option<String>,
+    #[serde(default, deserialize_with = "lenient")]
+    pub original_name: Option<String>,
+    #[serde(default, deserialize_with = "lenient")]
     pub overview: String,
+    #[serde(default, deserialize_with = "lenient")]
     pub poster_path: Option<String>,
+    #[serde(default, deserialize_with = "lenient")]
     pub backdrop_path: Option<String>,
+    #[serde(default, deserialize_with = "lenient")]
     pub media_type: Option<String>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub vote_average: f32,
+    #[serde(default, deserialize_with = "lenient")]
     pub release_date: Option<String>,
+    #[serde(default, deserialize_with = "lenient")]
     pub first_air_date: Option<String>,
+    #[serde(default, deserialize_with = "lenient")]
+    pub genre_ids: Vec<u64>,
+}
+
+/// Treats `null` or a value of an unexpected type as missing, so one odd field
+/// from TMDb doesn't fail the whole result.
+fn lenient<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned + Default,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).unwrap_or_default())
+}
+
+/// Drops list entries that can't be parsed instead of failing the whole list.
+fn skip_invalid<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let values = Vec::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(values
+        .into_iter()
+        .filter_map(|value| serde_json::from_value(value).ok())
+        .collect())
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|s| !s.trim().is_empty())
+}
+
+impl TmdbMediaResult {
+    /// Endpoints scoped to one type (`/tv/top_rated`, `/discover/tv`, ...) omit
+    /// `media_type`, so TV results are recognised by their TV-only fields.
+    fn resolved_media_type(&self) -> MediaType {
+        match self.media_type.as_deref() {
+            Some("tv") => MediaType::TvSeries,
+            Some(_) => MediaType::Movie,
+            None if self.first_air_date.is_some() || self.title.is_none() => MediaType::TvSeries,
+            None => MediaType::Movie,
+        }
+    }
 }
 
 impl From<TmdbMediaResult> for MediaItem {
     fn from(result: TmdbMediaResult) -> Self {
-        let media_type = match result.media_type.as_deref() {
-            Some("tv") => MediaType::TvSeries,
-            _ => MediaType::Movie,
+        let media_type = result.resolved_media_type();
+        let release_date = match media_type {
+            MediaType::TvSeries => {
+                non_empty(result.first_air_date).or(non_empty(result.release_date))
+            }
+            MediaType::Movie => non_empty(result.release_date).or(non_empty(result.first_air_date)),
+        };
+        let (title, original_title) = match media_type {
+            MediaType::TvSeries => (
+                non_empty(result.name).or(non_empty(result.title)),
+                non_empty(result.original_name).or(non_empty(result.original_title)),
+            ),
+            MediaType::Movie => (
+                non_empty(result.title).or(non_empty(result.name)),
+                non_empty(result.original_title).or(non_empty(result.original_name)),
+            ),
         };
+        // Lists only carry genre ids; the names come with the details.
+        let media_scope = match media_type {
+            MediaType::Movie => GenreScope::Movie,
+            MediaType::TvSeries => GenreScope::Tv,
+        };
+        let genres = result
+            .genre_ids
+            .into_iter()
+            .map(|id| Genre {
+                id,
+                name: String::new(),
+                media_scope,
+            })
+            .collect();
         Self {
             id: result.id,
-            title: result.title.or(result.name).unwrap_or_default(),
+            title: title.or(original_title.clone()).unwrap_or_default(),
+            original_title,
             overview: result.overview,
             poster_path: result.poster_path,
             backdrop_path: result.backdrop_path,
             logo_path: None,
             media_type,
             vote_average: result.vote_average,
-            release_date: result.release_date.or(result.first_air_date),
+            release_date,
             runtime: None,
+            runtime_range: None,
             certification: None,
             tagline: None,
-            genres: Vec::new(),
+            genres,
             budget: None,
             revenue: None,
             status: None,
             original_language: None,
+            production_countries: Vec::new(),
+            spoken_languages: Vec::new(),
             collection_id: None,
             number_of_episodes: None,
             number_of_seasons: None,
@@ -546,7 +1270,20 @@ impl From<TmdbMediaResult> for MediaItem {
 
 #[derive(Deserialize)]
 pub struct TmdbSearchResponse {
+    #[serde(default, deserialize_with = "skip_invalid")]
     pub results: Vec<TmdbMediaResult>,
+    #[serde(default)]
+    pub page: u32,
+    #[serde(default)]
+    pub total_pages: u32,
+}
+
+/// One page of search results and where it sits in the full result set.
+#[derive(Debug, Clone)]
+pub struct SearchPage {
+    pub items: Vec<MediaItem>,
+    pub page: u32,
+    pub total_pages: u32,
 }
 
 pub fn truncate_description(description: &str, max_length: usize) -> String {
@@ -559,3 +1296,307 @@ pub fn truncate_description(description: &str, max_length: usize) -> String {
         truncated.rfind(' ').map_or(truncated, |i| &truncated[..i])
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handle() -> Handle {
+        Handle::from_rgba(1, 1, vec![0, 0, 0, 255])
+    }
+
+    fn media_item(id: MediaId, vote_average: f32) -> MediaItem {
+        MediaItem {
+            id,
+            title: format!("Title {}", id),
+            original_title: None,
+            overview: String::new(),
+            poster_path: None,
+            backdrop_path: None,
+            logo_path: None,
+            media_type: MediaType::Movie,
+            vote_average,
+            release_date: None,
+            runtime: None,
+            runtime_range: None,
+            certification: None,
+            tagline: None,
+            genres: Vec::new(),
+            budget: None,
+            revenue: None,
+            status: None,
+            original_language: None,
+            production_countries: Vec::new(),
+            spoken_languages: Vec::new(),
+            collection_id: None,
+            number_of_episodes: None,
+            number_of_seasons: None,
+        }
+    }
+
+    fn filtered_ids(filters: &SearchFilters, items: &[MediaItem]) -> Vec<MediaId> {
+        let mut ids: Vec<MediaId> = filters.apply(items).iter().map(|i| i.id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn rating_filter_includes_the_boundary() {
+        let items = [media_item(1, 8.0), media_item(2, 7.9), media_item(3, 8.1)];
+        let filters = SearchFilters {
+            min_rating: 8.0,
+            ..Default::default()
+        };
+        assert_eq!(filtered_ids(&filters, &items), vec![1, 3]);
+    }
+
+    #[test]
+    fn rating_filter_ignores_float_drift_from_slider_steps() {
+        // Ten 0.1 steps land on 1.0000001, just above an item rated 1.0.
+        let min_rating = (0..10).fold(0.0_f32, |acc, _| acc + 0.1);
+        let filters = SearchFilters {
+            min_rating,
+            ..Default::default()
+        };
+        assert_eq!(filtered_ids(&filters, &[media_item(1, 1.0)]), vec![1]);
+        assert_eq!(
+            filtered_ids(&filters, &[media_item(2, 0.9)]),
+            Vec::<MediaId>::new()
+        );
+    }
+
+    #[test]
+    fn failed_image_load_can_be_retried() {
+        let mut cache = ImageCache::new();
+        let url = String::from("https://image.tmdb.org/t/p/w500/poster.jpg");
+
+        cache.mark_pending(url.clone());
+        assert_eq!(cache.record_failure(&url), 1);
+        cache.clear_pending(&url);
+        assert!(!cache.is_pending(&url));
+        assert!(cache.can_retry(&url));
+
+        cache.mark_pending(url.clone());
+        cache.insert(url.clone(), handle());
+        assert!(!cache.is_pending(&url));
+        assert!(cache.get(&url).is_some());
+    }
+
+    #[test]
+    fn image_load_stops_retrying_after_max_attempts() {
+        let mut cache = ImageCache::new();
+        let url = "https://image.tmdb.org/t/p/w500/missing.jpg";
+        for _ in 0..MAX_IMAGE_LOAD_ATTEMPTS {
+            cache.record_failure(url);
+        }
+        assert!(!cache.can_retry(url));
+    }
+
+    #[test]
+    fn least_recently_used_image_is_evicted_first() {
+        let mut cache = ImageCache::new();
+        cache.set_max_entries(2);
+        cache.insert(String::from("touched"), handle());
+        cache.insert(String::from("untouched"), handle());
+        assert!(cache.get("touched").is_some());
+
+        cache.insert(String::from("newest"), handle());
+
+        assert!(cache.get("untouched").is_none());
+        assert!(cache.get("touched").is_some());
+        assert!(cache.get("newest").is_some());
+    }
+
+    #[test]
+    fn evicted_image_requested_by_a_view_is_queued_once() {
+        let mut cache = ImageCache::new();
+        cache.set_max_entries(1);
+        cache.insert(String::from("evicted"), handle());
+        cache.insert(String::from("kept"), handle());
+
+        cache.request("evicted");
+        cache.request("evicted");
+        cache.request("kept");
+
+        assert_eq!(cache.take_requests(), vec![String::from("evicted")]);
+        assert!(!cache.has_requests());
+    }
+
+    #[test]
+    fn disk_cache_prunes_the_oldest_files_first() {
+        let directory =
+            std::env::temp_dir().join(format!("movix-prune-test-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let now = std::time::SystemTime::now();
+        for (name, age_secs) in [("oldest", 300), ("middle", 200), ("newest", 100)] {
+            let file = std::fs::File::create(directory.join(name)).unwrap();
+            file.set_len(100).unwrap();
+            file.set_modified(now - std::time::Duration::from_secs(age_secs))
+                .unwrap();
+        }
+
+        let removed = prune_directory(&directory, 250).unwrap();
+        let remaining = ["oldest", "middle", "newest"].map(|name| directory.join(name).exists());
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(removed, 1);
+        assert_eq!(remaining, [false, true, true]);
+    }
+
+    fn parse_result(json: serde_json::Value) -> MediaItem {
+        MediaItem::from(serde_json::from_value::<TmdbMediaResult>(json).unwrap())
+    }
+
+    #[test]
+    fn tv_results_use_the_first_air_date() {
+        let item = parse_result(serde_json::json!({
+            "id": 1399,
+            "name": "Game of Thrones",
+            "media_type": "tv",
+            "first_air_date": "2011-04-17"
+        }));
+        assert!(matches!(item.media_type, MediaType::TvSeries));
+        assert_eq!(item.release_date.as_deref(), Some("2011-04-17"));
+    }
+
+    #[test]
+    fn tv_results_without_media_type_still_get_their_air_date() {
+        let item = parse_result(serde_json::json!({
+            "id": 1399,
+            "name": "Game of Thrones",
+            "first_air_date": "2011-04-17",
+            "release_date": ""
+        }));
+        assert!(matches!(item.media_type, MediaType::TvSeries));
+        assert_eq!(item.release_date.as_deref(), Some("2011-04-17"));
+    }
+
+    #[test]
+    fn movie_results_use_the_release_date() {
+        let item = parse_result(serde_json::json!({
+            "id": 603,
+            "title": "The Matrix",
+            "media_type": "movie",
+            "release_date": "1999-03-30"
+        }));
+        assert!(matches!(item.media_type, MediaType::Movie));
+        assert_eq!(item.release_date.as_deref(), Some("1999-03-30"));
+    }
+
+    #[test]
+    fn movie_results_read_title_and_original_title() {
+        let item = parse_result(serde_json::json!({
+            "id": 129,
+            "title": "Spirited Away",
+            "original_title": "千と千尋の神隠し",
+            "media_type": "movie"
+        }));
+        assert_eq!(item.title, "Spirited Away");
+        assert_eq!(item.original_title.as_deref(), Some("千と千尋の神隠し"));
+    }
+
+    #[test]
+    fn tv_results_read_name_and_original_name() {
+        let item = parse_result(serde_json::json!({
+            "id": 70523,
+            "name": "Dark",
+            "original_name": "Dark",
+            "media_type": "tv"
+        }));
+        assert_eq!(item.title, "Dark");
+        assert_eq!(item.original_title.as_deref(), Some("Dark"));
+    }
+
+    #[test]
+    fn blank_titles_fall_back_to_the_original_title() {
+        let item = parse_result(serde_json::json!({
+            "id": 1,
+            "name": " ",
+            "original_name": "Haus des Geldes",
+            "media_type": "tv"
+        }));
+        assert_eq!(item.title, "Haus des Geldes");
+    }
+
+    #[test]
+    fn result_with_only_an_id_parses() {
+        let item = parse_result(serde_json::json!({ "id": 42 }));
+        assert_eq!(item.id, 42);
+        assert!(item.overview.is_empty());
+        assert!(item.poster_path.is_none());
+        assert_eq!(item.vote_average, 0.0);
+    }
+
+    #[test]
+    fn null_and_mistyped_fields_are_treated_as_missing() {
+        let item = parse_result(serde_json::json!({
+            "id": 42,
+            "title": "Heat",
+            "overview": null,
+            "poster_path": 17,
+            "vote_average": "n/a",
+            "release_date": null
+        }));
+        assert_eq!(item.title, "Heat");
+        assert!(item.overview.is_empty());
+        assert!(item.poster_path.is_none());
+        assert_eq!(item.vote_average, 0.0);
+        assert!(item.release_date.is_none());
+    }
+
+    #[test]
+    fn unparseable_entries_are_dropped_from_a_result_list() {
+        let response: TmdbSearchResponse = serde_json::from_value(serde_json::json!({
+            "results": [
+                { "id": 1, "title": "Kept" },
+                { "title": "No id" },
+                "not an object",
+                { "id": 2, "name": "Also kept" }
+            ]
+        }))
+        .unwrap();
+        let ids: Vec<u64> = response.results.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    fn genre(id: u64) -> Genre {
+        Genre {
+            id,
+            name: String::new(),
+            media_scope: GenreScope::default(),
+        }
+    }
+
+    #[test]
+    fn list_genre_ids_become_item_genres() {
+        let item = parse_result(serde_json::json!({
+            "id": 42,
+            "name": "Dark",
+            "media_type": "tv",
+            "genre_ids": [18, 9648]
+        }));
+        let ids: Vec<u64> = item.genres.iter().map(|g| g.id).collect();
+        assert_eq!(ids, vec![18, 9648]);
+        assert!(item.genres.iter().all(|g| g.media_scope == GenreScope::Tv));
+    }
+
+    #[test]
+    fn genre_filter_hides_items_without_the_genre() {
+        let mut drama = media_item(1, 5.0);
+        drama.genres = vec![genre(18)];
+        let mut drama_mystery = media_item(2, 5.0);
+        drama_mystery.genres = vec![genre(18), genre(9648)];
+        let untagged = media_item(3, 5.0);
+        let items = [drama, drama_mystery, untagged];
+
+        let mut filters = SearchFilters {
+            genre_ids: vec![18, 9648],
+            genre_match: GenreMatch::Any,
+            ..Default::default()
+        };
+        assert_eq!(filtered_ids(&filters, &items), vec![1, 2]);
+
+        filters.genre_match = GenreMatch::All;
+        assert_eq!(filtered_ids(&filters, &items), vec![2]);
+    }
+}
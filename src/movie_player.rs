@@ -5,12 +5,19 @@ use std::sync::Arc;
 use std::thread;
 use tokio::sync::Mutex;
 
-use iced::widget::{button, column, container, row, slider, text, Space};
+use iced::widget::{button, column, container, pick_list, row, slider, text, Column, Space};
 use iced::{Border, Color, Element, Length, Padding, Shadow};
 use rodio::Sink;
+use serde::{Deserialize, Serialize};
 
+use crate::audio::{create_audio_output, create_resampler, frame_source, AudioConfig};
 use crate::media::{MediaId, Message, NETFLIX_RED, TEXT_GRAY, TEXT_WHITE};
-use crate::streaming;
+use crate::proxy;
+use crate::streaming::{ResolvedStream, StreamError, StreamQuery, StreamVariant, StreamingService};
+use crate::subtitles::{active_cue_text, SubtitleCue};
+use crate::video::{
+    join_decoder_thread, reap_decoder_threads, MAX_PACKET_READ_ERRORS, PACKET_READ_RETRY_DELAY,
+};
 use crate::Movix;
 
 const ICON_ARROW_LEFT: char = '\u{F12F}';
@@ -21,6 +28,15 @@ const ICON_SKIP_FORWARD_FILL: char = '\u{F555}';
 const ICON_VOLUME_UP_FILL: char = '\u{F611}';
 const ICON_VOLUME_MUTE_FILL: char = '\u{F608}';
 const ICON_FULLSCREEN: char = '\u{F31E}';
+const ICON_FULLSCREEN_EXIT: char = '\u{F31F}';
+const ICON_BADGE_CC_FILL: char = '\u{F173}';
+const ICON_BADGE_CC: char = '\u{F174}';
+
+const VOLUME_WHEEL_STEP: f64 = 0.05;
+const VOLUME_INDICATOR_DURATION: std::time::Duration = std::time::Duration::from_millis(1500);
+/// Frames further behind the clock than this are dropped to catch up.
+const LATE_FRAME_THRESHOLD: std::time::Duration = std::time::Duration::from_millis(50);
+const METRICS_SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
 
 pub struct FrameData {
     pub width: u32,
@@ -32,13 +48,26 @@ enum PlayerCommand {
     Pause,
     Resume,
     SetVolume(f32),
+    Seek(f64),
+    SetSpeed(f32),
     Shutdown,
 }
 
+pub const PLAYBACK_SPEEDS: [f32; 6] = [0.5, 0.75, 1.0, 1.25, 1.5, 2.0];
+
+fn format_speed(speed: f32) -> String {
+    format!("{}x", speed)
+}
+
 struct SharedState {
     position: AtomicU64,
     duration: AtomicU64,
     is_ended: AtomicBool,
+    /// Set with `is_ended` when the stream stopped yielding packets.
+    read_failed: AtomicBool,
+    shutdown: AtomicBool,
+    decoded_frames: AtomicU64,
+    dropped_frames: AtomicU64,
 }
 
 impl SharedState {
@@ -46,7 +75,11 @@ impl SharedState {
         Self {
             position: AtomicU64::new(0),
             duration: AtomicU64::new(0),
+            decoded_frames: AtomicU64::new(0),
+            dropped_frames: AtomicU64::new(0),
             is_ended: AtomicBool::new(false),
+            read_failed: AtomicBool::new(false),
+            shutdown: AtomicBool::new(false),
         }
     }
 }
@@ -57,19 +90,115 @@ pub struct MoviePlayer {
     frame_receiver: Option<crossbeam_channel::Receiver<FrameData>>,
     command_sender: Option<crossbeam_channel::Sender<PlayerCommand>>,
     decoder_thread: Option<thread::JoinHandle<()>>,
+    /// Stopped decoders that didn't exit within the join timeout.
+    stopped_threads: Vec<thread::JoinHandle<()>>,
     shared_state: Arc<SharedState>,
     is_playing: bool,
     is_muted: bool,
     volume: f32,
     current_url: Option<String>,
+    audio: AudioConfig,
+    proxy_url: Option<String>,
     progress_store: Arc<Mutex<PlaybackProgressStore>>,
+    /// Saved with the progress of an episode so it isn't mistaken for a movie.
+    series_id: Option<MediaId>,
+    rendered_frames: u64,
+    buffer_capacity: usize,
     target_width: u32,
     target_height: u32,
+    subtitle_url: Option<String>,
+    subtitles: Vec<SubtitleCue>,
+    speed: f32,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlaybackStats {
+    pub decoded_frames: u64,
+    pub rendered_frames: u64,
+    /// Frames skipped because they were decoded too late to show.
+    pub dropped_frames: u64,
+    pub buffered: usize,
+    pub buffer_capacity: usize,
+}
+
+/// Rates derived from `PlaybackStats` samples for the debug overlay.
+#[derive(Debug, Default)]
+pub struct PlaybackMetrics {
+    sampled_at: Option<std::time::Instant>,
+    sampled_stats: PlaybackStats,
+    pub decode_fps: f64,
+    pub render_fps: f64,
+    pub stats: PlaybackStats,
+}
+
+impl PlaybackMetrics {
+    pub fn record(&mut self, stats: PlaybackStats) {
+        self.stats = stats;
+        let Some(sampled_at) = self.sampled_at else {
+            self.sampled_at = Some(std::time::Instant::now());
+            self.sampled_stats = stats;
+            return;
+        };
+        let elapsed = sampled_at.elapsed();
+        if elapsed < METRICS_SAMPLE_INTERVAL {
+            return;
+        }
+        let secs = elapsed.as_secs_f64();
+        self.decode_fps = stats
+            .decoded_frames
+            .saturating_sub(self.sampled_stats.decoded_frames) as f64
+            / secs;
+        self.render_fps = stats
+            .rendered_frames
+            .saturating_sub(self.sampled_stats.rendered_frames) as f64
+            / secs;
+        self.sampled_at = Some(std::time::Instant::now());
+        self.sampled_stats = stats;
+    }
+}
+
+/// Positions below this are treated as not started.
+const MIN_RESUME_POSITION: f64 = 5.0;
+/// Fraction of the duration past which a title counts as finished.
+const FINISHED_FRACTION: f64 = 0.95;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PlaybackProgress {
+    pub position: f64,
+    /// Zero when the duration was unknown, e.g. for entries saved by older versions.
+    #[serde(default)]
+    pub duration: f64,
+    /// Unix seconds of the last save.
+    #[serde(default)]
+    pub updated_at: u64,
+    /// The series an episode belongs to; `None` for movies.
+    #[serde(default)]
+    pub series_id: Option<MediaId>,
+}
+
+impl PlaybackProgress {
+    pub fn fraction(&self) -> Option<f64> {
+        (self.duration > 0.0).then(|| (self.position / self.duration).clamp(0.0, 1.0))
+    }
+
+    fn is_in_progress(&self) -> bool {
+        self.position > MIN_RESUME_POSITION && self.fraction().is_none_or(|f| f < FINISHED_FRACTION)
+    }
 }
 
+/// Offered when a title with saved progress is opened. Playback is held
+/// until it is answered or goes unanswered for `RESUME_PROMPT_TIMEOUT`.
+#[derive(Debug, Clone, Copy)]
+pub struct ResumePrompt {
+    pub position: f64,
+    pub shown_at: std::time::Instant,
+}
+
+pub const RESUME_PROMPT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(8);
+
 #[derive(Clone, Default)]
 pub struct PlaybackProgressStore {
-    progress: HashMap<MediaId, f64>,
+    progress: HashMap<MediaId, PlaybackProgress>,
     storage_path: Option<PathBuf>,
 }
 
@@ -95,10 +224,25 @@ impl PlaybackProgressStore {
         let Some(ref path) = self.storage_path else {
             return;
         };
-        if let Ok(content) = std::fs::read_to_string(path) {
-            if let Ok(data) = serde_json::from_str(&content) {
-                self.progress = data;
-            }
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return;
+        };
+        if let Ok(data) = serde_json::from_str(&content) {
+            self.progress = data;
+        } else if let Ok(positions) = serde_json::from_str::<HashMap<MediaId, f64>>(&content) {
+            // Older versions stored bare positions.
+            self.progress = positions
+                .into_iter()
+                .map(|(id, position)| {
+                    let progress = PlaybackProgress {
+                        position,
+                        duration: 0.0,
+                        updated_at: 0,
+                        series_id: None,
+                    };
+                    (id, progress)
+                })
+                .collect();
         }
     }
 
@@ -111,14 +255,63 @@ impl PlaybackProgressStore {
         }
     }
 
+    /// Every movie with saved progress, most recently watched first.
+    /// Episodes are left out since their ids aren't TMDb titles.
+    pub fn movie_ids(&self) -> Vec<MediaId> {
+        self.sorted_ids(|p| p.series_id.is_none())
+    }
+
+    /// Started but unfinished movies, most recently watched first.
+    pub fn in_progress_movie_ids(&self) -> Vec<MediaId> {
+        self.sorted_ids(|p| p.series_id.is_none() && p.is_in_progress())
+    }
+
+    fn sorted_ids(&self, keep: impl Fn(&PlaybackProgress) -> bool) -> Vec<MediaId> {
+        let mut entries: Vec<(MediaId, PlaybackProgress)> = self
+            .progress
+            .iter()
+            .filter(|(_, p)| keep(p))
+            .map(|(id, p)| (*id, *p))
+            .collect();
+        entries.sort_by(|a, b| b.1.updated_at.cmp(&a.1.updated_at));
+        entries.into_iter().map(|(id, _)| id).collect()
+    }
+
     pub fn get(&self, media_id: MediaId) -> Option<f64> {
+        self.progress.get(&media_id).map(|p| p.position)
+    }
+
+    pub fn progress(&self, media_id: MediaId) -> Option<PlaybackProgress> {
         self.progress.get(&media_id).copied()
     }
 
-    pub fn set(&mut self, media_id: MediaId, position: f64) {
-        self.progress.insert(media_id, position);
+    pub fn set(
+        &mut self,
+        media_id: MediaId,
+        position: f64,
+        duration: f64,
+        series_id: Option<MediaId>,
+    ) {
+        let updated_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        self.progress.insert(
+            media_id,
+            PlaybackProgress {
+                position,
+                duration,
+                updated_at,
+                series_id,
+            },
+        );
         self.save();
     }
+
+    pub fn remove(&mut self, media_id: MediaId) {
+        if self.progress.remove(&media_id).is_some() {
+            self.save();
+        }
+    }
 }
 
 impl MoviePlayer {
@@ -130,29 +323,51 @@ impl MoviePlayer {
             frame_receiver: None,
             command_sender: None,
             decoder_thread: None,
+            stopped_threads: Vec::new(),
             shared_state: Arc::new(SharedState::new()),
             is_playing: false,
             is_muted: false,
             volume: 1.0,
             current_url: None,
+            audio: AudioConfig::default(),
+            proxy_url: None,
             progress_store,
+            series_id: None,
+            rendered_frames: 0,
+            buffer_capacity: 0,
             target_width: 1920,
             target_height: 1080,
+            subtitle_url: None,
+            subtitles: Vec::new(),
+            speed: 1.0,
         })
     }
 
-    pub fn play(&mut self, media_id: MediaId, url: &str) -> Result<(), String> {
+    pub fn play(
+        &mut self,
+        media_id: MediaId,
+        url: &str,
+        buffer_depth: usize,
+    ) -> Result<(), String> {
         self.stop();
-        let (frame_tx, frame_rx) = crossbeam_channel::bounded(4);
+        let (frame_tx, frame_rx) = crossbeam_channel::bounded(buffer_depth);
         let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
         let url_clone = url.to_string();
         let width = self.target_width;
         let height = self.target_height;
         let shared = Arc::new(SharedState::new());
         self.shared_state = shared.clone();
+        let audio = self.audio.clone();
+        let proxy_url = self.proxy_url.clone();
+        let _ = cmd_tx.send(PlayerCommand::SetVolume(self.effective_volume()));
+        if self.speed != 1.0 {
+            let _ = cmd_tx.send(PlayerCommand::SetSpeed(self.speed));
+        }
 
         let handle = thread::spawn(move || {
-            run_movie_decoder(url_clone, width, height, frame_tx, cmd_rx, shared);
+            run_movie_decoder(
+                url_clone, width, height, frame_tx, cmd_rx, shared, audio, proxy_url,
+            );
         });
 
         self.frame_receiver = Some(frame_rx);
@@ -160,18 +375,22 @@ impl MoviePlayer {
         self.decoder_thread = Some(handle);
         self.current_media_id = Some(media_id);
         self.current_url = Some(url.to_string());
+        self.rendered_frames = 0;
+        self.buffer_capacity = buffer_depth;
         self.is_playing = true;
         Ok(())
     }
 
     pub fn stop(&mut self) {
+        self.shared_state.shutdown.store(true, Ordering::SeqCst);
         if let Some(sender) = self.command_sender.take() {
             let _ = sender.send(PlayerCommand::Shutdown);
         }
+        self.frame_receiver = None;
         if let Some(handle) = self.decoder_thread.take() {
-            let _ = handle.join();
+            self.stopped_threads.extend(join_decoder_thread(handle));
         }
-        self.frame_receiver = None;
+        reap_decoder_threads(&mut self.stopped_threads);
         self.current_media_id = None;
         self.current_url = None;
         self.is_playing = false;
@@ -212,11 +431,22 @@ impl MoviePlayer {
         self.current_media_id
     }
 
+    pub fn set_series(&mut self, series_id: Option<MediaId>) {
+        self.series_id = series_id;
+    }
+
+    fn effective_volume(&self) -> f32 {
+        if self.is_muted {
+            0.0
+        } else {
+            self.volume
+        }
+    }
+
     pub fn set_volume(&mut self, v: f64) {
         self.volume = v.clamp(0.0, 1.0) as f32;
         if let Some(ref sender) = self.command_sender {
-            let vol = if self.is_muted { 0.0 } else { self.volume };
-            let _ = sender.send(PlayerCommand::SetVolume(vol));
+            let _ = sender.send(PlayerCommand::SetVolume(self.effective_volume()));
         }
     }
 
@@ -225,10 +455,13 @@ impl MoviePlayer {
     }
 
     pub fn toggle_mute(&mut self) {
-        self.is_muted = !self.is_muted;
+        self.set_muted(!self.is_muted);
+    }
+
+    pub fn set_muted(&mut self, muted: bool) {
+        self.is_muted = muted;
         if let Some(ref sender) = self.command_sender {
-            let vol = if self.is_muted { 0.0 } else { self.volume };
-            let _ = sender.send(PlayerCommand::SetVolume(vol));
+            let _ = sender.send(PlayerCommand::SetVolume(self.effective_volume()));
         }
     }
 
@@ -236,8 +469,81 @@ impl MoviePlayer {
         self.is_muted
     }
 
-    pub fn seek(&mut self, _pos: f64) {}
-    pub fn seek_relative(&mut self, _delta: f64) {}
+    /// Takes effect from the next `play`.
+    pub fn set_audio(&mut self, audio: AudioConfig) {
+        self.audio = audio;
+    }
+
+    /// Takes effect from the next `play`.
+    pub fn set_proxy(&mut self, proxy_url: Option<&str>) {
+        self.proxy_url = proxy_url.map(String::from);
+    }
+
+    /// Takes effect from the next `play`.
+    pub fn set_target_size(&mut self, width: u32, height: u32) {
+        self.target_width = width;
+        self.target_height = height;
+    }
+
+    /// Kept across titles for the rest of the session.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.clamp(
+            PLAYBACK_SPEEDS[0],
+            PLAYBACK_SPEEDS[PLAYBACK_SPEEDS.len() - 1],
+        );
+        if let Some(ref sender) = self.command_sender {
+            let _ = sender.send(PlayerCommand::SetSpeed(self.speed));
+        }
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Selects a subtitle track, or turns subtitles off with `None`. Cues
+    /// show once they are loaded with `set_subtitle_cues`.
+    pub fn set_subtitle_track(&mut self, url: Option<String>) {
+        self.subtitle_url = url;
+        self.subtitles.clear();
+    }
+
+    /// Ignored when another track was selected while these were loading.
+    pub fn set_subtitle_cues(&mut self, url: &str, cues: Vec<SubtitleCue>) {
+        if self.subtitle_url.as_deref() == Some(url) {
+            self.subtitles = cues;
+        }
+    }
+
+    pub fn subtitle_url(&self) -> Option<&str> {
+        self.subtitle_url.as_deref()
+    }
+
+    pub fn active_subtitle(&self) -> Option<String> {
+        active_cue_text(&self.subtitles, self.position())
+    }
+
+    pub fn seek(&mut self, pos: f64) {
+        let Some(ref sender) = self.command_sender else {
+            return;
+        };
+        let duration = self.duration();
+        let pos = if duration > 0.0 {
+            pos.clamp(0.0, duration)
+        } else {
+            pos.max(0.0)
+        };
+        let _ = sender.send(PlayerCommand::Seek(pos));
+        self.shared_state
+            .position
+            .store(pos.to_bits(), Ordering::SeqCst);
+        if let Some(ref receiver) = self.frame_receiver {
+            while receiver.try_recv().is_ok() {}
+        }
+    }
+
+    pub fn seek_relative(&mut self, delta: f64) {
+        self.seek(self.position() + delta);
+    }
 
     pub fn position(&self) -> f64 {
         f64::from_bits(self.shared_state.position.load(Ordering::SeqCst))
@@ -251,9 +557,14 @@ impl MoviePlayer {
         self.shared_state.is_ended.load(Ordering::SeqCst)
     }
 
+    pub fn read_failed(&self) -> bool {
+        self.shared_state.read_failed.load(Ordering::SeqCst)
+    }
+
     pub fn get_new_frame(&mut self) -> Option<FrameData> {
         let receiver = self.frame_receiver.as_ref()?;
         if let Ok(frame) = receiver.try_recv() {
+            self.rendered_frames += 1;
             self.current_frame = Some(FrameData {
                 width: frame.width,
                 height: frame.height,
@@ -272,12 +583,22 @@ impl MoviePlayer {
         })
     }
 
+    pub fn stats(&self) -> PlaybackStats {
+        PlaybackStats {
+            decoded_frames: self.shared_state.decoded_frames.load(Ordering::Relaxed),
+            rendered_frames: self.rendered_frames,
+            dropped_frames: self.shared_state.dropped_frames.load(Ordering::Relaxed),
+            buffered: self.frame_receiver.as_ref().map_or(0, |r| r.len()),
+            buffer_capacity: self.buffer_capacity,
+        }
+    }
+
     pub fn save_progress_sync(&self) {
         if let Some(id) = self.current_media_id {
             let pos = self.position();
-            if pos > 5.0 {
+            if pos > MIN_RESUME_POSITION {
                 if let Ok(mut store) = self.progress_store.try_lock() {
-                    store.set(id, pos);
+                    store.set(id, pos, self.duration(), self.series_id);
                 }
             }
         }
@@ -286,6 +607,21 @@ impl MoviePlayer {
     pub fn get_stored_position(&self, media_id: MediaId) -> Option<f64> {
         self.progress_store.try_lock().ok()?.get(media_id)
     }
+
+    pub fn clear_stored_position(&self, media_id: MediaId) {
+        if let Ok(mut store) = self.progress_store.try_lock() {
+            store.remove(media_id);
+        }
+    }
+
+    /// Share of the title already watched, when its duration is known.
+    pub fn get_watched_fraction(&self, media_id: MediaId) -> Option<f64> {
+        self.progress_store
+            .try_lock()
+            .ok()?
+            .progress(media_id)?
+            .fraction()
+    }
 }
 
 impl Drop for MoviePlayer {
@@ -294,6 +630,7 @@ impl Drop for MoviePlayer {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_movie_decoder(
     url: String,
     target_width: u32,
@@ -301,8 +638,10 @@ fn run_movie_decoder(
     frame_sender: crossbeam_channel::Sender<FrameData>,
     command_receiver: crossbeam_channel::Receiver<PlayerCommand>,
     shared_state: Arc<SharedState>,
+    audio: AudioConfig,
+    proxy_url: Option<String>,
 ) {
-    let (_stream, sink) = match create_audio_output() {
+    let (_stream, sink) = match create_audio_output(audio.device.as_deref()) {
         Some(s) => s,
         None => {
             shared_state.is_ended.store(true, Ordering::SeqCst);
@@ -310,7 +649,10 @@ fn run_movie_decoder(
         }
     };
 
-    let mut ictx = match ffmpeg_next::format::input(&url) {
+    let interrupt_state = shared_state.clone();
+    let mut ictx = match proxy::open_input(&url, proxy_url.as_deref(), move || {
+        interrupt_state.shutdown.load(Ordering::SeqCst)
+    }) {
         Ok(ctx) => ctx,
         Err(_) => {
             shared_state.is_ended.store(true, Ordering::SeqCst);
@@ -328,6 +670,7 @@ fn run_movie_decoder(
     let video_index = video_stream.as_ref().map(|s| s.index());
     let audio_index = audio_stream.as_ref().map(|s| s.index());
     let video_time_base = video_stream.as_ref().map(|s| s.time_base());
+    let audio_time_base = audio_stream.as_ref().map(|s| s.time_base());
 
     let mut video_decoder = video_stream.and_then(|s| {
         ffmpeg_next::codec::context::Context::from_parameters(s.parameters())
@@ -358,103 +701,183 @@ fn run_movie_decoder(
         .ok()
     });
 
-    let mut resampler = audio_decoder.as_ref().and_then(|dec| {
-        ffmpeg_next::software::resampling::Context::get(
-            dec.format(),
-            dec.channel_layout(),
-            dec.rate(),
-            ffmpeg_next::format::Sample::I16(ffmpeg_next::format::sample::Type::Packed),
-            ffmpeg_next::ChannelLayout::STEREO,
-            44100,
-        )
-        .ok()
-    });
+    let mut resampler = audio_decoder.as_ref().and_then(create_resampler);
 
-    let playback_start = std::time::Instant::now();
+    let mut playback_start = std::time::Instant::now();
     let mut pause_offset = std::time::Duration::ZERO;
     let mut pause_start: Option<std::time::Instant> = None;
     let mut is_paused = false;
-
-    for (pkt_stream, packet) in ictx.packets() {
-        while let Ok(cmd) = command_receiver.try_recv() {
-            match cmd {
-                PlayerCommand::Shutdown => return,
-                PlayerCommand::Pause => {
-                    is_paused = true;
-                    pause_start = Some(std::time::Instant::now());
-                    sink.pause();
+    // Media time at `playback_start`, moved by seeks.
+    let mut clock_base = std::time::Duration::ZERO;
+    // Frames decoded from the keyframe before a seek target are dropped.
+    let mut seek_target: Option<f64> = None;
+    // Media seconds per wall-clock second. The clock is rebased whenever it
+    // changes, so `clock_base` always holds media time.
+    let mut speed: f32 = 1.0;
+    let mut read_errors = 0;
+    let mut dropped_last_frame = false;
+    let mut pending_seek: Option<f64> = None;
+
+    'playback: loop {
+        loop {
+            // A seek that arrived after the end of the file restarts decoding.
+            let commands = pending_seek
+                .take()
+                .map(PlayerCommand::Seek)
+                .into_iter()
+                .chain(command_receiver.try_iter());
+            for cmd in commands {
+                match cmd {
+                    PlayerCommand::Shutdown => return,
+                    PlayerCommand::Pause => {
+                        is_paused = true;
+                        pause_start = Some(std::time::Instant::now());
+                        sink.pause();
+                    }
+                    PlayerCommand::Resume => {
+                        is_paused = false;
+                        if let Some(ps) = pause_start.take() {
+                            pause_offset += ps.elapsed();
+                        }
+                        sink.play();
+                    }
+                    PlayerCommand::SetVolume(v) => sink.set_volume(v),
+                    PlayerCommand::SetSpeed(new_speed) => {
+                        let now = std::time::Instant::now();
+                        let paused_for =
+                            pause_start.map_or(std::time::Duration::ZERO, |ps| now - ps);
+                        let played = (now - playback_start)
+                            .saturating_sub(pause_offset)
+                            .saturating_sub(paused_for);
+                        clock_base += played.mul_f32(speed);
+                        playback_start = now;
+                        pause_offset = std::time::Duration::ZERO;
+                        if is_paused {
+                            pause_start = Some(now);
+                        }
+                        speed = new_speed;
+                        // Rodio resamples to play faster, which also raises the pitch.
+                        sink.set_speed(speed);
+                    }
+                    PlayerCommand::Seek(target) => {
+                        let ts = (target * f64::from(ffmpeg_next::ffi::AV_TIME_BASE)) as i64;
+                        if ictx.seek(ts, ..ts).is_err() {
+                            continue;
+                        }
+                        if let Some(ref mut decoder) = video_decoder {
+                            decoder.flush();
+                        }
+                        if let Some(ref mut decoder) = audio_decoder {
+                            decoder.flush();
+                        }
+                        sink.clear();
+                        if !is_paused {
+                            sink.play();
+                        }
+                        clock_base = std::time::Duration::from_secs_f64(target);
+                        playback_start = std::time::Instant::now();
+                        pause_offset = std::time::Duration::ZERO;
+                        if is_paused {
+                            pause_start = Some(playback_start);
+                        }
+                        seek_target = Some(target);
+                    }
                 }
-                PlayerCommand::Resume => {
-                    is_paused = false;
-                    if let Some(ps) = pause_start.take() {
-                        pause_offset += ps.elapsed();
+            }
+
+            if is_paused {
+                thread::sleep(std::time::Duration::from_millis(50));
+                continue;
+            }
+            let mut packet = ffmpeg_next::Packet::empty();
+            match packet.read(&mut ictx) {
+                Ok(()) => read_errors = 0,
+                Err(ffmpeg_next::Error::Eof) => break,
+                Err(_) if shared_state.shutdown.load(Ordering::SeqCst) => return,
+                Err(_) => {
+                    read_errors += 1;
+                    if read_errors >= MAX_PACKET_READ_ERRORS {
+                        shared_state.read_failed.store(true, Ordering::SeqCst);
+                        shared_state.is_ended.store(true, Ordering::SeqCst);
+                        return;
                     }
-                    sink.play();
+                    thread::sleep(PACKET_READ_RETRY_DELAY);
+                    continue;
                 }
-                PlayerCommand::SetVolume(v) => sink.set_volume(v),
             }
-        }
 
-        if is_paused {
-            thread::sleep(std::time::Duration::from_millis(50));
-            continue;
-        }
-
-        let stream_index = pkt_stream.index();
-
-        if Some(stream_index) == audio_index {
-            if let (Some(ref mut decoder), Some(ref mut resamp)) =
-                (&mut audio_decoder, &mut resampler)
-            {
-                if decoder.send_packet(&packet).is_ok() {
-                    let mut decoded = ffmpeg_next::frame::Audio::empty();
-                    while decoder.receive_frame(&mut decoded).is_ok() {
-                        let mut resampled = ffmpeg_next::frame::Audio::empty();
-                        if resamp.run(&decoded, &mut resampled).is_ok() {
-                            let data = resampled.data(0);
-                            let samples: Vec<f32> = data
-                                .chunks_exact(2)
-                                .map(|c| i16::from_le_bytes([c[0], c[1]]) as f32 / 32768.0)
-                                .collect();
-                            let source = rodio::buffer::SamplesBuffer::new(2, 44100, samples);
-                            sink.append(source);
+            let stream_index = packet.stream();
+
+            if Some(stream_index) == audio_index {
+                if let (Some(ref mut decoder), Some(ref mut resamp)) =
+                    (&mut audio_decoder, &mut resampler)
+                {
+                    if decoder.send_packet(&packet).is_ok() {
+                        let mut decoded = ffmpeg_next::frame::Audio::empty();
+                        while decoder.receive_frame(&mut decoded).is_ok() {
+                            if let (Some(target), Some(tb)) = (seek_target, audio_time_base) {
+                                let pts = decoded.pts().unwrap_or(0) as f64 * f64::from(tb);
+                                if pts < target {
+                                    continue;
+                                }
+                            }
+                            let mut resampled = ffmpeg_next::frame::Audio::empty();
+                            if resamp.run(&decoded, &mut resampled).is_ok() {
+                                if let Some(source) = frame_source(&resampled, audio.gain) {
+                                    sink.append(source);
+                                }
+                            }
                         }
                     }
                 }
             }
-        }
 
-        if Some(stream_index) == video_index {
-            if let Some(ref mut decoder) = video_decoder {
-                if decoder.send_packet(&packet).is_ok() {
-                    let mut decoded = ffmpeg_next::frame::Video::empty();
-                    while decoder.receive_frame(&mut decoded).is_ok() {
-                        if let Some(tb) = video_time_base {
-                            let pts = decoded.pts().unwrap_or(0);
-                            let pos = pts as f64 * f64::from(tb);
-                            shared_state.position.store(pos.to_bits(), Ordering::SeqCst);
-                        }
-                        if let Some(ref mut sc) = scaler {
-                            let mut rgb = ffmpeg_next::frame::Video::empty();
-                            if sc.run(&decoded, &mut rgb).is_ok() {
+            if Some(stream_index) == video_index {
+                if let Some(ref mut decoder) = video_decoder {
+                    if decoder.send_packet(&packet).is_ok() {
+                        let mut decoded = ffmpeg_next::frame::Video::empty();
+                        while decoder.receive_frame(&mut decoded).is_ok() {
+                            shared_state.decoded_frames.fetch_add(1, Ordering::Relaxed);
+                            if let Some(tb) = video_time_base {
+                                let pts = decoded.pts().unwrap_or(0);
+                                let pos = pts as f64 * f64::from(tb);
+                                if seek_target.is_some_and(|target| pos < target) {
+                                    continue;
+                                }
+                                seek_target = None;
+                                shared_state.position.store(pos.to_bits(), Ordering::SeqCst);
+                            }
+                            if let Some(ref mut sc) = scaler {
                                 if let Some(tb) = video_time_base {
                                     let pts = decoded.pts().unwrap_or(0);
                                     let frame_time = std::time::Duration::from_secs_f64(
                                         pts as f64 * f64::from(tb),
                                     );
-                                    let elapsed = playback_start.elapsed() - pause_offset;
+                                    let elapsed = clock_base
+                                        + (playback_start.elapsed() - pause_offset).mul_f32(speed);
                                     if frame_time > elapsed {
-                                        thread::sleep(frame_time - elapsed);
+                                        thread::sleep((frame_time - elapsed).div_f32(speed));
+                                    } else if elapsed - frame_time > LATE_FRAME_THRESHOLD
+                                        && !dropped_last_frame
+                                    {
+                                        // Never two in a row, so a slow machine still shows video.
+                                        shared_state.dropped_frames.fetch_add(1, Ordering::Relaxed);
+                                        dropped_last_frame = true;
+                                        continue;
                                     }
                                 }
-                                let frame = FrameData {
-                                    width: target_width,
-                                    height: target_height,
-                                    data: rgb.data(0).to_vec(),
-                                };
-                                if frame_sender.send(frame).is_err() {
-                                    shared_state.is_ended.store(true, Ordering::SeqCst);
-                                    return;
+                                dropped_last_frame = false;
+                                let mut rgb = ffmpeg_next::frame::Video::empty();
+                                if sc.run(&decoded, &mut rgb).is_ok() {
+                                    let frame = FrameData {
+                                        width: target_width,
+                                        height: target_height,
+                                        data: rgb.data(0).to_vec(),
+                                    };
+                                    if frame_sender.send(frame).is_err() {
+                                        shared_state.is_ended.store(true, Ordering::SeqCst);
+                                        return;
+                                    }
                                 }
                             }
                         }
@@ -462,26 +885,53 @@ fn run_movie_decoder(
                 }
             }
         }
-    }
 
-    sink.sleep_until_end();
+        while !sink.empty() {
+            match command_receiver.try_recv() {
+                Ok(PlayerCommand::Shutdown)
+                | Err(crossbeam_channel::TryRecvError::Disconnected) => return,
+                Ok(PlayerCommand::Pause) => {
+                    is_paused = true;
+                    pause_start = Some(std::time::Instant::now());
+                    sink.pause();
+                }
+                Ok(PlayerCommand::Resume) => {
+                    is_paused = false;
+                    pause_start = None;
+                    sink.play();
+                }
+                Ok(PlayerCommand::SetVolume(v)) => sink.set_volume(v),
+                Ok(PlayerCommand::SetSpeed(v)) => {
+                    speed = v;
+                    sink.set_speed(v);
+                }
+                Ok(PlayerCommand::Seek(target)) => {
+                    pending_seek = Some(target);
+                    continue 'playback;
+                }
+                Err(crossbeam_channel::TryRecvError::Empty) => {
+                    thread::sleep(std::time::Duration::from_millis(50));
+                }
+            }
+        }
+        break;
+    }
     shared_state.is_ended.store(true, Ordering::SeqCst);
 }
 
-fn create_audio_output() -> Option<(std::mem::ManuallyDrop<rodio::OutputStream>, Sink)> {
-    let stream = rodio::OutputStreamBuilder::open_default_stream().ok()?;
-    let sink = Sink::connect_new(stream.mixer());
-    Some((std::mem::ManuallyDrop::new(stream), sink))
-}
-
 pub struct VoeStreamResolver;
 
 impl VoeStreamResolver {
-    pub async fn get_download_url(title: &str) -> Result<String, String> {
-        streaming::create_default_service()
-            .get_stream_url(title)
-            .await
-            .map_err(|e| e.to_string())
+    pub async fn get_download_url(
+        service: &StreamingService,
+        query: &StreamQuery,
+        provider_name: Option<&str>,
+        excluded: &[String],
+    ) -> Result<ResolvedStream, StreamError> {
+        match provider_name {
+            Some(name) => service.get_stream_url_with_provider(query, name).await,
+            None => service.get_stream_url(query, excluded).await,
+        }
     }
 }
 
@@ -504,16 +954,261 @@ fn icon(codepoint: char) -> iced::widget::Text<'static> {
     })
 }
 
+/// Stays visible while the controls are hidden so a trailer is never
+/// mistaken for the full title.
+fn view_trailer_label() -> Element<'static, Message> {
+    let pill = container(text("Trailer").size(14).color(TEXT_WHITE))
+        .padding(Padding::new(6.0).left(14.0).right(14.0))
+        .style(|_| container::Style {
+            background: Some(iced::Background::Color(NETFLIX_RED)),
+            border: Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    container(pill)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_right(Length::Fill)
+        .padding(Padding::new(24.0))
+        .into()
+}
+
+fn picker_style() -> pick_list::Style {
+    pick_list::Style {
+        text_color: TEXT_WHITE,
+        placeholder_color: TEXT_GRAY,
+        handle_color: TEXT_WHITE,
+        background: iced::Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.1)),
+        border: Border {
+            color: Color::from_rgba(1.0, 1.0, 1.0, 0.2),
+            width: 1.0,
+            radius: 4.0.into(),
+        },
+    }
+}
+
+fn view_resume_prompt(
+    position: f64,
+    quality_picker: Option<Element<'_, Message>>,
+) -> Element<'_, Message> {
+    let prompt_button = |label: String, primary: bool, message: Message| {
+        button(text(label).size(16).color(TEXT_WHITE))
+            .padding(Padding::new(10.0).left(24.0).right(24.0))
+            .style(move |_, status| {
+                let hovered = matches!(status, button::Status::Hovered);
+                let background = match (primary, hovered) {
+                    (true, true) => Color::from_rgb(0.698, 0.027, 0.063),
+                    (true, false) => NETFLIX_RED,
+                    (false, true) => Color::from_rgba(1.0, 1.0, 1.0, 0.3),
+                    (false, false) => Color::from_rgba(1.0, 1.0, 1.0, 0.2),
+                };
+                button::Style {
+                    background: Some(iced::Background::Color(background)),
+                    text_color: TEXT_WHITE,
+                    border: Border {
+                        radius: 4.0.into(),
+                        ..Default::default()
+                    },
+                    shadow: Shadow::default(),
+                    snap: false,
+                }
+            })
+            .on_press(message)
+    };
+    let mut buttons = row![
+        prompt_button(
+            format!("Resume from {}", format_time(position)),
+            true,
+            Message::MoviePlayerResumeStored,
+        ),
+        prompt_button(
+            String::from("Start over"),
+            false,
+            Message::MoviePlayerStartOver
+        ),
+    ]
+    .spacing(12)
+    .align_y(iced::Alignment::Center);
+    if let Some(picker) = quality_picker {
+        buttons = buttons.push(picker);
+    }
+    let panel = container(buttons)
+        .padding(Padding::new(20.0))
+        .style(|_| container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                0.0, 0.0, 0.0, 0.8,
+            ))),
+            border: Border {
+                radius: 8.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    container(panel)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+}
+
 impl Movix {
+    /// The wheel handler sits on the video layer below the controls, so
+    /// scrolling over the seek bar or buttons never reaches it.
     pub fn view_movie_player_overlay(&self) -> Element<'_, Message> {
-        let video = self.view_movie_video();
+        let volume = self.movie_player_volume;
+        let video = iced::widget::mouse_area(self.view_movie_video()).on_scroll(move |delta| {
+            let (iced::mouse::ScrollDelta::Lines { y, .. }
+            | iced::mouse::ScrollDelta::Pixels { y, .. }) = delta;
+            let step = VOLUME_WHEEL_STEP * f64::from(y.signum());
+            Message::MoviePlayerSetVolume((volume + step).clamp(0.0, 1.0))
+        });
         let controls = self.view_movie_controls_overlay();
-        iced::widget::stack![video, controls]
+
+        let mut layers = iced::widget::stack![video];
+        if self.movie_player_trailer_only {
+            layers = layers.push(view_trailer_label());
+        }
+        if let Some(cue) = self.movie_player.active_subtitle() {
+            layers = layers.push(self.view_movie_subtitle(cue));
+        }
+        if let Some(indicator) = self.view_movie_volume_indicator() {
+            layers = layers.push(indicator);
+        }
+        if self.movie_player_metrics_visible {
+            layers = layers.push(self.view_movie_metrics());
+        }
+        if let Some(prompt) = self.movie_player_resume_prompt {
+            return layers
+                .push(view_resume_prompt(
+                    prompt.position,
+                    self.view_quality_picker(),
+                ))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into();
+        }
+        layers
+            .push(controls)
             .width(Length::Fill)
             .height(Length::Fill)
             .into()
     }
 
+    /// Raised above the bottom controls while they are showing.
+    fn view_movie_subtitle(&self, cue: String) -> Element<'_, Message> {
+        let bottom = if self.movie_player_controls_visible {
+            110.0
+        } else {
+            48.0
+        };
+        let caption = container(
+            text(cue)
+                .size(22)
+                .color(TEXT_WHITE)
+                .align_x(iced::alignment::Horizontal::Center),
+        )
+        .padding(Padding::new(6.0).left(12.0).right(12.0))
+        .style(|_| container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                0.0, 0.0, 0.0, 0.6,
+            ))),
+            border: Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        container(caption)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_x(iced::alignment::Horizontal::Center)
+            .align_y(iced::alignment::Vertical::Bottom)
+            .padding(Padding::new(0.0).bottom(bottom).left(48.0).right(48.0))
+            .into()
+    }
+
+    fn view_movie_metrics(&self) -> Element<'_, Message> {
+        let metrics = &self.movie_player_metrics;
+        let lines = [
+            format!("Render  {:.1} fps", metrics.render_fps),
+            format!("Decode  {:.1} fps", metrics.decode_fps),
+            format!("Dropped {}", metrics.stats.dropped_frames),
+            format!(
+                "Buffer  {}/{}",
+                metrics.stats.buffered, metrics.stats.buffer_capacity
+            ),
+        ];
+        let panel = container(
+            Column::with_children(lines.into_iter().map(|line| {
+                text(line)
+                    .size(13)
+                    .font(iced::Font::MONOSPACE)
+                    .color(TEXT_WHITE)
+                    .into()
+            }))
+            .spacing(2),
+        )
+        .padding(Padding::new(10.0).left(14.0).right(14.0))
+        .style(|_| container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                0.0, 0.0, 0.0, 0.7,
+            ))),
+            border: Border {
+                radius: 6.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        container(panel)
+            .padding(Padding::new(0.0).top(80.0).left(24.0))
+            .into()
+    }
+
+    fn view_movie_volume_indicator(&self) -> Option<Element<'_, Message>> {
+        let changed_at = self.movie_player_volume_changed_at?;
+        if changed_at.elapsed() >= VOLUME_INDICATOR_DURATION {
+            return None;
+        }
+        let (vol_icon, label) = if self.movie_player_muted {
+            (ICON_VOLUME_MUTE_FILL, String::from("Muted"))
+        } else {
+            (
+                ICON_VOLUME_UP_FILL,
+                format!("{}%", (self.movie_player_volume * 100.0).round()),
+            )
+        };
+        let pill = container(
+            row![
+                icon(vol_icon).size(20).color(TEXT_WHITE),
+                text(label).size(18).color(TEXT_WHITE)
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center),
+        )
+        .padding(Padding::new(12.0).left(20.0).right(20.0))
+        .style(|_| container::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                0.0, 0.0, 0.0, 0.6,
+            ))),
+            border: Border {
+                radius: 24.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        Some(
+            container(pill)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .center_x(Length::Fill)
+                .center_y(Length::Fill)
+                .into(),
+        )
+    }
+
     fn view_movie_video(&self) -> Element<'_, Message> {
         if let Some(ref err) = self.movie_player_error {
             return self.view_movie_error(err);
@@ -541,45 +1236,68 @@ impl Movix {
 
     fn view_movie_error(&self, err: &str) -> Element<'_, Message> {
         let title = self.movie_player_title.clone().unwrap_or_default();
-        container(
-            column![
-                text("Failed to load").size(24).color(NETFLIX_RED),
-                text(err.to_string()).size(14).color(TEXT_GRAY),
-                text(title).size(16).color(TEXT_WHITE)
-            ]
-            .spacing(12)
-            .align_x(iced::Alignment::Center),
-        )
-        .width(Length::Fill)
-        .height(Length::Fill)
-        .center_x(Length::Fill)
-        .center_y(Length::Fill)
-        .style(|_| container::Style {
-            background: Some(iced::Background::Color(Color::BLACK)),
-            ..Default::default()
-        })
-        .into()
+        let mut content = column![
+            text("Failed to load").size(24).color(NETFLIX_RED),
+            text(err.to_string()).size(14).color(TEXT_GRAY),
+            text(title).size(16).color(TEXT_WHITE)
+        ]
+        .spacing(12)
+        .align_x(iced::Alignment::Center);
+        if self.movie_player_error_retryable {
+            let retry_btn = button(text("Retry").size(16).color(TEXT_WHITE))
+                .padding(Padding::new(10.0).left(24.0).right(24.0))
+                .style(|_, status| button::Style {
+                    background: Some(iced::Background::Color(
+                        if matches!(status, button::Status::Hovered) {
+                            Color::from_rgb(0.698, 0.027, 0.063)
+                        } else {
+                            NETFLIX_RED
+                        },
+                    )),
+                    text_color: TEXT_WHITE,
+                    border: Border {
+                        radius: 4.0.into(),
+                        ..Default::default()
+                    },
+                    shadow: Shadow::default(),
+                    snap: false,
+                })
+                .on_press(Message::MoviePlayerRetry);
+            content = content.push(retry_btn);
+        }
+        container(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(|_| container::Style {
+                background: Some(iced::Background::Color(Color::BLACK)),
+                ..Default::default()
+            })
+            .into()
     }
 
     fn view_movie_loading(&self) -> Element<'_, Message> {
         let title = self.movie_player_title.clone().unwrap_or_default();
-        container(
-            column![
-                text("Loading...").size(24).color(TEXT_WHITE),
-                text(title).size(16).color(TEXT_GRAY)
-            ]
-            .spacing(8)
-            .align_x(iced::Alignment::Center),
-        )
-        .width(Length::Fill)
-        .height(Length::Fill)
-        .center_x(Length::Fill)
-        .center_y(Length::Fill)
-        .style(|_| container::Style {
-            background: Some(iced::Background::Color(Color::BLACK)),
-            ..Default::default()
-        })
-        .into()
+        let mut content = column![
+            text("Loading...").size(24).color(TEXT_WHITE),
+            text(title).size(16).color(TEXT_GRAY)
+        ]
+        .spacing(8)
+        .align_x(iced::Alignment::Center);
+        if let Some(picker) = self.view_quality_picker() {
+            content = content.push(picker);
+        }
+        container(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .style(|_| container::Style {
+                background: Some(iced::Background::Color(Color::BLACK)),
+                ..Default::default()
+            })
+            .into()
     }
 
     fn view_movie_controls_overlay(&self) -> Element<'_, Message> {
@@ -611,7 +1329,34 @@ impl Movix {
                 snap: false,
             })
             .on_press(Message::MoviePlayerClose);
-        let top = container(back_btn)
+        let mut top_row =
+            row![back_btn, Space::new().width(Length::Fill)].align_y(iced::Alignment::Center);
+        if self.movie_player_stream_provider.is_some() {
+            let report_btn = button(text("Report broken").size(14).color(TEXT_WHITE))
+                .padding(Padding::new(10.0).left(16.0).right(16.0))
+                .style(|_, status| button::Style {
+                    background: Some(iced::Background::Color(Color::from_rgba(
+                        0.0,
+                        0.0,
+                        0.0,
+                        if matches!(status, button::Status::Hovered) {
+                            0.5
+                        } else {
+                            0.3
+                        },
+                    ))),
+                    text_color: TEXT_WHITE,
+                    border: Border {
+                        radius: 24.0.into(),
+                        ..Default::default()
+                    },
+                    shadow: Shadow::default(),
+                    snap: false,
+                })
+                .on_press(Message::MoviePlayerReportBroken);
+            top_row = top_row.push(report_btn);
+        }
+        let top = container(top_row)
             .width(Length::Fill)
             .padding(Padding::new(16.0));
         let bottom = self.view_movie_bottom_controls();
@@ -712,7 +1457,27 @@ impl Movix {
         let center = container(text(title).size(14).color(TEXT_WHITE))
             .width(Length::Fill)
             .center_x(Length::Fill);
-        let right = self.ctrl_btn(ICON_FULLSCREEN, Message::MoviePlayerToggleFullscreen);
+        let fullscreen_icon = if self.movie_player_fullscreen {
+            ICON_FULLSCREEN_EXIT
+        } else {
+            ICON_FULLSCREEN
+        };
+        let mut right = row![self.view_speed_picker()]
+            .spacing(4)
+            .align_y(iced::Alignment::Center);
+        if let Some(picker) = self.view_quality_picker() {
+            right = right.push(picker);
+        }
+        if let Some(ref source) = self.movie_player_subtitle_source {
+            let (cc_icon, toggle) = if self.movie_player.subtitle_url().is_some() {
+                (ICON_BADGE_CC_FILL, None)
+            } else {
+                (ICON_BADGE_CC, Some(source.clone()))
+            };
+            right = right.push(self.ctrl_btn(cc_icon, Message::MoviePlayerSetSubtitle(toggle)));
+        }
+        let right =
+            right.push(self.ctrl_btn(fullscreen_icon, Message::MoviePlayerToggleFullscreen));
         let controls_row = row![left, center, right]
             .align_y(iced::Alignment::Center)
             .width(Length::Fill);
@@ -735,6 +1500,47 @@ impl Movix {
         .into()
     }
 
+    fn view_speed_picker(&self) -> Element<'_, Message> {
+        let options: Vec<String> = PLAYBACK_SPEEDS.iter().copied().map(format_speed).collect();
+        let selected = format_speed(self.movie_player.speed());
+        pick_list(options, Some(selected), |sel| {
+            let speed = PLAYBACK_SPEEDS
+                .iter()
+                .copied()
+                .find(|speed| format_speed(*speed) == sel)
+                .unwrap_or(1.0);
+            Message::MoviePlayerSetSpeed(speed)
+        })
+        .text_size(13)
+        .padding(Padding::new(6.0).left(10.0).right(10.0))
+        .style(|_, _| picker_style())
+        .into()
+    }
+
+    /// Only offered when the stream comes in more than one variant.
+    fn view_quality_picker(&self) -> Option<Element<'_, Message>> {
+        let variants = &self.movie_player_stream_variants;
+        if variants.len() < 2 {
+            return None;
+        }
+        let options: Vec<String> = variants.iter().map(StreamVariant::label).collect();
+        let selected = variants
+            .iter()
+            .find(|variant| variant.quality == self.movie_player_stream_quality)
+            .map(StreamVariant::label);
+        let picker = pick_list(options, selected, |sel| {
+            let quality = variants
+                .iter()
+                .find(|variant| variant.label() == sel)
+                .and_then(|variant| variant.quality);
+            Message::MoviePlayerSetQuality(quality)
+        })
+        .text_size(13)
+        .padding(Padding::new(6.0).left(10.0).right(10.0))
+        .style(|_, _| picker_style());
+        Some(picker.into())
+    }
+
     fn ctrl_btn(&self, ic: char, msg: Message) -> Element<'_, Message> {
         button(icon(ic).size(18).color(TEXT_WHITE))
             .padding(Padding::new(8.0))
@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+
+use iced::widget::{column, container, text};
+use iced::{Element, Length, Padding};
+
+use crate::media::{MediaId, MediaItem, Message, TEXT_GRAY, TEXT_WHITE};
+use crate::Movix;
+
+/// Titles saved with the "My List" buttons, newest first. Whole items are
+/// stored so the page renders without refetching them from TMDb.
+#[derive(Clone, Default)]
+pub struct MyListStore {
+    items: Vec<MediaItem>,
+    storage_path: Option<PathBuf>,
+}
+
+impl MyListStore {
+    pub fn new() -> Self {
+        let storage_path = std::env::var("HOME")
+            .ok()
+            .map(|home| PathBuf::from(home).join(".local/share/movix/my_list.json"));
+        if let Some(ref path) = storage_path {
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+        }
+        let mut store = Self {
+            items: Vec::new(),
+            storage_path,
+        };
+        store.load();
+        store
+    }
+
+    fn load(&mut self) {
+        let Some(ref path) = self.storage_path else {
+            return;
+        };
+        if let Ok(content) = std::fs::read_to_string(path) {
+            if let Ok(data) = serde_json::from_str(&content) {
+                self.items = data;
+            }
+        }
+    }
+
+    fn save(&self) {
+        let Some(ref path) = self.storage_path else {
+            return;
+        };
+        if let Ok(json) = serde_json::to_string(&self.items) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+
+    pub fn items(&self) -> &[MediaItem] {
+        &self.items
+    }
+
+    pub fn contains(&self, media_id: MediaId) -> bool {
+        self.items.iter().any(|i| i.id == media_id)
+    }
+
+    pub fn add(&mut self, item: MediaItem) {
+        if self.contains(item.id) {
+            return;
+        }
+        self.items.insert(0, item);
+        self.save();
+    }
+
+    pub fn remove(&mut self, media_id: MediaId) {
+        let len = self.items.len();
+        self.items.retain(|i| i.id != media_id);
+        if self.items.len() != len {
+            self.save();
+        }
+    }
+}
+
+impl Movix {
+    pub fn view_my_list_page(&self) -> Element<'_, Message> {
+        let header = text("My List").size(28).color(TEXT_WHITE).font(iced::Font {
+            weight: iced::font::Weight::Bold,
+            ..Default::default()
+        });
+
+        let items = self.my_list.items();
+        let content = if items.is_empty() {
+            view_empty_list()
+        } else {
+            self.view_media_grid(items)
+        };
+
+        column![header, content]
+            .spacing(24)
+            .padding(Padding::new(100.0).left(48.0).right(48.0).bottom(48.0))
+            .width(Length::Fill)
+            .into()
+    }
+}
+
+fn view_empty_list() -> Element<'static, Message> {
+    let message = text("Your list is empty")
+        .size(24)
+        .color(TEXT_GRAY)
+        .font(iced::Font {
+            weight: iced::font::Weight::Medium,
+            ..Default::default()
+        });
+
+    let suggestion = text("Add movies and series with the + button to find them here later")
+        .size(16)
+        .color(TEXT_GRAY);
+
+    container(
+        column![message, suggestion]
+            .spacing(12)
+            .align_x(iced::Alignment::Center),
+    )
+    .width(Length::Fill)
+    .height(Length::Fixed(300.0))
+    .center_x(Length::Fill)
+    .center_y(Length::Fill)
+    .into()
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use serde::Deserialize;
+
+use crate::media::ApiError;
+use crate::proxy;
+use crate::settings::AppSettings;
+
+#[derive(Debug, Clone, Deserialize)]
+struct OmdbResponse {
+    #[serde(rename = "imdbRating")]
+    imdb_rating: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct OmdbClient {
+    api_key: String,
+    base_url: String,
+    http_client: Arc<reqwest::Client>,
+    rating_cache: Arc<RwLock<HashMap<String, Option<String>>>>,
+}
+
+impl OmdbClient {
+    pub fn new(api_key: String, user_agent: &str, proxy_url: Option<&str>) -> Self {
+        let http_client = proxy::apply(reqwest::Client::builder(), proxy_url)
+            .user_agent(user_agent)
+            .build()
+            .unwrap_or_default();
+        Self {
+            api_key,
+            base_url: String::from("https://www.omdbapi.com/"),
+            http_client: Arc::new(http_client),
+            rating_cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub fn from_settings(settings: &AppSettings) -> Option<Self> {
+        let key = settings.omdb_api_key.trim();
+        if key.is_empty() {
+            return None;
+        }
+        Some(Self::new(
+            key.to_string(),
+            &settings.effective_user_agent(),
+            settings.proxy(),
+        ))
+    }
+
+    pub async fn fetch_imdb_rating(&self, imdb_id: &str) -> Result<Option<String>, ApiError> {
+        if let Some(cached) = self
+            .rating_cache
+            .read()
+            .ok()
+            .and_then(|c| c.get(imdb_id).cloned())
+        {
+            return Ok(cached);
+        }
+
+        let url = format!("{}?i={}&apikey={}", self.base_url, imdb_id, self.api_key);
+        let response = self
+            .http_client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| ApiError::Network(e.to_string()))?;
+
+        let response = match response.status().as_u16() {
+            401 => return Err(ApiError::Unauthorized),
+            429 => return Err(ApiError::RateLimit),
+            s if s >= 400 => return Err(ApiError::Network(format!("HTTP error: {}", s))),
+            _ => response,
+        };
+
+        let body: OmdbResponse = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        let rating = body.imdb_rating.filter(|r| r != "N/A" && !r.is_empty());
+
+        if let Ok(mut cache) = self.rating_cache.write() {
+            cache.insert(imdb_id.to_string(), rating.clone());
+        }
+        Ok(rating)
+    }
+}
@@ -0,0 +1,207 @@
+use iced::widget::{button, column, container, row, text, Column, Row, Space};
+use iced::{Border, Color, Element, Length, Padding, Shadow};
+
+use crate::detail_popup::{icon, ICON_PERSON_FILL};
+use crate::media::{Message, Page, Person, PersonDetail, SURFACE_DARK_GRAY, TEXT_GRAY, TEXT_WHITE};
+use crate::tmdb::ImageSize;
+use crate::Movix;
+
+const PEOPLE_PER_ROW: usize = 6;
+const AVATAR_SIZE: f32 = 140.0;
+const PROFILE_AVATAR_SIZE: f32 = 180.0;
+
+fn title_text(s: impl ToString) -> iced::widget::Text<'static> {
+    text(s.to_string())
+        .size(28)
+        .color(TEXT_WHITE)
+        .font(iced::Font {
+            weight: iced::font::Weight::Bold,
+            ..Default::default()
+        })
+}
+
+fn loading_placeholder() -> Element<'static, Message> {
+    container(text("Loading...").size(16).color(TEXT_GRAY))
+        .width(Length::Fill)
+        .height(Length::Fixed(300.0))
+        .center_x(Length::Fill)
+        .center_y(Length::Fill)
+        .into()
+}
+
+impl Movix {
+    pub fn view_people_page(&self) -> Element<'_, Message> {
+        let header = column![
+            title_text("Popular People"),
+            text("Browse the people everyone is watching right now")
+                .size(16)
+                .color(TEXT_GRAY)
+        ]
+        .spacing(8);
+
+        let grid: Element<'_, Message> = if self.popular_people.is_empty() {
+            loading_placeholder()
+        } else {
+            let rows: Vec<Element<Message>> = self
+                .popular_people
+                .chunks(PEOPLE_PER_ROW)
+                .map(|chunk| {
+                    let cards: Vec<Element<Message>> = chunk
+                        .iter()
+                        .map(|person| self.view_person_card(person))
+                        .collect();
+                    Row::with_children(cards)
+                        .spacing(24)
+                        .align_y(iced::Alignment::Start)
+                        .into()
+                })
+                .collect();
+            Column::with_children(rows).spacing(32).into()
+        };
+
+        column![header, grid]
+            .spacing(24)
+            .padding(Padding::new(100.0).left(48.0).right(48.0).bottom(48.0))
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn view_person_card<'a>(&'a self, person: &'a Person) -> Element<'a, Message> {
+        let avatar = self.view_person_avatar(person, AVATAR_SIZE);
+        let known_for = person
+            .known_for
+            .iter()
+            .take(2)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let content = column![
+            avatar,
+            text(person.name.clone())
+                .size(15)
+                .color(TEXT_WHITE)
+                .font(iced::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+            text(person.known_for_department.clone())
+                .size(12)
+                .color(TEXT_GRAY),
+            text(known_for).size(12).color(TEXT_GRAY)
+        ]
+        .spacing(6)
+        .width(Length::Fixed(AVATAR_SIZE + 20.0))
+        .align_x(iced::Alignment::Center);
+
+        button(content)
+            .padding(8)
+            .style(|_theme, status| button::Style {
+                background: Some(iced::Background::Color(
+                    if matches!(status, button::Status::Hovered) {
+                        SURFACE_DARK_GRAY
+                    } else {
+                        Color::TRANSPARENT
+                    },
+                )),
+                text_color: TEXT_WHITE,
+                border: Border {
+                    radius: 8.0.into(),
+                    ..Default::default()
+                },
+                shadow: Shadow::default(),
+                snap: false,
+            })
+            .on_press(Message::NavigateTo(Page::Person(person.id)))
+            .into()
+    }
+
+    fn view_person_avatar(&self, person: &Person, size: f32) -> Element<'_, Message> {
+        let handle = self.get_cached_image(person.profile_path.as_ref(), ImageSize::Poster);
+        Self::image_or_placeholder(
+            handle,
+            size,
+            size,
+            size / 2.0,
+            icon(ICON_PERSON_FILL)
+                .size(size / 3.0)
+                .color(TEXT_GRAY)
+                .into(),
+        )
+    }
+
+    pub fn view_person_page(&self) -> Element<'_, Message> {
+        let back_button = button(text("Back").size(14).color(TEXT_WHITE))
+            .padding(Padding::new(8.0).left(16.0).right(16.0))
+            .style(|_theme, status| button::Style {
+                background: Some(iced::Background::Color(
+                    if matches!(status, button::Status::Hovered) {
+                        Color::from_rgb(0.25, 0.25, 0.25)
+                    } else {
+                        SURFACE_DARK_GRAY
+                    },
+                )),
+                text_color: TEXT_WHITE,
+                border: Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                shadow: Shadow::default(),
+                snap: false,
+            })
+            .on_press(Message::NavigateTo(self.previous_page.clone()));
+
+        let body = match &self.person_detail {
+            Some(detail) => self.view_person_detail(detail),
+            None => loading_placeholder(),
+        };
+
+        column![row![Space::new().width(Length::Fill), back_button], body]
+            .spacing(24)
+            .padding(Padding::new(100.0).left(48.0).right(48.0).bottom(48.0))
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn view_person_detail<'a>(&'a self, detail: &'a PersonDetail) -> Element<'a, Message> {
+        let avatar = self.view_person_avatar(&detail.person, PROFILE_AVATAR_SIZE);
+
+        let born = match (&detail.birthday, &detail.place_of_birth) {
+            (Some(date), Some(place)) => format!("Born {} in {}", date, place),
+            (Some(date), None) => format!("Born {}", date),
+            (None, Some(place)) => format!("Born in {}", place),
+            (None, None) => String::new(),
+        };
+        let biography = if detail.biography.is_empty() {
+            String::from("No biography available.")
+        } else {
+            detail.biography.clone()
+        };
+
+        let info = column![
+            title_text(&detail.person.name),
+            text(detail.person.known_for_department.clone())
+                .size(14)
+                .color(TEXT_GRAY),
+            text(born).size(14).color(TEXT_GRAY),
+            text(biography).size(14).color(TEXT_WHITE)
+        ]
+        .spacing(8)
+        .width(Length::Fill);
+
+        let profile = row![avatar, info]
+            .spacing(32)
+            .align_y(iced::Alignment::Start);
+
+        let filmography: Element<'_, Message> = if detail.filmography.is_empty() {
+            text("No titles found").size(16).color(TEXT_GRAY).into()
+        } else {
+            self.view_media_grid(&detail.filmography)
+        };
+
+        column![profile, title_text("Filmography").size(22), filmography]
+            .spacing(24)
+            .width(Length::Fill)
+            .into()
+    }
+}
@@ -1,28 +1,80 @@
 use iced::Task;
 
-use crate::media::{MediaId, Message};
-use crate::movie_player::VoeStreamResolver;
+use crate::detail_handlers::{self, lookup_media_type};
+use crate::media::{local_media_id, MediaId, MediaType, Message, Page};
+use crate::movie_player::{
+    PlaybackMetrics, ResumePrompt, VoeStreamResolver, RESUME_PROMPT_TIMEOUT,
+};
+use crate::proxy;
+use crate::streaming::{ResolvedStream, StreamError, StreamQuery, StreamVariant, StreamingService};
+use crate::subtitles::{find_sidecar_subtitles, load_subtitles, SubtitleCue};
+use crate::video::select_best_trailer;
 use crate::Movix;
 
+const STREAM_BLACKLIST_DURATION: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+/// Quiet time after the last volume change before it is saved, so dragging
+/// the slider doesn't write the config on every step.
+const VOLUME_SAVE_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
 pub fn handle_play_content(app: &mut Movix, id: MediaId) -> Task<Message> {
     let item = app
         .content_sections
         .iter()
         .flat_map(|s| &s.items)
+        .chain(&app.hero_items)
+        .chain(&app.search_results)
+        .chain(&app.browse_results)
+        .chain(app.person_detail.iter().flat_map(|d| &d.filmography))
+        .chain(app.detail_popup_data.iter().map(|d| &d.media_item))
         .find(|i| i.id == id)
-        .or_else(|| app.hero_content.as_ref().filter(|h| h.id == id));
+        .map(|i| (i.title.clone(), i.media_type.clone()));
 
-    let Some(item) = item else {
+    let Some((title, media_type)) = item else {
         return Task::none();
     };
-    let title = item.title.clone();
+    play_media(app, id, title, media_type)
+}
+
+/// Series have no single stream to resolve, so playing one opens its episode
+/// list instead.
+pub fn play_media(
+    app: &mut Movix,
+    id: MediaId,
+    title: String,
+    media_type: MediaType,
+) -> Task<Message> {
+    if !matches!(media_type, MediaType::TvSeries) {
+        return start_playback(app, id, title, None);
+    }
 
+    let select_first_season = Task::done(Message::DetailSelectSeason(Some(1)));
+    if app.detail_popup_open && app.detail_popup_media_id == Some(id) {
+        if app.detail_selected_season.is_none() {
+            return select_first_season;
+        }
+        return Task::none();
+    }
+    Task::batch([
+        detail_handlers::open_detail_popup(app, id, media_type),
+        select_first_season,
+    ])
+}
+
+fn reset_movie_player(app: &mut Movix, id: MediaId, title: String) {
     app.movie_player_active = true;
     app.movie_player_media_id = Some(id);
-    app.movie_player_title = Some(title.clone());
+    app.movie_player_title = Some(title);
+    app.movie_player_episode = None;
+    app.movie_player.set_series(None);
     app.movie_player_loading = true;
     app.movie_player_controls_visible = true;
     app.movie_player_error = None;
+    app.movie_player_trailer_only = false;
+    app.movie_player_stream_provider = None;
+    app.apply_stored_volume();
+    offer_resume(app, id);
+    app.movie_player_subtitle_source = None;
+    app.movie_player.set_subtitle_track(None);
     app.hero_video_frame = None;
     app.card_video_frame = None;
     app.hovered_card = None;
@@ -30,10 +82,141 @@ pub fn handle_play_content(app: &mut Movix, id: MediaId) -> Task<Message> {
 
     app.hero_player.stop();
     app.card_player.stop();
+}
+
+/// Asks whether to pick up where the title was left off. The decoder is held
+/// paused until the prompt is answered.
+fn offer_resume(app: &mut Movix, id: MediaId) {
+    app.movie_player_pending_seek = None;
+    app.movie_player_resume_prompt =
+        app.movie_player
+            .get_stored_position(id)
+            .map(|position| ResumePrompt {
+                position,
+                shown_at: std::time::Instant::now(),
+            });
+}
+
+fn play_movie(app: &mut Movix, media_id: MediaId, url: &str) -> Result<(), String> {
+    app.movie_player
+        .play(media_id, url, app.settings.frame_buffer_depth())?;
+    if let Some(prompt) = app.movie_player_resume_prompt.as_mut() {
+        // The timeout counts from when playback could start, not from
+        // when stream resolution began.
+        prompt.shown_at = std::time::Instant::now();
+    }
+    if app.movie_player_resume_prompt.is_some() || app.movie_player_pending_seek.is_some() {
+        app.movie_player.pause();
+    }
+    Ok(())
+}
+
+fn start_playback(
+    app: &mut Movix,
+    id: MediaId,
+    title: String,
+    episode: Option<(u32, u32)>,
+) -> Task<Message> {
+    reset_movie_player(app, id, title.clone());
+    app.movie_player_episode = episode;
+
+    if app.is_offline {
+        app.movie_player_loading = false;
+        app.movie_player_error = Some(String::from("Streaming is unavailable while offline"));
+        app.movie_player_error_retryable = false;
+        return Task::none();
+    }
+
+    resolve_movie_stream(app, id, title)
+}
 
+/// Episodes play under their own id, so progress is kept per episode, while
+/// the stream is looked up by the series title.
+pub fn handle_play_episode(
+    app: &mut Movix,
+    episode_id: MediaId,
+    season: u32,
+    episode: u32,
+) -> Task<Message> {
+    let Some((series_id, title)) = app
+        .detail_popup_data
+        .as_ref()
+        .map(|d| (d.media_item.id, d.media_item.title.clone()))
+    else {
+        return Task::none();
+    };
+    let task = start_playback(app, episode_id, title, Some((season, episode)));
+    app.movie_player.set_series(Some(series_id));
+    task
+}
+
+/// Plays the path from the open-file dialog directly, skipping stream
+/// resolution. Works offline since nothing is fetched.
+pub fn handle_open_local_file(app: &mut Movix) -> Task<Message> {
+    let Some(input) = app.open_file_path.as_deref() else {
+        return Task::none();
+    };
+    let path = input.trim().trim_matches(|c| c == '"' || c == '\'');
+    if path.is_empty() {
+        return Task::none();
+    }
+    if !std::path::Path::new(path).is_file() {
+        app.open_file_error = Some(format!("No file found at {}", path));
+        return Task::none();
+    }
+
+    let path = path.to_string();
+    let media_id = local_media_id(&path);
+    let title = std::path::Path::new(&path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.clone());
+    app.open_file_path = None;
+    app.open_file_error = None;
+
+    reset_movie_player(app, media_id, title);
+    clear_stream_variants(app);
+    app.movie_player_loading = false;
+    if let Err(e) = play_movie(app, media_id, &path) {
+        app.movie_player_error = Some(e);
+        app.movie_player_error_retryable = false;
+        return Task::none();
+    }
+    match find_sidecar_subtitles(&path) {
+        Some(subtitles) => handle_movie_set_subtitle(app, Some(subtitles)),
+        None => Task::none(),
+    }
+}
+
+/// Providers reported broken for this title are skipped until their entry
+/// expires, even when the title has an override pointing at them.
+fn resolve_movie_stream(app: &mut Movix, media_id: MediaId, title: String) -> Task<Message> {
+    clear_stream_variants(app);
+    app.stream_blacklist
+        .retain(|_, reported_at| reported_at.elapsed() < STREAM_BLACKLIST_DURATION);
+    let excluded: Vec<String> = app
+        .stream_blacklist
+        .keys()
+        .filter(|(_, id)| *id == media_id)
+        .map(|(provider, _)| provider.clone())
+        .collect();
+    let provider = app
+        .settings
+        .stream_provider_overrides
+        .get(&media_id)
+        .filter(|name| !excluded.contains(name))
+        .cloned();
+    let query = match app.movie_player_episode {
+        Some((season, episode)) => StreamQuery::episode(&title, season, episode),
+        None => StreamQuery::movie(&title),
+    };
+    let service = StreamingService::from_settings(&app.settings);
     Task::perform(
-        async move { VoeStreamResolver::get_download_url(&title).await },
-        move |result| Message::MoviePlayerStreamResolved(id, result),
+        async move {
+            VoeStreamResolver::get_download_url(&service, &query, provider.as_deref(), &excluded)
+                .await
+        },
+        move |result| Message::MoviePlayerStreamResolved(media_id, result),
     )
 }
 
@@ -43,6 +226,9 @@ pub fn handle_trailer_stream_url_loaded(
     result: Result<String, String>,
 ) -> Task<Message> {
     let Ok(url) = result else {
+        if app.card_trailer_resolving == Some(media_id) {
+            app.card_trailer_resolving = None;
+        }
         return Task::none();
     };
     app.stream_url_cache.insert(media_id, url.clone());
@@ -61,7 +247,7 @@ pub fn handle_trailer_stream_url_loaded(
         return Task::none();
     }
 
-    let is_hero = app.hero_content.as_ref().map(|h| h.id) == Some(media_id);
+    let is_hero = app.hero_content().map(|h| h.id) == Some(media_id);
     let is_hovered = app.hovered_card == Some(media_id);
 
     if is_hero && app.hero_visible {
@@ -73,27 +259,105 @@ pub fn handle_trailer_stream_url_loaded(
     Task::none()
 }
 
+/// The trailer players that can decode on their own threads.
+#[derive(Clone, Copy, PartialEq)]
+enum TrailerSlot {
+    Hero,
+    Card,
+    Detail,
+}
+
+/// Only one trailer context is on screen at a time, so starting one releases
+/// the others. The hero is paused rather than stopped so it can resume where
+/// it left off.
+fn claim_trailer_decoder(app: &mut Movix, slot: TrailerSlot) {
+    if slot != TrailerSlot::Hero && app.hero_player.is_playing() {
+        app.hero_player.pause();
+    }
+    if slot != TrailerSlot::Card && app.card_player.has_pipeline() {
+        app.card_player.stop();
+        app.card_video_frame = None;
+    }
+    if slot != TrailerSlot::Detail && app.detail_player.has_pipeline() {
+        app.detail_player.stop();
+        app.detail_video_frame = None;
+    }
+}
+
 pub fn handle_play_hero_trailer(app: &mut Movix, media_id: MediaId) -> Task<Message> {
-    if let Some(url) = app.stream_url_cache.get(&media_id).cloned() {
-        let _ = app.hero_player.play(media_id, &url);
+    if !app.settings.autoplay_trailers() {
+        return Task::none();
     }
+    if let Some(wait) = hero_trailer_wait(app) {
+        return resume_hero_after(wait);
+    }
+    start_hero_player(app, media_id);
     Task::none()
 }
 
+/// Time the current hero's backdrop still has to stay up before its trailer
+/// may start.
+fn hero_trailer_wait(app: &Movix) -> Option<std::time::Duration> {
+    std::time::Duration::from_secs(app.settings.hero_trailer_delay_secs)
+        .checked_sub(app.hero_rotated_at.elapsed())
+        .filter(|wait| !wait.is_zero())
+}
+
+/// Goes through `ResumeHeroTrailer` so the hero is re-checked for visibility
+/// once the wait is over.
+fn resume_hero_after(wait: std::time::Duration) -> Task<Message> {
+    Task::perform(async move { tokio::time::sleep(wait).await }, |_| {
+        Message::ResumeHeroTrailer
+    })
+}
+
+fn start_hero_player(app: &mut Movix, media_id: MediaId) {
+    let Some(url) = app.stream_url_cache.get(&media_id).cloned() else {
+        return;
+    };
+    if app.settings.hero_start_muted && app.hero_player.current_media_id() != Some(media_id) {
+        app.hero_player.set_muted(true);
+    }
+    app.hero_muted = app.hero_player.is_muted();
+    claim_trailer_decoder(app, TrailerSlot::Hero);
+    let _ = app
+        .hero_player
+        .play(media_id, &url, app.settings.frame_buffer_depth());
+}
+
 pub fn handle_play_card_trailer(app: &mut Movix, media_id: MediaId) -> Task<Message> {
+    if app.card_trailer_resolving == Some(media_id) {
+        app.card_trailer_resolving = None;
+    }
+    if !app.settings.autoplay_trailers() {
+        return Task::none();
+    }
     if let Some(url) = app.stream_url_cache.get(&media_id).cloned() {
-        let _ = app.card_player.play(media_id, &url);
+        claim_trailer_decoder(app, TrailerSlot::Card);
+        let _ = app
+            .card_player
+            .play(media_id, &url, app.settings.frame_buffer_depth());
     }
     Task::none()
 }
 
 pub fn handle_play_detail_trailer(app: &mut Movix, media_id: MediaId) -> Task<Message> {
+    if !app.settings.autoplay_trailers() {
+        return Task::none();
+    }
     if let Some(url) = app.stream_url_cache.get(&media_id).cloned() {
-        let _ = app.detail_player.play(media_id, &url);
+        play_detail_video(app, media_id, &url);
     }
     Task::none()
 }
 
+pub fn play_detail_video(app: &mut Movix, media_id: MediaId, url: &str) {
+    claim_trailer_decoder(app, TrailerSlot::Detail);
+    let _ = app
+        .detail_player
+        .play(media_id, url, app.settings.frame_buffer_depth());
+}
+
 pub fn handle_hero_frame_tick(app: &mut Movix) -> Task<Message> {
     if app.movie_player_active {
         return Task::none();
@@ -138,31 +402,38 @@ pub fn handle_pause_hero_trailer(app: &mut Movix) -> Task<Message> {
 }
 
 pub fn handle_resume_hero_trailer(app: &mut Movix) -> Task<Message> {
-    if app.movie_player_active || app.detail_popup_open {
+    if app.movie_player_active
+        || app.detail_popup_open
+        || app.is_offline
+        || app.current_page.hides_hero()
+    {
         return Task::none();
     }
     if !app.hero_visible {
         return Task::none();
     }
 
-    let hero_id = match app.hero_content.as_ref() {
+    let hero_id = match app.hero_content() {
         Some(hero) => hero.id,
         None => return Task::none(),
     };
 
     if app.hero_player.has_pipeline() {
+        claim_trailer_decoder(app, TrailerSlot::Hero);
         app.hero_player.resume();
         return Task::none();
     }
-
-    if let Some(url) = app.stream_url_cache.get(&hero_id).cloned() {
-        let _ = app.hero_player.play(hero_id, &url);
+    if let Some(wait) = hero_trailer_wait(app) {
+        return resume_hero_after(wait);
     }
+
+    start_hero_player(app, hero_id);
     Task::none()
 }
 
 pub fn handle_toggle_hero_mute(app: &mut Movix) -> Task<Message> {
     app.hero_player.toggle_mute();
+    app.hero_muted = app.hero_player.is_muted();
     Task::none()
 }
 
@@ -177,53 +448,334 @@ pub fn handle_movie_player_open(
     media_id: MediaId,
     title: String,
 ) -> Task<Message> {
-    app.movie_player_active = true;
-    app.movie_player_media_id = Some(media_id);
-    app.movie_player_title = Some(title.clone());
-    app.movie_player_loading = true;
+    start_playback(app, media_id, title, None)
+}
+
+pub fn handle_movie_stream_resolved(
+    app: &mut Movix,
+    media_id: MediaId,
+    result: Result<ResolvedStream, StreamError>,
+) -> Task<Message> {
+    if !app.movie_player_active || app.movie_player_media_id != Some(media_id) {
+        return Task::none();
+    }
+    match result {
+        Ok(stream) => {
+            app.movie_player_loading = false;
+            let Some(variant) = stream
+                .select(app.settings.preferred_stream_quality)
+                .cloned()
+            else {
+                return Task::none();
+            };
+            app.movie_player_stream_provider = Some(stream.provider);
+            app.movie_player_stream_variants = stream.variants;
+            let _ = play_stream_variant(app, media_id, &variant);
+            Task::none()
+        }
+        // An episode's id isn't a title TMDb has trailers for.
+        Err(error)
+            if app.settings.trailer_fallback
+                && !app.is_offline
+                && !error.is_retryable()
+                && app.movie_player_episode.is_none() =>
+        {
+            resolve_trailer_fallback(app, media_id)
+        }
+        Err(error) => {
+            app.movie_player_loading = false;
+            app.movie_player_error = Some(error.to_string());
+            app.movie_player_error_retryable = error.is_retryable();
+            Task::none()
+        }
+    }
+}
+
+/// Decodes at the variant's own height when that is below the usual decode
+/// size.
+fn play_stream_variant(
+    app: &mut Movix,
+    media_id: MediaId,
+    variant: &StreamVariant,
+) -> Result<(), String> {
+    let (width, height) = app.settings.movie_decode_size_for(variant.quality);
+    app.movie_player.set_target_size(width, height);
+    app.movie_player_stream_quality = variant.quality;
+    play_movie(app, media_id, &variant.url)
+}
+
+fn clear_stream_variants(app: &mut Movix) {
+    app.movie_player_stream_variants.clear();
+    app.movie_player_stream_quality = None;
+    let (width, height) = app.settings.movie_decode_size();
+    app.movie_player.set_target_size(width, height);
+}
+
+/// Switches to another variant of the current stream and carries on from the
+/// same position. The choice is remembered for later titles; picking the
+/// best variant clears the preference so better streams aren't capped.
+pub fn handle_movie_set_quality(app: &mut Movix, quality: Option<u32>) -> Task<Message> {
+    let Some(media_id) = app.movie_player_media_id else {
+        return Task::none();
+    };
+    if quality == app.movie_player_stream_quality {
+        return Task::none();
+    }
+    let Some(index) = app
+        .movie_player_stream_variants
+        .iter()
+        .position(|variant| variant.quality == quality)
+    else {
+        return Task::none();
+    };
+    let variant = app.movie_player_stream_variants[index].clone();
+
+    app.settings.preferred_stream_quality = if index == 0 { None } else { quality };
+    let _ = app.settings.save();
+
+    let position = app.movie_player.position();
+    if app.movie_player_pending_seek.is_none()
+        && app.movie_player_resume_prompt.is_none()
+        && position > 0.0
+    {
+        app.movie_player_pending_seek = Some(position);
+    }
+    app.movie_player_frame = None;
+    if let Err(e) = play_stream_variant(app, media_id, &variant) {
+        app.movie_player_error = Some(e);
+        app.movie_player_error_retryable = false;
+    }
+    Task::none()
+}
+
+/// Resolves the YouTube trailer stream for a title that has no full stream,
+/// reusing whatever the trailer caches already know about it.
+fn resolve_trailer_fallback(app: &Movix, media_id: MediaId) -> Task<Message> {
+    if let Some(url) = app.stream_url_cache.get(&media_id).cloned() {
+        return Task::done(Message::MoviePlayerTrailerResolved(media_id, Ok(url)));
+    }
+    let known_trailer = app.trailer_cache.get(&media_id).cloned();
+    let Some(client) = app.tmdb_client.clone() else {
+        return Task::done(Message::MoviePlayerTrailerResolved(
+            media_id,
+            Err(String::from("No stream found")),
+        ));
+    };
+    if matches!(known_trailer, Some(None)) {
+        return Task::done(Message::MoviePlayerTrailerResolved(
+            media_id,
+            Err(String::from("No stream or trailer found")),
+        ));
+    }
 
+    let media_type = lookup_media_type(app, media_id);
+    let manager = app.trailer_manager.clone();
     Task::perform(
-        async move { VoeStreamResolver::get_download_url(&title).await },
-        move |result| Message::MoviePlayerStreamResolved(media_id, result),
+        async move {
+            let youtube_id = match known_trailer.flatten() {
+                Some(youtube_id) => youtube_id,
+                None => {
+                    let videos = client
+                        .fetch_videos(media_id, &media_type)
+                        .await
+                        .map_err(|e| format!("{:?}", e))?;
+                    select_best_trailer(&videos)
+                        .map(|t| t.key.clone())
+                        .ok_or_else(|| String::from("No stream or trailer found"))?
+                }
+            };
+            manager.get_stream_url(&youtube_id).await
+        },
+        move |result| Message::MoviePlayerTrailerResolved(media_id, result),
     )
 }
 
-pub fn handle_movie_stream_resolved(
+pub fn handle_movie_trailer_resolved(
     app: &mut Movix,
     media_id: MediaId,
     result: Result<String, String>,
 ) -> Task<Message> {
+    if !app.movie_player_active || app.movie_player_media_id != Some(media_id) {
+        return Task::none();
+    }
     app.movie_player_loading = false;
     match result {
         Ok(url) => {
-            let _ = app.movie_player.play(media_id, &url);
-            if let Some(pos) = app.movie_player.get_stored_position(media_id) {
-                app.movie_player.seek(pos);
-            }
-            Task::none()
+            app.stream_url_cache.insert(media_id, url.clone());
+            app.movie_player_trailer_only = true;
+            // Saved progress belongs to the full title, not its trailer.
+            app.movie_player_resume_prompt = None;
+            app.movie_player_pending_seek = None;
+            let _ = app
+                .movie_player
+                .play(media_id, &url, app.settings.frame_buffer_depth());
         }
         Err(error) => {
             app.movie_player_error = Some(error);
-            Task::none()
+            app.movie_player_error_retryable = false;
         }
     }
+    Task::none()
 }
 
 pub fn handle_movie_player_close(app: &mut Movix) -> Task<Message> {
+    let exit_fullscreen = if app.movie_player_fullscreen {
+        set_movie_fullscreen(app, false)
+    } else {
+        Task::none()
+    };
     app.movie_player_active = false;
     app.movie_player_frame = None;
     app.movie_player_error = None;
+    app.movie_player_resume_prompt = None;
+    app.movie_player_pending_seek = None;
+    save_volume(app);
 
     let should_resume_hero = app.hero_visible && !app.detail_popup_open;
 
-    app.movie_player.save_progress_sync();
+    if !app.movie_player_trailer_only {
+        app.movie_player.save_progress_sync();
+    }
+    app.movie_player_trailer_only = false;
+    app.movie_player_stream_provider = None;
+    clear_stream_variants(app);
     app.movie_player.stop();
 
     if should_resume_hero {
-        Task::done(Message::ResumeHeroTrailer)
+        Task::batch([exit_fullscreen, Task::done(Message::ResumeHeroTrailer)])
     } else {
-        Task::none()
+        exit_fullscreen
+    }
+}
+
+pub fn handle_movie_toggle_fullscreen(app: &mut Movix) -> Task<Message> {
+    if !app.movie_player_active {
+        return Task::none();
+    }
+    let fullscreen = !app.movie_player_fullscreen;
+    set_movie_fullscreen(app, fullscreen)
+}
+
+/// Leaves fullscreen first, so a second press is needed to close the player.
+pub fn handle_movie_escape(app: &mut Movix) -> Task<Message> {
+    if !app.movie_player_active {
+        return Task::none();
+    }
+    if app.movie_player_fullscreen {
+        return set_movie_fullscreen(app, false);
+    }
+    handle_movie_player_close(app)
+}
+
+fn set_movie_fullscreen(app: &mut Movix, fullscreen: bool) -> Task<Message> {
+    app.movie_player_fullscreen = fullscreen;
+    let mode = if fullscreen {
+        iced::window::Mode::Fullscreen
+    } else {
+        iced::window::Mode::Windowed
+    };
+    match app.window_id {
+        Some(id) => iced::window::set_mode(id, mode),
+        None => iced::window::latest().then(move |id| match id {
+            Some(id) => {
+                Task::done(Message::WindowIdentified(id)).chain(iced::window::set_mode(id, mode))
+            }
+            None => Task::none(),
+        }),
+    }
+}
+
+/// Flushes progress periodically so a crash or kill mid-movie loses at most
+/// one interval, independent of the save on close.
+pub fn handle_movie_auto_save(app: &mut Movix) -> Task<Message> {
+    if app.movie_player_active && !app.movie_player_trailer_only {
+        app.movie_player.save_progress_sync();
+    }
+    Task::none()
+}
+
+pub fn handle_movie_retry(app: &mut Movix) -> Task<Message> {
+    let Some(media_id) = app.movie_player_media_id else {
+        return Task::none();
+    };
+    app.movie_player_error = None;
+    app.movie_player_error_retryable = false;
+    app.movie_player_loading = true;
+    let title = app.movie_player_title.clone().unwrap_or_default();
+    resolve_movie_stream(app, media_id, title)
+}
+
+/// Blacklists the provider of the current stream for this title and resolves
+/// it again, so the next attempt goes to another provider or the trailer.
+pub fn handle_movie_report_broken(app: &mut Movix) -> Task<Message> {
+    let Some(media_id) = app.movie_player_media_id else {
+        return Task::none();
+    };
+    let Some(provider) = app.movie_player_stream_provider.take() else {
+        return Task::none();
+    };
+    app.stream_blacklist
+        .insert((provider, media_id), std::time::Instant::now());
+
+    app.movie_player.stop();
+    app.movie_player_frame = None;
+    app.movie_player_error = None;
+    app.movie_player_loading = true;
+    let title = app.movie_player_title.clone().unwrap_or_default();
+    resolve_movie_stream(app, media_id, title)
+}
+
+pub fn handle_movie_resume_stored(app: &mut Movix) -> Task<Message> {
+    if let Some(prompt) = app.movie_player_resume_prompt.take() {
+        app.movie_player_pending_seek = Some(prompt.position);
+    }
+    Task::none()
+}
+
+pub fn handle_movie_start_over(app: &mut Movix) -> Task<Message> {
+    if app.movie_player_resume_prompt.take().is_none() {
+        return Task::none();
     }
+    if let Some(media_id) = app.movie_player_media_id {
+        app.movie_player.clear_stored_position(media_id);
+    }
+    if app.movie_player.has_pipeline() {
+        app.movie_player.resume();
+    }
+    Task::none()
+}
+
+pub fn handle_movie_set_subtitle(app: &mut Movix, source: Option<String>) -> Task<Message> {
+    app.movie_player.set_subtitle_track(source.clone());
+    let Some(source) = source else {
+        return Task::none();
+    };
+    app.movie_player_subtitle_source = Some(source.clone());
+    let client = proxy::apply(reqwest::Client::builder(), app.settings.proxy())
+        .user_agent(app.settings.effective_user_agent())
+        .build()
+        .unwrap_or_default();
+    Task::perform(load_subtitles(client, source.clone()), move |result| {
+        Message::MoviePlayerSubtitlesLoaded(source, result)
+    })
+}
+
+/// A track that fails to load is turned off again, leaving the toggle to
+/// retry it.
+pub fn handle_movie_subtitles_loaded(
+    app: &mut Movix,
+    source: String,
+    result: Result<Vec<SubtitleCue>, String>,
+) -> Task<Message> {
+    match result {
+        Ok(cues) => app.movie_player.set_subtitle_cues(&source, cues),
+        Err(_) => {
+            if app.movie_player.subtitle_url() == Some(source.as_str()) {
+                app.movie_player.set_subtitle_track(None);
+            }
+        }
+    }
+    Task::none()
 }
 
 pub fn handle_movie_toggle_play(app: &mut Movix) -> Task<Message> {
@@ -233,26 +785,77 @@ pub fn handle_movie_toggle_play(app: &mut Movix) -> Task<Message> {
 
 pub fn handle_movie_seek(app: &mut Movix, position: f64) -> Task<Message> {
     app.movie_player.seek(position);
+    app.movie_player_position = app.movie_player.position();
     Task::none()
 }
 
 pub fn handle_movie_seek_relative(app: &mut Movix, delta: f64) -> Task<Message> {
     app.movie_player.seek_relative(delta);
+    app.movie_player_position = app.movie_player.position();
     Task::none()
 }
 
 pub fn handle_movie_set_volume(app: &mut Movix, volume: f64) -> Task<Message> {
     app.movie_player_volume = volume;
     app.movie_player.set_volume(volume);
+    app.movie_player_volume_changed_at = Some(std::time::Instant::now());
+    for player in [
+        &mut app.hero_player,
+        &mut app.card_player,
+        &mut app.detail_player,
+    ] {
+        player.set_volume(volume as f32);
+    }
+    app.settings.player_volume = volume;
+    app.volume_save_timer = Some(std::time::Instant::now());
     Task::none()
 }
 
 pub fn handle_movie_toggle_mute(app: &mut Movix) -> Task<Message> {
     app.movie_player.toggle_mute();
+    app.settings.player_muted = app.movie_player.is_muted();
+    app.volume_save_timer = Some(std::time::Instant::now());
+    Task::none()
+}
+
+pub fn handle_save_volume_tick(app: &mut Movix) -> Task<Message> {
+    if app
+        .volume_save_timer
+        .is_some_and(|timer| timer.elapsed() >= VOLUME_SAVE_DELAY)
+    {
+        save_volume(app);
+    }
+    Task::none()
+}
+
+fn save_volume(app: &mut Movix) {
+    if app.volume_save_timer.take().is_none() {
+        return;
+    }
+    let _ = app.settings.save();
+}
+
+pub fn handle_movie_toggle_metrics(app: &mut Movix) -> Task<Message> {
+    if app.movie_player_active {
+        app.movie_player_metrics_visible = !app.movie_player_metrics_visible;
+        app.movie_player_metrics = PlaybackMetrics::default();
+    }
     Task::none()
 }
 
 pub fn handle_movie_frame_tick(app: &mut Movix) {
+    if app
+        .movie_player_resume_prompt
+        .is_some_and(|prompt| prompt.shown_at.elapsed() > RESUME_PROMPT_TIMEOUT)
+    {
+        let _ = handle_movie_resume_stored(app);
+    }
+    if app.movie_player_resume_prompt.is_none() && app.movie_player.duration() > 0.0 {
+        if let Some(position) = app.movie_player_pending_seek.take() {
+            app.movie_player.seek(position);
+            app.movie_player.resume();
+        }
+    }
     app.movie_player_position = app.movie_player.position();
     app.movie_player_duration = app.movie_player.duration();
     app.movie_player_playing = app.movie_player.is_playing();
@@ -274,8 +877,15 @@ pub fn handle_movie_frame_tick(app: &mut Movix) {
             ));
         }
     }
+    if app.movie_player_metrics_visible {
+        app.movie_player_metrics.record(app.movie_player.stats());
+    }
     if app.movie_player.check_ended() {
         app.movie_player_playing = false;
+        if app.movie_player.read_failed() && app.movie_player_error.is_none() {
+            app.movie_player_error = Some(String::from("The stream stopped responding"));
+            app.movie_player_error_retryable = true;
+        }
     }
 
     if let Some(timer) = app.movie_player_controls_timer {
@@ -287,6 +897,9 @@ pub fn handle_movie_frame_tick(app: &mut Movix) {
 }
 
 pub fn handle_movie_show_controls(app: &mut Movix) -> Task<Message> {
+    if let Some(prompt) = app.movie_player_resume_prompt.as_mut() {
+        prompt.shown_at = std::time::Instant::now();
+    }
     if !app.movie_player_controls_visible {
         app.movie_player_controls_visible = true;
     }
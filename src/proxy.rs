@@ -0,0 +1,74 @@
+const REACHABILITY_URL: &str = "https://api.themoviedb.org/3";
+
+/// Routes a reqwest client through the configured proxy, if any.
+pub fn apply(builder: reqwest::ClientBuilder, proxy_url: Option<&str>) -> reqwest::ClientBuilder {
+    match proxy_url.and_then(|url| reqwest::Proxy::all(url).ok()) {
+        Some(proxy) => builder.proxy(proxy),
+        None => builder,
+    }
+}
+
+pub fn validate(proxy_url: &str) -> Result<(), String> {
+    reqwest::Proxy::all(proxy_url)
+        .map(|_| ())
+        .map_err(|e| format!("Invalid proxy URL {}: {}", proxy_url, e))
+}
+
+/// `ffmpeg_next::format::input_with_interrupt` with ffmpeg's `http_proxy`
+/// option set for this input only. The HLS demuxer hands the option on to the
+/// playlist and segment requests it opens.
+pub fn open_input<F>(
+    url: &str,
+    proxy_url: Option<&str>,
+    interrupt: F,
+) -> Result<ffmpeg_next::format::context::Input, ffmpeg_next::Error>
+where
+    F: FnMut() -> bool + 'static,
+{
+    let Some(proxy_url) = proxy_url else {
+        return ffmpeg_next::format::input_with_interrupt(&url, interrupt);
+    };
+    let path = std::ffi::CString::new(url).map_err(|_| ffmpeg_next::Error::InvalidData)?;
+    let mut options = ffmpeg_next::Dictionary::new();
+    options.set("http_proxy", proxy_url);
+
+    unsafe {
+        let mut context = ffmpeg_next::ffi::avformat_alloc_context();
+        (*context).interrupt_callback =
+            ffmpeg_next::util::interrupt::new(Box::new(interrupt)).interrupt;
+        let mut options = options.disown();
+        let opened = ffmpeg_next::ffi::avformat_open_input(
+            &mut context,
+            path.as_ptr(),
+            std::ptr::null_mut(),
+            &mut options,
+        );
+        // Whatever ffmpeg didn't consume is handed back and freed here.
+        drop(ffmpeg_next::Dictionary::own(options));
+        if opened < 0 {
+            return Err(ffmpeg_next::Error::from(opened));
+        }
+        let found = ffmpeg_next::ffi::avformat_find_stream_info(context, std::ptr::null_mut());
+        if found < 0 {
+            ffmpeg_next::ffi::avformat_close_input(&mut context);
+            return Err(ffmpeg_next::Error::from(found));
+        }
+        Ok(ffmpeg_next::format::context::Input::wrap(context))
+    }
+}
+
+/// Any HTTP response through the proxy counts as reachable; only connection
+/// failures are reported.
+pub async fn check_reachable(proxy_url: String) -> Result<(), String> {
+    validate(&proxy_url)?;
+    let client = apply(reqwest::Client::builder(), Some(&proxy_url))
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    client
+        .get(REACHABILITY_URL)
+        .send()
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Proxy {} is unreachable: {}", proxy_url, e))
+}
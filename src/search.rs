@@ -4,7 +4,8 @@ use iced::widget::{
 use iced::{Border, Color, Element, Length, Padding, Shadow};
 
 use crate::media::{
-    MediaTypeFilter, Message, SortOption, NETFLIX_RED, SURFACE_DARK_GRAY, TEXT_GRAY, TEXT_WHITE,
+    GenreMatch, MediaItem, MediaTypeFilter, Message, SortOption, NETFLIX_RED, SURFACE_DARK_GRAY,
+    TEXT_GRAY, TEXT_WHITE,
 };
 use crate::tmdb::ImageSize;
 use crate::Movix;
@@ -50,23 +51,127 @@ impl Movix {
         if self.filtered_results.is_empty() {
             return self.view_no_results();
         }
+        let grid = self.view_media_grid(&self.filtered_results);
+        if !self.search_page_loading {
+            return grid;
+        }
+        column![
+            grid,
+            container(text("Loading more...").size(14).color(TEXT_GRAY))
+                .width(Length::Fill)
+                .center_x(Length::Fill)
+        ]
+        .spacing(24)
+        .into()
+    }
+
+    pub fn view_genre_browse_page(&self) -> Element<'_, Message> {
+        let genre_name = self
+            .browse_genre
+            .as_ref()
+            .map(|g| g.name.as_str())
+            .unwrap_or_default();
+        let title = text(genre_name.to_string())
+            .size(28)
+            .color(TEXT_WHITE)
+            .font(iced::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            });
+
+        let back_button = button(text("Back").size(14).color(TEXT_WHITE))
+            .padding(Padding::new(8.0).left(16.0).right(16.0))
+            .style(|_theme, status| button::Style {
+                background: Some(iced::Background::Color(
+                    if matches!(status, button::Status::Hovered) {
+                        Color::from_rgb(0.25, 0.25, 0.25)
+                    } else {
+                        SURFACE_DARK_GRAY
+                    },
+                )),
+                text_color: TEXT_WHITE,
+                border: Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                shadow: Shadow::default(),
+                snap: false,
+            })
+            .on_press(Message::CloseGenreBrowse);
+
+        let header = row![title, Space::new().width(Length::Fill), back_button]
+            .align_y(iced::Alignment::Center)
+            .width(Length::Fill);
+
+        let results = if !self.browse_results.is_empty() {
+            self.view_media_grid(&self.browse_results)
+        } else {
+            let status: Element<Message> = if self.browse_loading {
+                text("Loading...").size(16).color(TEXT_GRAY).into()
+            } else if self.browse_error.is_some() {
+                column![
+                    text("Couldn't load this genre").size(16).color(TEXT_GRAY),
+                    button(text("Retry").size(14).color(TEXT_WHITE))
+                        .padding(Padding::new(6.0).left(16.0).right(16.0))
+                        .style(|_theme, status| button::Style {
+                            background: Some(iced::Background::Color(
+                                if matches!(status, button::Status::Hovered) {
+                                    Color::from_rgb(0.25, 0.25, 0.25)
+                                } else {
+                                    SURFACE_DARK_GRAY
+                                },
+                            )),
+                            text_color: TEXT_WHITE,
+                            border: Border {
+                                radius: 4.0.into(),
+                                ..Default::default()
+                            },
+                            shadow: Shadow::default(),
+                            snap: false,
+                        })
+                        .on_press(Message::RetryGenreBrowse),
+                ]
+                .spacing(16)
+                .align_x(iced::Alignment::Center)
+                .into()
+            } else {
+                text("No titles found in this genre")
+                    .size(16)
+                    .color(TEXT_GRAY)
+                    .into()
+            };
+            container(status)
+                .width(Length::Fill)
+                .height(Length::Fixed(300.0))
+                .center_x(Length::Fill)
+                .center_y(Length::Fill)
+                .into()
+        };
+
+        column![header, results]
+            .spacing(24)
+            .padding(Padding::new(100.0).left(48.0).right(48.0).bottom(48.0))
+            .width(Length::Fill)
+            .into()
+    }
 
-        let cards_per_row = 4;
+    pub fn view_media_grid<'a>(&'a self, items: &'a [MediaItem]) -> Element<'a, Message> {
+        let density = self.settings.search_grid_density;
         let mut rows: Vec<Element<Message>> = Vec::new();
 
-        for chunk in self.filtered_results.chunks(cards_per_row) {
+        for chunk in items.chunks(density.cards_per_row()) {
             let row_cards: Vec<Element<Message>> = chunk
                 .iter()
                 .map(|item| self.view_search_result_card(item))
                 .collect();
             let row_element = Row::with_children(row_cards)
-                .spacing(16)
+                .spacing(density.spacing())
                 .align_y(iced::Alignment::Start);
             rows.push(row_element.into());
         }
 
         Column::with_children(rows)
-            .spacing(16)
+            .spacing(density.spacing())
             .width(Length::Fill)
             .into()
     }
@@ -76,7 +181,7 @@ impl Movix {
         media_item: &crate::media::MediaItem,
     ) -> Element<'_, Message> {
         let media_id = media_item.id;
-        let (w, h) = (276.0, 155.0);
+        let (w, h) = self.settings.search_grid_density.card_size();
 
         if self.hovered_card == Some(media_id) {
             return self.view_search_result_expanded_card(media_item, w, h);
@@ -105,7 +210,8 @@ impl Movix {
         iced::widget::mouse_area(card)
             .on_enter(Message::HoverCard(Some(media_id)))
             .on_exit(Message::HoverCard(None))
-            .on_press(Message::OpenDetailPopup(media_id))
+            .on_press(self.settings.card_click_action.message(media_id))
+            .on_double_click(Message::PlayContent(media_id))
             .into()
     }
 
@@ -139,7 +245,8 @@ impl Movix {
         iced::widget::mouse_area(card)
             .on_enter(Message::HoverCard(Some(media_id)))
             .on_exit(Message::HoverCard(None))
-            .on_press(Message::OpenDetailPopup(media_id))
+            .on_press(self.settings.card_click_action.message(media_id))
+            .on_double_click(Message::PlayContent(media_id))
             .into()
     }
 
@@ -149,15 +256,7 @@ impl Movix {
         w: f32,
         h: f32,
     ) -> Element<'_, Message> {
-        let handle = media_item.backdrop_path.as_ref().and_then(|path| {
-            let url = self
-                .tmdb_client
-                .as_ref()?
-                .image_url(path, ImageSize::Backdrop);
-            self.image_cache.get(&url).cloned()
-        });
-
-        match handle {
+        match self.cached_backdrop(media_item) {
             Some(h_img) => container(
                 iced::widget::image(h_img)
                     .width(Length::Fixed(w))
@@ -417,7 +516,7 @@ impl Movix {
             .size(16)
             .color(TEXT_GRAY);
 
-        container(
+        let empty_state = container(
             column![message, suggestion]
                 .spacing(12)
                 .align_x(iced::Alignment::Center),
@@ -425,7 +524,24 @@ impl Movix {
         .width(Length::Fill)
         .height(Length::Fixed(300.0))
         .center_x(Length::Fill)
-        .center_y(Length::Fill)
+        .center_y(Length::Fill);
+        if self.popular_suggestions.is_empty() {
+            return empty_state.into();
+        }
+
+        let popular_title = text("Popular right now")
+            .size(20)
+            .color(TEXT_WHITE)
+            .font(iced::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            });
+        column![
+            empty_state,
+            popular_title,
+            self.view_media_grid(&self.popular_suggestions)
+        ]
+        .spacing(16)
         .into()
     }
 
@@ -449,7 +565,12 @@ impl Movix {
         .spacing(16)
         .align_y(iced::Alignment::Center);
 
-        container(filter_row)
+        let mut panel = column![filter_row].spacing(12);
+        if let Some(selected_genres) = self.view_selected_genres() {
+            panel = panel.push(selected_genres);
+        }
+
+        container(panel)
             .width(Length::Fill)
             .padding(Padding::new(16.0))
             .style(|_theme| container::Style {
@@ -517,25 +638,28 @@ impl Movix {
     }
 
     fn view_genre_dropdown(&self) -> Element<'_, Message> {
-        let mut options: Vec<String> = vec![String::from("All Genres")];
-        options.extend(self.genre_list.iter().map(|g| g.name.clone()));
-
-        let selected = self
-            .search_filters
-            .genre_id
-            .and_then(|id| self.genre_list.iter().find(|g| g.id == id))
+        let options: Vec<String> = self
+            .genre_list
+            .iter()
+            .filter(|g| !self.search_filters.genre_ids.contains(&g.id))
+            .filter(|g| g.media_scope.matches(self.search_filters.media_type))
             .map(|g| g.name.clone())
-            .unwrap_or_else(|| String::from("All Genres"));
+            .collect();
+
+        let placeholder = if self.search_filters.genre_ids.is_empty() {
+            "All Genres"
+        } else {
+            "Add Genre"
+        };
 
         let genre_list = self.genre_list.clone();
-        pick_list(options, Some(selected), move |sel| {
-            let genre_id = if sel == "All Genres" {
-                None
-            } else {
-                genre_list.iter().find(|g| g.name == sel).map(|g| g.id)
-            };
-            Message::SetGenreFilter(genre_id)
+        let dropdown = pick_list(options, None::<String>, move |sel| {
+            match genre_list.iter().find(|g| g.name == sel) {
+                Some(genre) => Message::ToggleGenreFilter(genre.id),
+                None => Message::SetGenreFilter(None),
+            }
         })
+        .placeholder(placeholder)
         .text_size(13)
         .padding(Padding::new(8.0).left(12.0).right(12.0))
         .style(|_, _| pick_list::Style {
@@ -548,8 +672,101 @@ impl Movix {
                 width: 1.0,
                 radius: 4.0.into(),
             },
+        });
+
+        if self.search_filters.genre_ids.is_empty() {
+            return dropdown.into();
+        }
+        let clear_genres = Self::view_clear_filter_button(Message::SetGenreFilter(None));
+        if self.search_filters.genre_ids.len() < 2 {
+            return row![dropdown, clear_genres]
+                .spacing(8)
+                .align_y(iced::Alignment::Center)
+                .into();
+        }
+
+        let next_match = match self.search_filters.genre_match {
+            GenreMatch::Any => GenreMatch::All,
+            GenreMatch::All => GenreMatch::Any,
+        };
+        let match_toggle = button(
+            text(format!("Match: {}", self.search_filters.genre_match))
+                .size(13)
+                .color(TEXT_WHITE),
+        )
+        .padding(Padding::new(8.0).left(12.0).right(12.0))
+        .style(|_theme, status| button::Style {
+            background: Some(iced::Background::Color(Color::from_rgba(
+                1.0,
+                1.0,
+                1.0,
+                if matches!(status, button::Status::Hovered) {
+                    0.15
+                } else {
+                    0.1
+                },
+            ))),
+            text_color: TEXT_WHITE,
+            border: Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            shadow: Shadow::default(),
+            snap: false,
         })
-        .into()
+        .on_press(Message::SetGenreMatch(next_match));
+
+        row![dropdown, match_toggle, clear_genres]
+            .spacing(8)
+            .align_y(iced::Alignment::Center)
+            .into()
+    }
+
+    fn view_selected_genres(&self) -> Option<Element<'_, Message>> {
+        if self.search_filters.genre_ids.is_empty() {
+            return None;
+        }
+
+        let chips: Vec<Element<Message>> = self
+            .search_filters
+            .genre_ids
+            .iter()
+            .filter_map(|id| self.genre_list.iter().find(|g| g.id == *id))
+            .map(|genre| {
+                button(
+                    text(format!("{}  ×", genre.name))
+                        .size(12)
+                        .color(TEXT_WHITE),
+                )
+                .padding(Padding::new(4.0).left(10.0).right(10.0))
+                .style(|_theme, status| button::Style {
+                    background: Some(iced::Background::Color(
+                        if matches!(status, button::Status::Hovered) {
+                            Color::from_rgba(0.898, 0.035, 0.078, 0.5)
+                        } else {
+                            Color::from_rgba(0.898, 0.035, 0.078, 0.3)
+                        },
+                    )),
+                    text_color: TEXT_WHITE,
+                    border: Border {
+                        color: NETFLIX_RED,
+                        width: 1.0,
+                        radius: 12.0.into(),
+                    },
+                    shadow: Shadow::default(),
+                    snap: false,
+                })
+                .on_press(Message::ToggleGenreFilter(genre.id))
+                .into()
+            })
+            .collect();
+
+        Some(
+            Row::with_children(chips)
+                .spacing(8)
+                .align_y(iced::Alignment::Center)
+                .into(),
+        )
     }
 
     fn view_year_range_inputs(&self) -> Element<'_, Message> {
@@ -590,25 +807,30 @@ impl Movix {
             .width(Length::Fixed(70.0))
             .style(year_input_style);
 
-        row![
-            text("Year:").size(13).color(TEXT_GRAY),
-            year_from_input,
-            text("-").size(13).color(TEXT_GRAY),
-            year_to_input
-        ]
-        .spacing(8)
-        .align_y(iced::Alignment::Center)
-        .into()
+        let mut year_row = row![text("Year:").size(13).color(TEXT_GRAY), year_from_input]
+            .spacing(8)
+            .align_y(iced::Alignment::Center);
+        if self.search_filters.year_from.is_some() {
+            year_row = year_row.push(Self::view_clear_filter_button(Message::SetYearFrom(None)));
+        }
+        year_row = year_row
+            .push(text("-").size(13).color(TEXT_GRAY))
+            .push(year_to_input);
+        if self.search_filters.year_to.is_some() {
+            year_row = year_row.push(Self::view_clear_filter_button(Message::SetYearTo(None)));
+        }
+        year_row.into()
     }
 
     fn view_rating_slider(&self) -> Element<'_, Message> {
         let rating_value = self.search_filters.min_rating;
         let rating_text = format!("{:.1}+", rating_value);
 
+        let step = self.settings.rating_filter_step.clamp(0.1, 1.0);
         let rating_slider_widget = slider(0.0..=10.0, rating_value, Message::SetMinRating)
             .width(Length::Fixed(100.0))
             .height(4.0)
-            .step(0.5)
+            .step(step)
             .style(|_, _| slider::Style {
                 rail: slider::Rail {
                     backgrounds: (
@@ -626,14 +848,18 @@ impl Movix {
                 },
             });
 
-        row![
+        let mut rating_row = row![
             text("Rating:").size(13).color(TEXT_GRAY),
             rating_slider_widget,
             text(rating_text).size(13).color(TEXT_WHITE)
         ]
         .spacing(8)
-        .align_y(iced::Alignment::Center)
-        .into()
+        .align_y(iced::Alignment::Center);
+        if rating_value > 0.0 {
+            rating_row =
+                rating_row.push(Self::view_clear_filter_button(Message::SetMinRating(0.0)));
+        }
+        rating_row.into()
     }
 
     fn view_sort_dropdown(&self) -> Element<'_, Message> {
@@ -644,7 +870,7 @@ impl Movix {
             SortOption::Alphabetical,
         ];
 
-        pick_list(
+        let dropdown = pick_list(
             options,
             Some(self.search_filters.sort_by),
             Message::SetSortOption,
@@ -661,10 +887,46 @@ impl Movix {
                 width: 1.0,
                 radius: 4.0.into(),
             },
-        })
+        });
+
+        if self.search_filters.sort_by == SortOption::default() {
+            return dropdown.into();
+        }
+        row![
+            dropdown,
+            Self::view_clear_filter_button(Message::SetSortOption(SortOption::default()))
+        ]
+        .spacing(8)
+        .align_y(iced::Alignment::Center)
         .into()
     }
 
+    fn view_clear_filter_button(message: Message) -> Element<'static, Message> {
+        button(text("×").size(14).color(TEXT_WHITE))
+            .padding(Padding::new(2.0).left(8.0).right(8.0))
+            .style(|_theme, status| button::Style {
+                background: Some(iced::Background::Color(Color::from_rgba(
+                    1.0,
+                    1.0,
+                    1.0,
+                    if matches!(status, button::Status::Hovered) {
+                        0.25
+                    } else {
+                        0.1
+                    },
+                ))),
+                text_color: TEXT_WHITE,
+                border: Border {
+                    radius: 10.0.into(),
+                    ..Default::default()
+                },
+                shadow: Shadow::default(),
+                snap: false,
+            })
+            .on_press(message)
+            .into()
+    }
+
     fn view_reset_button(&self) -> Element<'_, Message> {
         button(text("Reset").size(13).color(TEXT_WHITE))
             .padding(Padding::new(8.0).left(16.0).right(16.0))
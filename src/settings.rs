@@ -1,15 +1,243 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use iced::widget::{button, column, container, row, text, text_input, Space};
+use iced::widget::{button, column, container, pick_list, row, text, text_input, Row, Space};
 use iced::{Alignment, Element, Length};
 use serde::{Deserialize, Serialize};
 
-use crate::media::{BACKGROUND_BLACK, NETFLIX_RED, TEXT_GRAY, TEXT_WHITE};
+use crate::audio::{AudioConfig, SYSTEM_DEFAULT_DEVICE};
+use crate::media::{
+    CardClickAction, CardExpandStyle, GridDensity, MediaId, SearchPreferences, TopPicksSource,
+    WatchProvider, BACKGROUND_BLACK, DEFAULT_IMAGE_CACHE_ENTRIES, DEFAULT_IMAGE_DISK_BUDGET_MB,
+    NETFLIX_RED, TEXT_GRAY, TEXT_WHITE,
+};
+use crate::tmdb::CacheStats;
+use crate::video::{CARD_TRAILER_SIZE, DETAIL_TRAILER_SIZE};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
     pub api_key: String,
     pub language: String,
+    #[serde(default)]
+    pub omdb_api_key: String,
+    #[serde(default = "default_hero_rotation_secs")]
+    pub hero_rotation_secs: u64,
+    #[serde(default = "default_true")]
+    pub hero_start_muted: bool,
+    #[serde(default = "default_hero_trailer_delay_secs")]
+    pub hero_trailer_delay_secs: u64,
+    #[serde(default = "default_true")]
+    pub show_hero: bool,
+    /// Plays the title's trailer when no stream can be found for it.
+    #[serde(default = "default_true")]
+    pub trailer_fallback: bool,
+    #[serde(default)]
+    pub card_click_action: CardClickAction,
+    #[serde(default)]
+    pub card_expand_style: CardExpandStyle,
+    /// Hides stills and overviews of episodes without playback progress until clicked.
+    #[serde(default)]
+    pub hide_episode_spoilers: bool,
+    #[serde(default)]
+    pub top_picks_source: TopPicksSource,
+    #[serde(default)]
+    pub user_agent: String,
+    #[serde(default)]
+    pub proxy_url: String,
+    #[serde(default = "default_rating_filter_step")]
+    pub rating_filter_step: f32,
+    #[serde(default = "default_section_item_cap")]
+    pub section_item_cap: usize,
+    #[serde(default = "default_true")]
+    pub image_disk_cache: bool,
+    /// Decoded images kept in memory; older ones are reloaded from disk.
+    #[serde(default = "default_image_cache_entries")]
+    pub image_cache_entries: usize,
+    /// Size the image disk cache is trimmed back to on startup.
+    #[serde(default = "default_image_disk_budget_mb")]
+    pub image_disk_budget_mb: u64,
+    /// Keeps TMDb responses on disk so a restart within the cache lifetime
+    /// doesn't refetch them.
+    #[serde(default = "default_true")]
+    pub api_disk_cache: bool,
+    /// How long TMDb responses are reused before being fetched again.
+    #[serde(default = "default_api_cache_ttl_secs")]
+    pub api_cache_ttl_secs: u64,
+    /// Most entries each TMDb cache keeps; the oldest are dropped first.
+    #[serde(default = "default_api_cache_max_entries")]
+    pub api_cache_max_entries: usize,
+    /// Name of the audio output device; empty uses the system default.
+    #[serde(default)]
+    pub audio_device: String,
+    /// Volume boost for quiet titles, from 1.0 (off) to 2.0. Peaks are
+    /// limited so the boost never clips.
+    #[serde(default = "default_audio_gain")]
+    pub audio_gain: f32,
+    /// Caps hero and backdrop images at 1280px wide instead of the original size.
+    #[serde(default)]
+    pub low_bandwidth_images: bool,
+    /// For metered connections: turns off trailer autoplay and preloading and
+    /// uses the smallest images and a lower decode resolution, whatever the
+    /// individual settings say.
+    #[serde(default)]
+    pub data_saver: bool,
+    /// Decoded frames queued ahead of display. Deeper buffers ride out
+    /// decoder hitches, but each frame of 1080p video is about 8 MB and
+    /// adds a frame of latency to seeks and pause.
+    #[serde(default = "default_frame_buffer_depth")]
+    pub frame_buffer_depth: usize,
+    /// Last volume set in the movie player, also used for trailers.
+    #[serde(default = "default_player_volume")]
+    pub player_volume: f64,
+    #[serde(default)]
+    pub player_muted: bool,
+    #[serde(default)]
+    pub search_preferences: SearchPreferences,
+    #[serde(default)]
+    pub search_grid_density: GridDensity,
+    /// Quiet time after the last keystroke before a search is sent.
+    #[serde(default = "default_search_debounce_ms")]
+    pub search_debounce_ms: u64,
+    /// ISO 3166-1 country used for certifications and release dates.
+    #[serde(default = "default_region")]
+    pub region: String,
+    #[serde(default = "default_watch_region")]
+    pub watch_region: String,
+    #[serde(default)]
+    pub streaming_providers: Vec<WatchProvider>,
+    #[serde(default)]
+    pub last_seen_version: String,
+    /// Stream provider to use instead of the automatic choice, per title.
+    #[serde(default)]
+    pub stream_provider_overrides: HashMap<MediaId, String>,
+    /// Provider names to try first when resolving a stream, e.g.
+    /// `["megakino"]`. Providers left out are tried after these.
+    #[serde(default)]
+    pub stream_provider_order: Vec<String>,
+    /// Tallest stream variant to pick, e.g. 720; `None` picks the best.
+    #[serde(default)]
+    pub preferred_stream_quality: Option<u32>,
+}
+
+fn default_hero_rotation_secs() -> u64 {
+    20
+}
+
+fn default_hero_trailer_delay_secs() -> u64 {
+    2
+}
+
+fn default_rating_filter_step() -> f32 {
+    0.5
+}
+
+const MAX_LISTED_PROVIDERS: usize = 40;
+
+fn default_audio_gain() -> f32 {
+    1.0
+}
+
+fn default_frame_buffer_depth() -> usize {
+    4
+}
+
+fn default_player_volume() -> f64 {
+    1.0
+}
+
+fn default_search_debounce_ms() -> u64 {
+    300
+}
+
+fn default_section_item_cap() -> usize {
+    40
+}
+
+fn default_image_cache_entries() -> usize {
+    DEFAULT_IMAGE_CACHE_ENTRIES
+}
+
+fn default_image_disk_budget_mb() -> u64 {
+    DEFAULT_IMAGE_DISK_BUDGET_MB
+}
+
+fn default_api_cache_ttl_secs() -> u64 {
+    300
+}
+
+fn default_api_cache_max_entries() -> usize {
+    200
+}
+
+fn default_region() -> String {
+    String::from("US")
+}
+
+fn default_watch_region() -> String {
+    String::from("US")
+}
+
+fn normalize_region(input: &str, fallback: fn() -> String) -> String {
+    let region = input.trim();
+    if region.is_empty() {
+        fallback()
+    } else {
+        region.to_uppercase()
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+pub fn default_user_agent() -> String {
+    format!("Movix/{}", env!("CARGO_PKG_VERSION"))
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            language: String::new(),
+            omdb_api_key: String::new(),
+            hero_rotation_secs: default_hero_rotation_secs(),
+            hero_start_muted: true,
+            hero_trailer_delay_secs: default_hero_trailer_delay_secs(),
+            show_hero: true,
+            trailer_fallback: true,
+            card_click_action: CardClickAction::default(),
+            card_expand_style: CardExpandStyle::default(),
+            hide_episode_spoilers: false,
+            top_picks_source: TopPicksSource::default(),
+            user_agent: String::new(),
+            proxy_url: String::new(),
+            rating_filter_step: default_rating_filter_step(),
+            section_item_cap: default_section_item_cap(),
+            image_disk_cache: true,
+            image_cache_entries: default_image_cache_entries(),
+            image_disk_budget_mb: default_image_disk_budget_mb(),
+            api_disk_cache: true,
+            api_cache_ttl_secs: default_api_cache_ttl_secs(),
+            api_cache_max_entries: default_api_cache_max_entries(),
+            audio_device: String::new(),
+            audio_gain: default_audio_gain(),
+            low_bandwidth_images: false,
+            data_saver: false,
+            frame_buffer_depth: default_frame_buffer_depth(),
+            player_volume: default_player_volume(),
+            player_muted: false,
+            search_preferences: SearchPreferences::default(),
+            search_grid_density: GridDensity::default(),
+            search_debounce_ms: default_search_debounce_ms(),
+            region: default_region(),
+            watch_region: default_watch_region(),
+            streaming_providers: Vec::new(),
+            last_seen_version: crate::whats_new::APP_VERSION.to_string(),
+            stream_provider_overrides: HashMap::new(),
+            stream_provider_order: Vec::new(),
+            preferred_stream_quality: None,
+        }
+    }
 }
 
 impl AppSettings {
@@ -49,19 +277,128 @@ impl AppSettings {
     pub fn is_valid(&self) -> bool {
         !self.api_key.trim().is_empty()
     }
+
+    pub fn search_debounce(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.search_debounce_ms)
+    }
+
+    /// Whether a search typed `elapsed` ago has settled long enough to run.
+    pub fn search_debounce_elapsed(&self, elapsed: std::time::Duration) -> bool {
+        elapsed >= self.search_debounce()
+    }
+
+    pub fn frame_buffer_depth(&self) -> usize {
+        self.frame_buffer_depth.clamp(4, 16)
+    }
+
+    pub fn audio_config(&self) -> AudioConfig {
+        AudioConfig {
+            device: (!self.audio_device.is_empty()).then(|| self.audio_device.clone()),
+            gain: self.audio_gain.clamp(1.0, 2.0),
+        }
+    }
+
+    pub fn autoplay_trailers(&self) -> bool {
+        !self.data_saver
+    }
+
+    pub fn preload_trailers(&self) -> bool {
+        !self.data_saver
+    }
+
+    pub fn low_bandwidth_images(&self) -> bool {
+        self.data_saver || self.low_bandwidth_images
+    }
+
+    pub fn api_cache_ttl(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.api_cache_ttl_secs)
+    }
+
+    pub fn image_disk_budget_bytes(&self) -> u64 {
+        self.image_disk_budget_mb.saturating_mul(1024 * 1024)
+    }
+
+    /// Frame size the movie player scales decoded video to.
+    pub fn movie_decode_size(&self) -> (u32, u32) {
+        if self.data_saver {
+            (1280, 720)
+        } else {
+            (1920, 1080)
+        }
+    }
+
+    /// Decode size for a stream variant of the given height, so a low
+    /// quality source isn't scaled up only to be shown smaller again.
+    pub fn movie_decode_size_for(&self, quality: Option<u32>) -> (u32, u32) {
+        let (width, height) = self.movie_decode_size();
+        match quality {
+            Some(quality) if quality < height => ((quality * 16 / 9 + 1) & !1, quality),
+            _ => (width, height),
+        }
+    }
+
+    /// Frame size the detail popup's trailer player scales decoded video to.
+    pub fn detail_trailer_size(&self) -> (u32, u32) {
+        if self.data_saver {
+            CARD_TRAILER_SIZE
+        } else {
+            DETAIL_TRAILER_SIZE
+        }
+    }
+
+    pub fn proxy(&self) -> Option<&str> {
+        let proxy_url = self.proxy_url.trim();
+        (!proxy_url.is_empty()).then_some(proxy_url)
+    }
+
+    pub fn effective_user_agent(&self) -> String {
+        let user_agent = self.user_agent.trim();
+        if user_agent.is_empty() {
+            default_user_agent()
+        } else {
+            user_agent.to_string()
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum SetupMessage {
     ApiKeyChanged(String),
     LanguageChanged(String),
+    OmdbApiKeyChanged(String),
+    UserAgentChanged(String),
+    ProxyUrlChanged(String),
+    RegionChanged(String),
+    WatchRegionChanged(String),
+    AudioDeviceChanged(String),
+    ToggleDataSaver,
+    LoadProviders,
+    ProvidersLoaded(Result<Vec<WatchProvider>, String>),
+    ToggleProvider(WatchProvider),
+    ToggleAdvanced,
     Submit,
+    Cancel,
 }
 
 pub struct SetupPage {
     pub api_key: String,
     pub language: String,
+    pub omdb_api_key: String,
+    pub user_agent: String,
+    pub proxy_url: String,
+    pub region: String,
+    pub watch_region: String,
+    pub audio_device: String,
+    pub audio_devices: Vec<String>,
+    pub data_saver: bool,
+    pub streaming_providers: Vec<WatchProvider>,
+    pub available_providers: Vec<WatchProvider>,
+    pub providers_loading: bool,
+    pub advanced_expanded: bool,
+    pub can_cancel: bool,
     pub error: Option<String>,
+    /// Filled in by the caller when editing settings of a running session.
+    pub cache_stats: Option<CacheStats>,
 }
 
 impl Default for SetupPage {
@@ -69,12 +406,54 @@ impl Default for SetupPage {
         Self {
             api_key: String::new(),
             language: String::from("en-US"),
+            omdb_api_key: String::new(),
+            user_agent: String::new(),
+            proxy_url: String::new(),
+            region: default_region(),
+            watch_region: default_watch_region(),
+            audio_device: String::new(),
+            audio_devices: crate::audio::output_device_names(),
+            data_saver: false,
+            streaming_providers: Vec::new(),
+            available_providers: Vec::new(),
+            providers_loading: false,
+            advanced_expanded: false,
+            can_cancel: false,
             error: None,
+            cache_stats: None,
         }
     }
 }
 
 impl SetupPage {
+    /// Opens the form pre-filled with the current settings, for editing them
+    /// after the initial setup.
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            api_key: settings.api_key.clone(),
+            language: settings.language.clone(),
+            omdb_api_key: settings.omdb_api_key.clone(),
+            user_agent: settings.user_agent.clone(),
+            proxy_url: settings.proxy_url.clone(),
+            region: settings.region.clone(),
+            watch_region: settings.watch_region.clone(),
+            audio_device: settings.audio_device.clone(),
+            audio_devices: crate::audio::output_device_names(),
+            data_saver: settings.data_saver,
+            streaming_providers: settings.streaming_providers.clone(),
+            available_providers: Vec::new(),
+            providers_loading: false,
+            advanced_expanded: false,
+            can_cancel: true,
+            error: None,
+            cache_stats: None,
+        }
+    }
+
+    pub fn provider_region(&self) -> String {
+        normalize_region(&self.watch_region, default_watch_region)
+    }
+
     pub fn update(&mut self, message: SetupMessage) -> Option<AppSettings> {
         match message {
             SetupMessage::ApiKeyChanged(key) => {
@@ -86,11 +465,79 @@ impl SetupPage {
                 self.language = lang;
                 None
             }
+            SetupMessage::OmdbApiKeyChanged(key) => {
+                self.omdb_api_key = key;
+                None
+            }
+            SetupMessage::UserAgentChanged(user_agent) => {
+                self.user_agent = user_agent;
+                None
+            }
+            SetupMessage::ProxyUrlChanged(proxy_url) => {
+                self.proxy_url = proxy_url;
+                self.error = None;
+                None
+            }
+            SetupMessage::RegionChanged(region) => {
+                self.region = region;
+                None
+            }
+            SetupMessage::WatchRegionChanged(region) => {
+                self.watch_region = region;
+                None
+            }
+            SetupMessage::AudioDeviceChanged(device) => {
+                self.audio_device = if device == SYSTEM_DEFAULT_DEVICE {
+                    String::new()
+                } else {
+                    device
+                };
+                None
+            }
+            SetupMessage::ToggleDataSaver => {
+                self.data_saver = !self.data_saver;
+                None
+            }
+            SetupMessage::LoadProviders => None,
+            SetupMessage::ProvidersLoaded(result) => {
+                self.providers_loading = false;
+                match result {
+                    Ok(providers) => self.available_providers = providers,
+                    Err(e) => self.error = Some(format!("Could not load providers: {}", e)),
+                }
+                None
+            }
+            SetupMessage::ToggleProvider(provider) => {
+                match self
+                    .streaming_providers
+                    .iter()
+                    .position(|p| p.id == provider.id)
+                {
+                    Some(index) => {
+                        self.streaming_providers.remove(index);
+                    }
+                    None => self.streaming_providers.push(provider),
+                }
+                None
+            }
+            SetupMessage::ToggleAdvanced => {
+                self.advanced_expanded = !self.advanced_expanded;
+                None
+            }
+            SetupMessage::Cancel => None,
             SetupMessage::Submit => {
                 if self.api_key.trim().is_empty() {
                     self.error = Some(String::from("API key is required"));
                     return None;
                 }
+                let proxy_url = self.proxy_url.trim();
+                if !proxy_url.is_empty() {
+                    if let Err(e) = crate::proxy::validate(proxy_url) {
+                        self.error = Some(e);
+                        self.advanced_expanded = true;
+                        return None;
+                    }
+                }
                 let settings = AppSettings {
                     api_key: self.api_key.trim().to_string(),
                     language: if self.language.trim().is_empty() {
@@ -98,6 +545,15 @@ impl SetupPage {
                     } else {
                         self.language.trim().to_string()
                     },
+                    omdb_api_key: self.omdb_api_key.trim().to_string(),
+                    user_agent: self.user_agent.trim().to_string(),
+                    proxy_url: proxy_url.to_string(),
+                    region: normalize_region(&self.region, default_region),
+                    watch_region: self.provider_region(),
+                    audio_device: self.audio_device.clone(),
+                    data_saver: self.data_saver,
+                    streaming_providers: self.streaming_providers.clone(),
+                    ..AppSettings::load().unwrap_or_default()
                 };
                 if let Err(e) = settings.save() {
                     self.error = Some(format!("Failed to save: {}", e));
@@ -108,6 +564,27 @@ impl SetupPage {
         }
     }
 
+    fn view_provider_chip(&self, provider: &WatchProvider) -> Element<'_, SetupMessage> {
+        let selected = self.streaming_providers.iter().any(|p| p.id == provider.id);
+        button(text(provider.name.clone()).size(13).color(TEXT_WHITE))
+            .padding([6, 12])
+            .style(move |_theme, status| {
+                let bg = match status {
+                    _ if selected => NETFLIX_RED,
+                    button::Status::Hovered => iced::Color::from_rgb(0.25, 0.25, 0.25),
+                    _ => iced::Color::from_rgb(0.15, 0.15, 0.15),
+                };
+                button::Style {
+                    background: Some(iced::Background::Color(bg)),
+                    text_color: TEXT_WHITE,
+                    border: iced::Border::default().rounded(12),
+                    ..Default::default()
+                }
+            })
+            .on_press(SetupMessage::ToggleProvider(provider.clone()))
+            .into()
+    }
+
     pub fn view(&self) -> Element<'_, SetupMessage> {
         let logo = text("MOVIX")
             .size(48)
@@ -141,7 +618,23 @@ impl SetupPage {
             .size(14)
             .width(Length::Fill);
 
-        let submit_button = button(text("Get Started").size(16).color(TEXT_WHITE))
+        let omdb_label = text("OMDb API Key (optional)").size(14).color(TEXT_WHITE);
+        let omdb_hint = text("Shows IMDb ratings. Get a key at omdbapi.com/apikey.aspx")
+            .size(12)
+            .color(TEXT_GRAY);
+        let omdb_input = text_input("Enter your OMDb API key...", &self.omdb_api_key)
+            .on_input(SetupMessage::OmdbApiKeyChanged)
+            .on_submit(SetupMessage::Submit)
+            .padding(12)
+            .size(14)
+            .width(Length::Fill);
+
+        let submit_label = if self.can_cancel {
+            "Save"
+        } else {
+            "Get Started"
+        };
+        let submit_button = button(text(submit_label).size(16).color(TEXT_WHITE))
             .padding([12, 32])
             .style(|_theme, status| {
                 let bg = match status {
@@ -157,6 +650,247 @@ impl SetupPage {
             })
             .on_press(SetupMessage::Submit);
 
+        let advanced_toggle = button(
+            text(if self.advanced_expanded {
+                "Hide advanced settings"
+            } else {
+                "Show advanced settings"
+            })
+            .size(14)
+            .color(TEXT_GRAY),
+        )
+        .padding(0)
+        .style(|_theme, _status| button::Style {
+            background: None,
+            text_color: TEXT_GRAY,
+            ..Default::default()
+        })
+        .on_press(SetupMessage::ToggleAdvanced);
+
+        let user_agent_label = text("User-Agent").size(14).color(TEXT_WHITE);
+        let user_agent_hint =
+            text("Sent with TMDB and image requests. Leave empty for the default.")
+                .size(12)
+                .color(TEXT_GRAY);
+        let default_user_agent = default_user_agent();
+        let user_agent_input = text_input(&default_user_agent, &self.user_agent)
+            .on_input(SetupMessage::UserAgentChanged)
+            .on_submit(SetupMessage::Submit)
+            .padding(12)
+            .size(14)
+            .width(Length::Fill);
+
+        let proxy_label = text("Proxy").size(14).color(TEXT_WHITE);
+        let proxy_hint = text("Routes all traffic, e.g. http://host:8080 or socks5://host:1080")
+            .size(12)
+            .color(TEXT_GRAY);
+        let proxy_input = text_input("No proxy", &self.proxy_url)
+            .on_input(SetupMessage::ProxyUrlChanged)
+            .on_submit(SetupMessage::Submit)
+            .padding(12)
+            .size(14)
+            .width(Length::Fill);
+
+        let region_label = text("Region").size(14).color(TEXT_WHITE);
+        let region_hint = text("Country used for age ratings and release dates, e.g. GB or DE")
+            .size(12)
+            .color(TEXT_GRAY);
+        let content_region_input = text_input("US", &self.region)
+            .on_input(SetupMessage::RegionChanged)
+            .on_submit(SetupMessage::Submit)
+            .padding(12)
+            .size(14)
+            .width(Length::Fixed(80.0));
+
+        let audio_label = text("Audio Output").size(14).color(TEXT_WHITE);
+        let audio_hint = text("Falls back to the system default if the device is unavailable")
+            .size(12)
+            .color(TEXT_GRAY);
+        let mut audio_options = vec![SYSTEM_DEFAULT_DEVICE.to_string()];
+        audio_options.extend(self.audio_devices.iter().cloned());
+        if !self.audio_device.is_empty() && !self.audio_devices.contains(&self.audio_device) {
+            audio_options.push(self.audio_device.clone());
+        }
+        let selected_audio = if self.audio_device.is_empty() {
+            SYSTEM_DEFAULT_DEVICE.to_string()
+        } else {
+            self.audio_device.clone()
+        };
+        let audio_picker = pick_list(
+            audio_options,
+            Some(selected_audio),
+            SetupMessage::AudioDeviceChanged,
+        )
+        .padding(12)
+        .text_size(14)
+        .width(Length::Fill);
+
+        let data_saver_label = text("Data Saver").size(14).color(TEXT_WHITE);
+        let data_saver_hint = text(if self.data_saver {
+            "On: trailers don't autoplay or preload, and image and video quality are \
+             reduced regardless of the other settings"
+        } else {
+            "Cuts data use on metered connections"
+        })
+        .size(12)
+        .color(TEXT_GRAY);
+        let data_saver_enabled = self.data_saver;
+        let data_saver_toggle = button(
+            text(if data_saver_enabled { "On" } else { "Off" })
+                .size(14)
+                .color(TEXT_WHITE),
+        )
+        .padding([8, 20])
+        .style(move |_theme, status| {
+            let bg = match status {
+                _ if data_saver_enabled => NETFLIX_RED,
+                button::Status::Hovered => iced::Color::from_rgb(0.25, 0.25, 0.25),
+                _ => iced::Color::from_rgb(0.15, 0.15, 0.15),
+            };
+            button::Style {
+                background: Some(iced::Background::Color(bg)),
+                text_color: TEXT_WHITE,
+                border: iced::Border::default().rounded(4),
+                ..Default::default()
+            }
+        })
+        .on_press(SetupMessage::ToggleDataSaver);
+
+        let providers_label = text("Streaming Services").size(14).color(TEXT_WHITE);
+        let providers_hint = text("Adds a home row for each selected service in your region")
+            .size(12)
+            .color(TEXT_GRAY);
+        let region_input = text_input("US", &self.watch_region)
+            .on_input(SetupMessage::WatchRegionChanged)
+            .on_submit(SetupMessage::LoadProviders)
+            .padding(12)
+            .size(14)
+            .width(Length::Fixed(80.0));
+        let load_providers_button = button(
+            text(if self.providers_loading {
+                "Loading..."
+            } else {
+                "Load services"
+            })
+            .size(14)
+            .color(TEXT_WHITE),
+        )
+        .padding([12, 16])
+        .style(|_theme, status| {
+            let bg = match status {
+                button::Status::Hovered => iced::Color::from_rgb(0.25, 0.25, 0.25),
+                _ => iced::Color::from_rgb(0.15, 0.15, 0.15),
+            };
+            button::Style {
+                background: Some(iced::Background::Color(bg)),
+                text_color: TEXT_WHITE,
+                border: iced::Border::default().rounded(4),
+                ..Default::default()
+            }
+        })
+        .on_press_maybe((!self.providers_loading).then_some(SetupMessage::LoadProviders));
+        let region_row = row![region_input, load_providers_button]
+            .spacing(8)
+            .align_y(Alignment::Center);
+        let provider_chips = Row::with_children(
+            self.streaming_providers
+                .iter()
+                .chain(
+                    self.available_providers
+                        .iter()
+                        .filter(|p| !self.streaming_providers.iter().any(|s| s.id == p.id))
+                        .take(MAX_LISTED_PROVIDERS),
+                )
+                .map(|provider| self.view_provider_chip(provider)),
+        )
+        .spacing(8)
+        .wrap();
+
+        let cache_section = match self.cache_stats {
+            Some(stats) => column![
+                Space::new().height(16),
+                text("TMDB Cache").size(14).color(TEXT_WHITE),
+                Space::new().height(4),
+                text(format!(
+                    "{} entries: {} lists, {} titles, {} detail pages, {} seasons",
+                    stats.total(),
+                    stats.lists,
+                    stats.details,
+                    stats.popups,
+                    stats.seasons
+                ))
+                .size(12)
+                .color(TEXT_GRAY),
+            ],
+            None => column![],
+        };
+
+        let advanced_section = if self.advanced_expanded {
+            column![
+                Space::new().height(16),
+                user_agent_label,
+                Space::new().height(4),
+                user_agent_hint,
+                Space::new().height(4),
+                user_agent_input,
+                Space::new().height(16),
+                proxy_label,
+                Space::new().height(4),
+                proxy_hint,
+                Space::new().height(4),
+                proxy_input,
+                Space::new().height(16),
+                region_label,
+                Space::new().height(4),
+                region_hint,
+                Space::new().height(4),
+                content_region_input,
+                Space::new().height(16),
+                audio_label,
+                Space::new().height(4),
+                audio_hint,
+                Space::new().height(4),
+                audio_picker,
+                Space::new().height(16),
+                data_saver_label,
+                Space::new().height(4),
+                data_saver_hint,
+                Space::new().height(4),
+                data_saver_toggle,
+                Space::new().height(16),
+                providers_label,
+                Space::new().height(4),
+                providers_hint,
+                Space::new().height(4),
+                region_row,
+                Space::new().height(8),
+                provider_chips,
+                cache_section,
+            ]
+        } else {
+            column![]
+        };
+
+        let mut actions = row![submit_button].spacing(12).width(Length::Fill);
+        if self.can_cancel {
+            let cancel_button = button(text("Cancel").size(16).color(TEXT_WHITE))
+                .padding([12, 32])
+                .style(|_theme, status| {
+                    let bg = match status {
+                        button::Status::Hovered => iced::Color::from_rgb(0.25, 0.25, 0.25),
+                        _ => iced::Color::from_rgb(0.15, 0.15, 0.15),
+                    };
+                    button::Style {
+                        background: Some(iced::Background::Color(bg)),
+                        text_color: TEXT_WHITE,
+                        border: iced::Border::default().rounded(4),
+                        ..Default::default()
+                    }
+                })
+                .on_press(SetupMessage::Cancel);
+            actions = actions.push(cancel_button);
+        }
+
         let error_text = if let Some(ref err) = self.error {
             text(err).size(14).color(NETFLIX_RED)
         } else {
@@ -186,9 +920,18 @@ impl SetupPage {
             small_spacer(),
             lang_input,
             spacer(),
+            omdb_label,
+            small_spacer(),
+            omdb_hint,
+            small_spacer(),
+            omdb_input,
+            spacer(),
+            advanced_toggle,
+            advanced_section,
+            spacer(),
             error_text,
             small_spacer(),
-            row![submit_button].width(Length::Fill),
+            actions,
         ]
         .width(Length::Fixed(400.0))
         .align_x(Alignment::Start);
@@ -205,3 +948,28 @@ impl SetupPage {
             .into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn search_runs_once_the_debounce_has_fully_elapsed() {
+        let settings = AppSettings {
+            search_debounce_ms: 300,
+            ..Default::default()
+        };
+        assert!(!settings.search_debounce_elapsed(Duration::from_millis(299)));
+        assert!(settings.search_debounce_elapsed(Duration::from_millis(300)));
+        assert!(settings.search_debounce_elapsed(Duration::from_millis(301)));
+    }
+
+    #[test]
+    fn search_debounce_defaults_to_300ms() {
+        assert_eq!(
+            AppSettings::default().search_debounce(),
+            Duration::from_millis(300)
+        );
+    }
+}
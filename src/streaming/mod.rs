@@ -3,6 +3,8 @@ pub mod resolvers;
 
 use async_trait::async_trait;
 
+use crate::settings::AppSettings;
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub enum StreamError {
@@ -10,6 +12,47 @@ pub enum StreamError {
     Parse(String),
     NotFound(String),
     Config(String),
+    /// An error attributed to the provider or resolver that produced it
+    Stage {
+        stage: StreamStage,
+        error: Box<StreamError>,
+    },
+    /// One error per provider tried, in order, when none of them worked
+    AllFailed(Vec<StreamError>),
+}
+
+/// The step of the pipeline an error came from, by display name
+#[derive(Debug, Clone)]
+pub enum StreamStage {
+    Provider(String),
+    Resolver(String),
+}
+
+impl StreamError {
+    fn in_provider(self, provider: &dyn StreamProvider) -> Self {
+        StreamError::Stage {
+            stage: StreamStage::Provider(provider.display_name().to_string()),
+            error: Box::new(self),
+        }
+    }
+
+    fn in_resolver(self, resolver: &dyn StreamResolver) -> Self {
+        StreamError::Stage {
+            stage: StreamStage::Resolver(resolver.display_name().to_string()),
+            error: Box::new(self),
+        }
+    }
+
+    /// Network failures are usually transient, while a missing title or an
+    /// unparseable page will fail the same way again
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            StreamError::Network(_) => true,
+            StreamError::Stage { error, .. } => error.is_retryable(),
+            StreamError::AllFailed(errors) => errors.iter().any(StreamError::is_retryable),
+            _ => false,
+        }
+    }
 }
 
 impl std::fmt::Display for StreamError {
@@ -19,19 +62,70 @@ impl std::fmt::Display for StreamError {
             StreamError::Parse(msg) => write!(f, "Parse error: {}", msg),
             StreamError::NotFound(msg) => write!(f, "Not found: {}", msg),
             StreamError::Config(msg) => write!(f, "Config error: {}", msg),
+            StreamError::Stage { stage, error } => {
+                let label = match stage {
+                    StreamStage::Provider(name) => name.clone(),
+                    StreamStage::Resolver(name) => format!("{} resolver", name),
+                };
+                match (stage, error.as_ref()) {
+                    (StreamStage::Provider(_), StreamError::NotFound(_)) => {
+                        write!(f, "{} couldn't find this title", label)
+                    }
+                    _ => write!(f, "{} failed: {}", label, error),
+                }
+            }
+            StreamError::AllFailed(errors) => {
+                let reasons: Vec<String> = errors.iter().map(ToString::to_string).collect();
+                write!(f, "All providers failed: {}", reasons.join("; "))
+            }
         }
     }
 }
 
 impl std::error::Error for StreamError {}
 
+/// What to find a stream for: a movie, or one episode of a series
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamQuery {
+    pub title: String,
+    pub season: Option<u32>,
+    pub episode: Option<u32>,
+}
+
+impl StreamQuery {
+    pub fn movie(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            season: None,
+            episode: None,
+        }
+    }
+
+    pub fn episode(title: &str, season: u32, episode: u32) -> Self {
+        Self {
+            title: title.to_string(),
+            season: Some(season),
+            episode: Some(episode),
+        }
+    }
+
+    /// Season and episode, when both are set
+    pub fn episode_number(&self) -> Option<(u32, u32)> {
+        self.season.zip(self.episode)
+    }
+}
+
 #[async_trait]
 pub trait StreamProvider: Send + Sync {
-    #[allow(dead_code)]
     fn name(&self) -> &str;
 
-    /// Get the stream page URL for a given title
-    async fn get_stream_page_url(&self, title: &str) -> Result<String, StreamError>;
+    /// Name shown to the user in error messages
+    fn display_name(&self) -> &str {
+        self.name()
+    }
+
+    /// Get the stream page URL for a movie or a single episode
+    async fn get_stream_page_url(&self, query: &StreamQuery) -> Result<String, StreamError>;
 }
 
 #[async_trait]
@@ -39,11 +133,55 @@ pub trait StreamResolver: Send + Sync {
     #[allow(dead_code)]
     fn name(&self) -> &str;
 
+    /// Name shown to the user in error messages
+    fn display_name(&self) -> &str {
+        self.name()
+    }
+
     /// Check if this resolver can handle the given URL
     fn can_handle(&self, url: &str) -> bool;
 
-    /// Resolve a stream page URL to a direct playable URL
-    async fn resolve(&self, url: &str) -> Result<String, StreamError>;
+    /// Resolve a stream page URL to its playable variants, best first
+    async fn resolve(&self, url: &str) -> Result<Vec<StreamVariant>, StreamError>;
+}
+
+/// One playable rendition of a stream
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamVariant {
+    /// Vertical resolution, e.g. 720; `None` when the source doesn't say
+    pub quality: Option<u32>,
+    pub url: String,
+}
+
+impl StreamVariant {
+    pub fn label(&self) -> String {
+        match self.quality {
+            Some(height) => format!("{}p", height),
+            None => String::from("Auto"),
+        }
+    }
+}
+
+/// The variants of a stream together with the provider it was found through
+#[derive(Debug, Clone)]
+pub struct ResolvedStream {
+    pub provider: String,
+    pub variants: Vec<StreamVariant>,
+}
+
+impl ResolvedStream {
+    /// The best variant no taller than `max_quality`, or the smallest one
+    /// when all are taller.
+    pub fn select(&self, max_quality: Option<u32>) -> Option<&StreamVariant> {
+        let fits = |variant: &&StreamVariant| match (variant.quality, max_quality) {
+            (Some(quality), Some(max)) => quality <= max,
+            _ => true,
+        };
+        self.variants
+            .iter()
+            .find(fits)
+            .or_else(|| self.variants.last())
+    }
 }
 
 /// Combined service that uses providers and resolvers together
@@ -60,7 +198,24 @@ impl StreamingService {
         }
     }
 
+    /// Providers named in `stream_provider_order` are tried first, in that
+    /// order; the rest follow in their default order.
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        let proxy_url = settings.proxy();
+        let mut remaining = providers::all(proxy_url);
+        let mut service = Self::new();
+        for name in &settings.stream_provider_order {
+            if let Some(index) = remaining.iter().position(|p| p.name() == name) {
+                service.providers.push(remaining.remove(index));
+            }
+        }
+        service.providers.extend(remaining);
+        service.add_resolver(resolvers::voe::VoeResolver::new(proxy_url));
+        service
+    }
+
     /// Add a stream provider
+    #[allow(dead_code)]
     pub fn add_provider<P: StreamProvider + 'static>(&mut self, provider: P) {
         self.providers.push(Box::new(provider));
     }
@@ -70,37 +225,68 @@ impl StreamingService {
         self.resolvers.push(Box::new(resolver));
     }
 
-    /// Get a direct stream URL for a title using the first available provider
-    pub async fn get_stream_url(&self, title: &str) -> Result<String, StreamError> {
-        // Try each provider until one succeeds
-        let mut last_error = StreamError::NotFound("No providers available".to_string());
+    /// Get a direct stream URL for a title from the first provider, in order,
+    /// that is not in `excluded` and works. When none does, the error lists
+    /// why each one failed.
+    pub async fn get_stream_url(
+        &self,
+        query: &StreamQuery,
+        excluded: &[String],
+    ) -> Result<ResolvedStream, StreamError> {
+        let mut errors = Vec::new();
 
         for provider in &self.providers {
-            match provider.get_stream_page_url(title).await {
-                Ok(page_url) => {
-                    // Find a resolver that can handle this URL
-                    for resolver in &self.resolvers {
-                        if resolver.can_handle(&page_url) {
-                            match resolver.resolve(&page_url).await {
-                                Ok(stream_url) => return Ok(stream_url),
-                                Err(e) => last_error = e,
-                            }
-                        }
-                    }
+            if excluded.iter().any(|name| name == provider.name()) {
+                continue;
+            }
+            let page_url = match provider.get_stream_page_url(query).await {
+                Ok(page_url) => page_url,
+                Err(e) => {
+                    errors.push(e.in_provider(provider.as_ref()));
+                    continue;
+                }
+            };
+            match self.resolve_page(&page_url).await {
+                Ok(variants) => {
+                    return Ok(ResolvedStream {
+                        provider: provider.name().to_string(),
+                        variants,
+                    })
                 }
-                Err(e) => last_error = e,
+                Err(e) => errors.push(e),
             }
         }
 
+        match errors.len() {
+            0 => Err(StreamError::NotFound("No providers available".to_string())),
+            1 => Err(errors.remove(0)),
+            _ => Err(StreamError::AllFailed(errors)),
+        }
+    }
+
+    /// Tries every resolver that can handle `page_url`
+    async fn resolve_page(&self, page_url: &str) -> Result<Vec<StreamVariant>, StreamError> {
+        let mut last_error = StreamError::NotFound("No resolver found for URL".to_string());
+        for resolver in &self.resolvers {
+            if resolver.can_handle(page_url) {
+                match resolver.resolve(page_url).await {
+                    Ok(variants) if !variants.is_empty() => return Ok(variants),
+                    Ok(_) => {
+                        last_error = StreamError::NotFound("No playable variants".to_string())
+                            .in_resolver(resolver.as_ref())
+                    }
+                    Err(e) => last_error = e.in_resolver(resolver.as_ref()),
+                }
+            }
+        }
         Err(last_error)
     }
 
-    #[allow(dead_code)]
     pub async fn get_stream_url_with_provider(
         &self,
-        title: &str,
+        query: &StreamQuery,
         provider_name: &str,
-    ) -> Result<String, StreamError> {
+    ) -> Result<ResolvedStream, StreamError> {
         let provider = self
             .providers
             .iter()
@@ -109,20 +295,19 @@ impl StreamingService {
                 StreamError::NotFound(format!("Provider '{}' not found", provider_name))
             })?;
 
-        let page_url = provider.get_stream_page_url(title).await?;
+        let page_url = provider
+            .get_stream_page_url(query)
+            .await
+            .map_err(|e| e.in_provider(provider.as_ref()))?;
 
-        for resolver in &self.resolvers {
-            if resolver.can_handle(&page_url) {
-                return resolver.resolve(&page_url).await;
-            }
-        }
-
-        Err(StreamError::NotFound(
-            "No resolver found for URL".to_string(),
-        ))
+        self.resolve_page(&page_url)
+            .await
+            .map(|variants| ResolvedStream {
+                provider: provider_name.to_string(),
+                variants,
+            })
     }
 
-    #[allow(dead_code)]
     pub fn provider_names(&self) -> Vec<&str> {
         self.providers.iter().map(|p| p.name()).collect()
     }
@@ -139,9 +324,18 @@ impl Default for StreamingService {
     }
 }
 
-pub fn create_default_service() -> StreamingService {
+/// Names of the providers in the default service, for picking one per title.
+pub fn default_provider_names() -> Vec<String> {
+    create_default_service(None)
+        .provider_names()
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+pub fn create_default_service(proxy_url: Option<&str>) -> StreamingService {
     let mut service = StreamingService::new();
-    service.add_provider(providers::FilmpalastToProvider::new());
-    service.add_resolver(resolvers::voe::VoeResolver::new());
+    service.providers = providers::all(proxy_url);
+    service.add_resolver(resolvers::voe::VoeResolver::new(proxy_url));
     service
 }
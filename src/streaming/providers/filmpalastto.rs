@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 
-use crate::streaming::{StreamError, StreamProvider};
+use crate::proxy;
+use crate::streaming::{StreamError, StreamProvider, StreamQuery};
 
 const FILMPALAST_DOMAIN: &str = "https://filmpalast.to/stream";
 
@@ -9,8 +10,8 @@ pub struct FilmpalastToProvider {
 }
 
 impl FilmpalastToProvider {
-    pub fn new() -> Self {
-        let client = reqwest::Client::builder()
+    pub fn new(proxy_url: Option<&str>) -> Self {
+        let client = proxy::apply(reqwest::Client::builder(), proxy_url)
             .user_agent("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36")
             .timeout(std::time::Duration::from_secs(30))
             .build()
@@ -58,7 +59,7 @@ impl FilmpalastToProvider {
 
 impl Default for FilmpalastToProvider {
     fn default() -> Self {
-        Self::new()
+        Self::new(None)
     }
 }
 
@@ -68,8 +69,17 @@ impl StreamProvider for FilmpalastToProvider {
         "filmpalastto"
     }
 
-    async fn get_stream_page_url(&self, title: &str) -> Result<String, StreamError> {
-        let slug = Self::normalize_title(title);
+    fn display_name(&self) -> &str {
+        "FilmpalastTo"
+    }
+
+    /// Episodes have their own pages, addressed as `<title>-s01e02`.
+    async fn get_stream_page_url(&self, query: &StreamQuery) -> Result<String, StreamError> {
+        let title = query.title.as_str();
+        let mut slug = Self::normalize_title(title);
+        if let Some((season, episode)) = query.episode_number() {
+            slug.push_str(&format!("-s{:02}e{:02}", season, episode));
+        }
         let url = format!("{}/{}", FILMPALAST_DOMAIN, slug);
 
         let response = self
@@ -86,7 +96,7 @@ impl StreamProvider for FilmpalastToProvider {
         if !response.status().is_success() {
             return Err(StreamError::NotFound(format!(
                 "Page not found for title: {}",
-                title
+                slug
             )));
         }
 
@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+
+use crate::proxy;
+use crate::streaming::{StreamError, StreamProvider, StreamQuery};
+
+const MEGAKINO_DOMAIN: &str = "https://megakino.ms";
+
+/// Unlike Filmpalast, titles can't be addressed by slug, so the site search
+/// is used and the first film page whose link contains the title's slug is
+/// opened.
+pub struct MegakinoProvider {
+    client: reqwest::Client,
+}
+
+impl MegakinoProvider {
+    pub fn new(proxy_url: Option<&str>) -> Self {
+        let client = proxy::apply(reqwest::Client::builder(), proxy_url)
+            .user_agent("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36")
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .unwrap_or_default();
+
+        Self { client }
+    }
+
+    fn slugify(title: &str) -> String {
+        title
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    fn extract_film_url(html: &str, slug: &str) -> Option<String> {
+        let marker = format!(r#"href="{}/films/"#, MEGAKINO_DOMAIN);
+        html.match_indices(&marker).find_map(|(start, _)| {
+            let rest = &html[start + 6..];
+            let url = &rest[..rest.find('"')?];
+            url.contains(slug).then(|| url.to_string())
+        })
+    }
+
+    /// The player is embedded, so the VOE link can sit in an `href`, `src`
+    /// or `data-src` attribute.
+    fn extract_voe_url(html: &str) -> Option<String> {
+        let start = html.find("\"https://voe.sx/")? + 1;
+        let rest = &html[start..];
+        let end = rest.find('"')?;
+        Some(rest[..end].to_string())
+    }
+
+    async fn fetch_html(&self, url: &str) -> Result<String, StreamError> {
+        let response = self
+            .client
+            .get(url)
+            .header(
+                "Accept",
+                "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+            )
+            .send()
+            .await
+            .map_err(|e| StreamError::Network(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(StreamError::Network(format!(
+                "HTTP {} for {}",
+                response.status(),
+                url
+            )));
+        }
+
+        response
+            .text()
+            .await
+            .map_err(|e| StreamError::Network(e.to_string()))
+    }
+}
+
+impl Default for MegakinoProvider {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+#[async_trait]
+impl StreamProvider for MegakinoProvider {
+    fn name(&self) -> &str {
+        "megakino"
+    }
+
+    fn display_name(&self) -> &str {
+        "Megakino"
+    }
+
+    /// Series are a single page with an in-page episode picker, so there is
+    /// no URL to return for one episode.
+    async fn get_stream_page_url(&self, query: &StreamQuery) -> Result<String, StreamError> {
+        if query.episode_number().is_some() {
+            return Err(StreamError::NotFound(
+                "Episodes aren't available from this provider".to_string(),
+            ));
+        }
+        let title = query.title.as_str();
+        let slug = Self::slugify(title);
+        let search_url = format!(
+            "{}/index.php?do=search&subaction=search&story={}",
+            MEGAKINO_DOMAIN,
+            slug.replace('-', "+")
+        );
+        let results = self.fetch_html(&search_url).await?;
+        let film_url = Self::extract_film_url(&results, &slug).ok_or_else(|| {
+            StreamError::NotFound(format!("No search result for title: {}", title))
+        })?;
+
+        let page = self.fetch_html(&film_url).await?;
+        Self::extract_voe_url(&page)
+            .ok_or_else(|| StreamError::NotFound("No VOE URL found on page".to_string()))
+    }
+}
@@ -1,3 +1,15 @@
 mod filmpalastto;
+mod megakino;
 
 pub use filmpalastto::FilmpalastToProvider;
+pub use megakino::MegakinoProvider;
+
+use crate::streaming::StreamProvider;
+
+/// Every provider, in the default order they are tried.
+pub fn all(proxy_url: Option<&str>) -> Vec<Box<dyn StreamProvider>> {
+    vec![
+        Box::new(FilmpalastToProvider::new(proxy_url)),
+        Box::new(MegakinoProvider::new(proxy_url)),
+    ]
+}
@@ -3,7 +3,8 @@ use base64::{engine::general_purpose::STANDARD, Engine};
 use regex::Regex;
 use serde_json::Value;
 
-use crate::streaming::{StreamError, StreamResolver};
+use crate::proxy;
+use crate::streaming::{StreamError, StreamResolver, StreamVariant};
 
 const MARKERS: &[&str] = &["@#", "^^", "~@", "%?", "*~", "!!", "#&"];
 const BAIT_PATTERNS: &[&str] = &["bigbuckbunny", "test-videos.co.uk", "sample-videos.com"];
@@ -14,8 +15,8 @@ pub struct VoeResolver {
 }
 
 impl VoeResolver {
-    pub fn new() -> Self {
-        let client = reqwest::Client::builder()
+    pub fn new(proxy_url: Option<&str>) -> Self {
+        let client = proxy::apply(reqwest::Client::builder(), proxy_url)
             .user_agent("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 Chrome/120.0.0.0")
             .timeout(std::time::Duration::from_secs(30))
             .redirect(reqwest::redirect::Policy::none())
@@ -162,6 +163,61 @@ impl VoeResolver {
             .map_err(|e| StreamError::Network(e.to_string()))
     }
 
+    /// Lists the renditions of an HLS master playlist, tallest first. Media
+    /// playlists have no `#EXT-X-STREAM-INF` entries and yield nothing.
+    fn parse_hls_variants(playlist_url: &str, playlist: &str) -> Vec<StreamVariant> {
+        let mut variants = Vec::new();
+        let mut lines = playlist.lines().map(str::trim);
+        while let Some(line) = lines.next() {
+            let Some(attributes) = line.strip_prefix("#EXT-X-STREAM-INF:") else {
+                continue;
+            };
+            let quality = attributes
+                .split(',')
+                .find_map(|attribute| attribute.strip_prefix("RESOLUTION="))
+                .and_then(|resolution| resolution.split_once('x'))
+                .and_then(|(_, height)| height.parse().ok());
+            let Some(uri) = lines.find(|l| !l.is_empty() && !l.starts_with('#')) else {
+                break;
+            };
+            variants.push(StreamVariant {
+                quality,
+                url: Self::join_playlist_url(playlist_url, uri),
+            });
+        }
+        variants.sort_by(|a, b| b.quality.cmp(&a.quality));
+        variants.dedup_by(|a, b| a.quality.is_some() && a.quality == b.quality);
+        variants
+    }
+
+    fn join_playlist_url(playlist_url: &str, uri: &str) -> String {
+        if uri.starts_with("http") || uri.starts_with("//") || uri.starts_with('/') {
+            return Self::resolve_redirect_url(playlist_url, uri);
+        }
+        let base = playlist_url.split('?').next().unwrap_or(playlist_url);
+        match base.rfind('/') {
+            Some(end) => format!("{}{}", &base[..=end], uri),
+            None => uri.to_string(),
+        }
+    }
+
+    /// Falls back to the stream itself as the only variant when the master
+    /// playlist can't be read.
+    async fn stream_variants(&self, stream_url: String) -> Vec<StreamVariant> {
+        if stream_url.contains(".m3u8") {
+            if let Ok(playlist) = self.fetch_page(&stream_url).await {
+                let variants = Self::parse_hls_variants(&stream_url, &playlist);
+                if !variants.is_empty() {
+                    return variants;
+                }
+            }
+        }
+        vec![StreamVariant {
+            quality: None,
+            url: stream_url,
+        }]
+    }
+
     fn resolve_redirect_url(base_url: &str, redirect: &str) -> String {
         match redirect {
             r if r.starts_with("//") => format!("https:{}", r),
@@ -183,7 +239,7 @@ impl VoeResolver {
 
 impl Default for VoeResolver {
     fn default() -> Self {
-        Self::new()
+        Self::new(None)
     }
 }
 
@@ -193,11 +249,15 @@ impl StreamResolver for VoeResolver {
         "voe"
     }
 
+    fn display_name(&self) -> &str {
+        "VOE"
+    }
+
     fn can_handle(&self, url: &str) -> bool {
         url.contains("voe.sx") || url.contains("voe.")
     }
 
-    async fn resolve(&self, url: &str) -> Result<String, StreamError> {
+    async fn resolve(&self, url: &str) -> Result<Vec<StreamVariant>, StreamError> {
         let mut current_url = url.to_string();
 
         for _ in 0..self.max_redirects {
@@ -209,7 +269,7 @@ impl StreamResolver for VoeResolver {
             }
 
             if let Some(stream_url) = Self::extract_stream_url(&html) {
-                return Ok(stream_url);
+                return Ok(self.stream_variants(stream_url).await);
             }
 
             break;
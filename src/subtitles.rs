@@ -0,0 +1,195 @@
+use std::path::Path;
+
+const SUBTITLE_EXTENSIONS: [&str; 2] = ["srt", "vtt"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleCue {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// Parses SRT and WebVTT alike: cue numbers, the WEBVTT header, NOTE and
+/// STYLE blocks are skipped since only blocks with a `-->` timing line are
+/// kept. Cues come back sorted by start time.
+pub fn parse_subtitles(content: &str) -> Vec<SubtitleCue> {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut cues = Vec::new();
+    let mut block: Vec<&str> = Vec::new();
+    for line in content.lines().chain(std::iter::once("")) {
+        if !line.trim().is_empty() {
+            block.push(line);
+            continue;
+        }
+        if let Some(cue) = parse_cue(&block) {
+            cues.push(cue);
+        }
+        block.clear();
+    }
+    cues.sort_by(|a, b| a.start.total_cmp(&b.start));
+    cues
+}
+
+fn parse_cue(block: &[&str]) -> Option<SubtitleCue> {
+    let timing_index = block.iter().position(|line| line.contains("-->"))?;
+    let (start, rest) = block[timing_index].split_once("-->")?;
+    // VTT cue settings such as `align:start` follow the end time.
+    let end = rest.split_whitespace().next()?;
+    let start = parse_timestamp(start)?;
+    let end = parse_timestamp(end)?;
+    let text = block[timing_index + 1..]
+        .iter()
+        .map(|line| strip_markup(line.trim()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if text.trim().is_empty() || end <= start {
+        return None;
+    }
+    Some(SubtitleCue { start, end, text })
+}
+
+/// Accepts `hh:mm:ss,mmm` (SRT) and `hh:mm:ss.mmm` or `mm:ss.mmm` (VTT).
+fn parse_timestamp(timestamp: &str) -> Option<f64> {
+    let timestamp = timestamp.trim().replace(',', ".");
+    let mut parts = timestamp.rsplit(':');
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    let minutes: f64 = parts.next()?.parse().ok()?;
+    let hours: f64 = match parts.next() {
+        Some(hours) => hours.parse().ok()?,
+        None => 0.0,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+/// Drops VTT voice, class and styling tags and SRT `<i>`/`<b>` tags, which
+/// a plain text widget can't render.
+fn strip_markup(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut in_tag = false;
+    for c in line.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Every cue showing at `position`, joined top to bottom, since cues may
+/// overlap (e.g. two speakers at once).
+pub fn active_cue_text(cues: &[SubtitleCue], position: f64) -> Option<String> {
+    let active: Vec<&str> = cues
+        .iter()
+        .take_while(|cue| cue.start <= position)
+        .filter(|cue| position < cue.end)
+        .map(|cue| cue.text.as_str())
+        .collect();
+    (!active.is_empty()).then(|| active.join("\n"))
+}
+
+/// Reads a track from a URL, fetched with `client` so the proxy and User-Agent
+/// settings apply, or from a local path.
+pub async fn load_subtitles(
+    client: reqwest::Client,
+    source: String,
+) -> Result<Vec<SubtitleCue>, String> {
+    let bytes = if source.starts_with("http://") || source.starts_with("https://") {
+        let response = client
+            .get(&source)
+            .send()
+            .await
+            .map_err(|e| format!("Subtitle request failed: {}", e))?
+            .error_for_status()
+            .map_err(|e| format!("Subtitle request failed: {}", e))?;
+        response
+            .bytes()
+            .await
+            .map_err(|e| format!("Subtitle download failed: {}", e))?
+            .to_vec()
+    } else {
+        tokio::fs::read(&source)
+            .await
+            .map_err(|e| format!("Could not read {}: {}", source, e))?
+    };
+    let cues = parse_subtitles(&String::from_utf8_lossy(&bytes));
+    if cues.is_empty() {
+        return Err(String::from("No subtitle cues found"));
+    }
+    Ok(cues)
+}
+
+/// A `.srt` or `.vtt` next to a local video with the same file stem.
+pub fn find_sidecar_subtitles(video_path: &str) -> Option<String> {
+    let path = Path::new(video_path);
+    SUBTITLE_EXTENSIONS
+        .iter()
+        .map(|extension| path.with_extension(extension))
+        .find(|candidate| candidate.is_file())
+        .map(|candidate| candidate.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue(start: f64, end: f64, text: &str) -> SubtitleCue {
+        SubtitleCue {
+            start,
+            end,
+            text: String::from(text),
+        }
+    }
+
+    #[test]
+    fn srt_uses_comma_milliseconds() {
+        let srt = "1\n00:00:01,500 --> 00:00:03,250\nHello\n\n2\n01:02:03,004 --> 01:02:04,000\n<i>Again</i>\n";
+
+        assert_eq!(
+            parse_subtitles(srt),
+            vec![cue(1.5, 3.25, "Hello"), cue(3723.004, 3724.0, "Again")]
+        );
+    }
+
+    #[test]
+    fn vtt_uses_dot_milliseconds_and_short_timestamps() {
+        let vtt = "WEBVTT\n\nNOTE a comment\n\n00:01.500 --> 00:03.250 align:start\n<v Ann>Hello</v>\n\n00:00:04.000 --> 00:00:05.000\nBye\n";
+
+        assert_eq!(
+            parse_subtitles(vtt),
+            vec![cue(1.5, 3.25, "Hello"), cue(4.0, 5.0, "Bye")]
+        );
+    }
+
+    #[test]
+    fn overlapping_cues_are_sorted_and_shown_together() {
+        let srt =
+            "2\n00:00:02,000 --> 00:00:06,000\nSecond\n\n1\n00:00:01,000 --> 00:00:04,000\nFirst\n";
+        let cues = parse_subtitles(srt);
+
+        assert_eq!(cues, vec![cue(1.0, 4.0, "First"), cue(2.0, 6.0, "Second")]);
+        assert_eq!(
+            active_cue_text(&cues, 3.0).as_deref(),
+            Some("First\nSecond")
+        );
+        assert_eq!(active_cue_text(&cues, 5.0).as_deref(), Some("Second"));
+    }
+
+    #[test]
+    fn active_cue_text_respects_cue_bounds() {
+        let cues = vec![cue(1.0, 2.0, "One"), cue(3.0, 4.0, "Two")];
+
+        assert_eq!(active_cue_text(&cues, 0.5), None);
+        assert_eq!(active_cue_text(&cues, 1.0).as_deref(), Some("One"));
+        assert_eq!(active_cue_text(&cues, 2.0), None);
+        assert_eq!(active_cue_text(&cues, 3.5).as_deref(), Some("Two"));
+        assert_eq!(active_cue_text(&cues, 4.0), None);
+    }
+}
@@ -1,19 +1,35 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use crate::proxy;
 use crate::settings::AppSettings;
 
 use crate::media::{
-    ApiError, CastMember, Category, Collection, ContentSection, DetailPopupData, Episode,
-    ExternalIds, Genre, Keyword, MediaId, MediaItem, MediaType, ProductionCompany, Season,
-    TmdbMediaResult, TmdbSearchResponse,
+    api_cache_dir, simple_hash, ApiError, CastMember, Category, Collection, ContentSection,
+    CrewMember, DetailPopupData, Episode, ExternalIds, Genre, GenreMatch, GenreScope, Keyword,
+    MediaId, MediaItem, MediaType, MediaTypeFilter, Person, PersonDetail, ProductionCompany,
+    SearchFilters, SearchPage, Season, SectionQuery, SortOption, TmdbMediaResult,
+    TmdbSearchResponse, TopPicksSource, WatchProvider, SECTION_IDS,
 };
 use crate::video::{TrailerVideo, VideosResponse};
 
-use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 
 const CACHE_TTL_SECONDS: u64 = 300;
+const MAX_CACHE_ENTRIES: usize = 200;
+const POPULAR_CACHE_TTL_SECONDS: u64 = 3600;
+const HERO_ROTATION_SIZE: usize = 5;
+/// Continue Watching plus the seven core rows.
+const BUILT_IN_SECTIONS: usize = 8;
+/// Provider rows fill whatever `SECTION_IDS` has left after the built-in rows.
+const MAX_PROVIDER_SECTIONS: usize = SECTION_IDS.len() - BUILT_IN_SECTIONS;
+const MAX_TOP_PICKS_SEEDS: usize = 3;
+const MAX_CONTINUE_WATCHING: usize = 10;
+/// Result pages fetched per media type when browsing with `discover`.
+const DISCOVER_PAGES: u32 = 3;
 
 fn url_encode(s: &str) -> String {
     let mut result = String::with_capacity(s.len() * 3);
@@ -51,6 +67,52 @@ pub struct TmdbCastMember {
     pub order: u32,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct TmdbWatchProvidersResponse {
+    pub results: Vec<TmdbWatchProvider>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TmdbWatchProvider {
+    pub provider_id: u64,
+    pub provider_name: String,
+    #[serde(default)]
+    pub display_priority: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TmdbPeopleResponse {
+    pub results: Vec<TmdbPerson>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TmdbPerson {
+    pub id: u64,
+    pub name: String,
+    pub profile_path: Option<String>,
+    #[serde(default)]
+    pub known_for_department: String,
+    #[serde(default)]
+    pub known_for: Vec<TmdbMediaResult>,
+}
+
+impl From<TmdbPerson> for Person {
+    fn from(person: TmdbPerson) -> Self {
+        Self {
+            id: person.id,
+            name: person.name,
+            profile_path: person.profile_path,
+            known_for_department: person.known_for_department,
+            known_for: person
+                .known_for
+                .into_iter()
+                .map(|r| MediaItem::from(r).title)
+                .filter(|title| !title.is_empty())
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct TmdbExternalIdsResponse {
     pub imdb_id: Option<String>,
@@ -101,18 +163,6 @@ pub struct TmdbEpisode {
     pub vote_average: f32,
 }
 
-pub async fn fetch_image_bytes(url: String) -> Result<Vec<u8>, String> {
-    reqwest::get(&url)
-        .await
-        .map_err(|e| e.to_string())?
-        .error_for_status()
-        .map_err(|e| e.to_string())?
-        .bytes()
-        .await
-        .map(|b| b.to_vec())
-        .map_err(|e| e.to_string())
-}
-
 #[derive(Clone)]
 pub enum ImageSize {
     Poster,
@@ -133,8 +183,185 @@ impl<T: Clone> CacheEntry<T> {
         }
     }
 
-    fn is_valid(&self) -> bool {
-        self.created_at.elapsed() < Duration::from_secs(CACHE_TTL_SECONDS)
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        self.created_at.elapsed() < ttl
+    }
+}
+
+/// Inserts `data` and drops the oldest entries while the cache holds more
+/// than `max_entries`.
+fn insert_capped<T: Clone>(
+    cache: &mut HashMap<String, CacheEntry<T>>,
+    key: String,
+    data: T,
+    max_entries: usize,
+) {
+    cache.insert(key, CacheEntry::new(data));
+    evict_oldest(cache, max_entries);
+}
+
+fn evict_oldest<T>(cache: &mut HashMap<String, CacheEntry<T>>, max_entries: usize) {
+    while cache.len() > max_entries.max(1) {
+        let Some(oldest) = cache
+            .iter()
+            .min_by_key(|(_, entry)| entry.created_at)
+            .map(|(key, _)| key.clone())
+        else {
+            break;
+        };
+        cache.remove(&oldest);
+    }
+}
+
+/// Entry counts of the in-memory caches, for the settings page.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheStats {
+    pub lists: usize,
+    pub details: usize,
+    pub popups: usize,
+    pub seasons: usize,
+}
+
+impl CacheStats {
+    pub fn total(&self) -> usize {
+        self.lists + self.details + self.popups + self.seasons
+    }
+}
+
+/// A `CacheEntry` as written to disk, where an `Instant` means nothing
+/// across restarts.
+#[derive(Serialize, Deserialize)]
+struct StoredEntry<T> {
+    /// Language and region the response was fetched for.
+    scope: String,
+    key: String,
+    created_at: u64,
+    data: T,
+}
+
+/// Just the age of a `StoredEntry`, read without parsing its data.
+#[derive(Deserialize)]
+struct StoredHeader {
+    created_at: u64,
+}
+
+const DISK_CACHE_KINDS: [&str; 4] = ["lists", "details", "popups", "seasons"];
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Mirrors the in-memory caches under `~/.cache/movix/api/`, one file per
+/// entry, so the home page can start warm after a restart.
+#[derive(Clone, Clone)]
+struct DiskCache {
+    directory: PathBuf,
+    scope: String,
+}
+
+impl DiskCache {
+    fn entry_path(&self, kind: &str, key: &str) -> PathBuf {
+        let name = simple_hash(&format!("{}:{}", self.scope, key));
+        self.directory.join(kind).join(format!("{}.json", name))
+    }
+
+    /// Serializes in place but writes on a background thread, since this is
+    /// called from the async fetch paths.
+    fn store<T: Serialize>(&self, kind: &str, key: &str, data: &T) {
+        let path = self.entry_path(kind, key);
+        let entry = StoredEntry {
+            scope: self.scope.clone(),
+            key: key.to_string(),
+            created_at: unix_now(),
+            data,
+        };
+        let Ok(json) = serde_json::to_string(&entry) else {
+            return;
+        };
+        std::thread::spawn(move || {
+            if let Some(dir) = path.parent() {
+                let _ = std::fs::create_dir_all(dir);
+            }
+            let _ = std::fs::write(path, json);
+        });
+    }
+
+    /// Deletes expired and unreadable entries of every kind, then the oldest
+    /// ones past `max_entries`, so the directory doesn't keep growing between
+    /// the loads at startup.
+    fn prune(&self, ttl: Duration, max_entries: usize) {
+        let now = unix_now();
+        for kind in DISK_CACHE_KINDS {
+            let Ok(files) = std::fs::read_dir(self.directory.join(kind)) else {
+                continue;
+            };
+            let mut kept = Vec::new();
+            for path in files.flatten().map(|file| file.path()) {
+                let created_at = std::fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|json| serde_json::from_str::<StoredHeader>(&json).ok())
+                    .map(|header| header.created_at);
+                match created_at {
+                    Some(created_at)
+                        if Duration::from_secs(now.saturating_sub(created_at)) < ttl =>
+                    {
+                        kept.push((created_at, path));
+                    }
+                    _ => {
+                        let _ = std::fs::remove_file(&path);
+                    }
+                }
+            }
+            kept.sort_by_key(|(created_at, _)| std::cmp::Reverse(*created_at));
+            for (_, path) in kept.into_iter().skip(max_entries.max(1)) {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+    }
+
+    /// Entries past `ttl` are deleted rather than loaded.
+    fn load<T: DeserializeOwned>(
+        &self,
+        kind: &str,
+        ttl: Duration,
+        max_entries: usize,
+    ) -> HashMap<String, CacheEntry<T>> {
+        let mut entries = HashMap::new();
+        let Ok(files) = std::fs::read_dir(self.directory.join(kind)) else {
+            return entries;
+        };
+        let now = unix_now();
+        for path in files.flatten().map(|file| file.path()) {
+            let stored = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|json| serde_json::from_str::<StoredEntry<T>>(&json).ok());
+            let Some(stored) = stored else {
+                let _ = std::fs::remove_file(&path);
+                continue;
+            };
+            let age = Duration::from_secs(now.saturating_sub(stored.created_at));
+            if age >= ttl {
+                let _ = std::fs::remove_file(&path);
+                continue;
+            }
+            if stored.scope != self.scope {
+                continue;
+            }
+            if let Some(created_at) = Instant::now().checked_sub(age) {
+                entries.insert(
+                    stored.key,
+                    CacheEntry {
+                        data: stored.data,
+                        created_at,
+                    },
+                );
+            }
+        }
+        evict_oldest(&mut entries, max_entries);
+        entries
     }
 }
 
@@ -144,23 +371,48 @@ pub struct TmdbClient {
     base_url: String,
     image_base_url: String,
     language: String,
+    region: String,
+    cap_backdrop_size: bool,
+    cap_poster_size: bool,
     http_client: Arc<reqwest::Client>,
     list_cache: Arc<RwLock<HashMap<String, CacheEntry<Vec<MediaItem>>>>>,
     details_cache: Arc<RwLock<HashMap<String, CacheEntry<MediaItem>>>>,
     detail_popup_cache: Arc<RwLock<HashMap<String, CacheEntry<DetailPopupData>>>>,
+    season_cache: Arc<RwLock<HashMap<String, CacheEntry<Vec<Episode>>>>>,
+    disk_cache: Option<DiskCache>,
+    cache_ttl: Duration,
+    /// Per cache, not across all of them.
+    max_cache_entries: usize,
 }
 
 impl TmdbClient {
-    pub fn new(api_key: String, language: String) -> Self {
+    pub fn new(
+        api_key: String,
+        language: String,
+        region: String,
+        user_agent: &str,
+        proxy_url: Option<&str>,
+    ) -> Self {
+        let http_client = proxy::apply(reqwest::Client::builder(), proxy_url)
+            .user_agent(user_agent)
+            .build()
+            .unwrap_or_default();
         Self {
             api_key,
             base_url: String::from("https://api.themoviedb.org/3"),
             image_base_url: String::from("https://image.tmdb.org/t/p"),
             language,
-            http_client: Arc::new(reqwest::Client::new()),
+            region,
+            cap_backdrop_size: false,
+            cap_poster_size: false,
+            http_client: Arc::new(http_client),
             list_cache: Arc::new(RwLock::new(HashMap::new())),
             details_cache: Arc::new(RwLock::new(HashMap::new())),
             detail_popup_cache: Arc::new(RwLock::new(HashMap::new())),
+            season_cache: Arc::new(RwLock::new(HashMap::new())),
+            disk_cache: None,
+            cache_ttl: Duration::from_secs(CACHE_TTL_SECONDS),
+            max_cache_entries: MAX_CACHE_ENTRIES,
         }
     }
 
@@ -170,12 +422,87 @@ impl TmdbClient {
         } else {
             settings.language.clone()
         };
-        Self::new(settings.api_key.clone(), language)
+        let mut client = Self::new(
+            settings.api_key.clone(),
+            language,
+            settings.region.clone(),
+            &settings.effective_user_agent(),
+            settings.proxy(),
+        );
+        client.cap_backdrop_size = settings.low_bandwidth_images();
+        client.cap_poster_size = settings.data_saver;
+        client.cache_ttl = settings.api_cache_ttl();
+        client.max_cache_entries = settings.api_cache_max_entries;
+        if settings.api_disk_cache {
+            client.enable_disk_cache();
+        }
+        client
+    }
+
+    /// Reads back what the last session cached and keeps writing new entries
+    /// to disk alongside memory.
+    fn enable_disk_cache(&mut self) {
+        let Some(directory) = api_cache_dir() else {
+            return;
+        };
+        let (ttl, max_entries) = (self.cache_ttl, self.max_cache_entries);
+        let disk_cache = DiskCache {
+            directory,
+            scope: format!("{}/{}", self.language, self.region),
+        };
+        self.list_cache = Arc::new(RwLock::new(disk_cache.load("lists", ttl, max_entries)));
+        self.details_cache = Arc::new(RwLock::new(disk_cache.load("details", ttl, max_entries)));
+        self.detail_popup_cache =
+            Arc::new(RwLock::new(disk_cache.load("popups", ttl, max_entries)));
+        self.season_cache = Arc::new(RwLock::new(disk_cache.load("seasons", ttl, max_entries)));
+        self.disk_cache = Some(disk_cache);
     }
 
+    pub fn cache_stats(&self) -> CacheStats {
+        let count = |len: Option<usize>| len.unwrap_or(0);
+        CacheStats {
+            lists: count(self.list_cache.read().ok().map(|c| c.len())),
+            details: count(self.details_cache.read().ok().map(|c| c.len())),
+            popups: count(self.detail_popup_cache.read().ok().map(|c| c.len())),
+            seasons: count(self.season_cache.read().ok().map(|c| c.len())),
+        }
+    }
+
+    /// Trims the on-disk API cache on a background thread.
+    pub fn prune_disk_cache(&self) {
+        let Some(disk_cache) = self.disk_cache.clone() else {
+            return;
+        };
+        let (ttl, max_entries) = (self.cache_ttl, self.max_cache_entries);
+        std::thread::spawn(move || disk_cache.prune(ttl, max_entries));
+    }
+
+    fn persist<T: Serialize>(&self, kind: &str, key: &str, data: &T) {
+        if let Some(ref disk_cache) = self.disk_cache {
+            disk_cache.store(kind, key, data);
+        }
+    }
+
+    pub async fn fetch_image_bytes(&self, url: &str) -> Result<Vec<u8>, String> {
+        self.http_client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?
+            .error_for_status()
+            .map_err(|e| e.to_string())?
+            .bytes()
+            .await
+            .map(|b| b.to_vec())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Logos use `Original` and are never capped, so they stay crisp.
     pub fn image_url(&self, path: &str, size: ImageSize) -> String {
         let size_path = match size {
+            ImageSize::Poster if self.cap_poster_size => "w342",
             ImageSize::Poster => "w500",
+            ImageSize::Backdrop if self.cap_backdrop_size => "w1280",
             ImageSize::Backdrop | ImageSize::Original => "original",
         };
         format!("{}/{}{}", self.image_base_url, size_path, path)
@@ -192,18 +519,38 @@ impl TmdbClient {
         format!("{}&{}", self.build_url(endpoint), params)
     }
 
+    fn build_regional_url(&self, endpoint: &str, params: &str) -> String {
+        let region = url_encode(&self.region);
+        let regional = format!("region={}&certification_country={}", region, region);
+        if params.is_empty() {
+            self.build_url_with_params(endpoint, &regional)
+        } else {
+            self.build_url_with_params(endpoint, &format!("{}&{}", params, regional))
+        }
+    }
+
     fn get_cached_list(&self, key: &str) -> Option<Vec<MediaItem>> {
         self.list_cache
             .read()
             .ok()?
             .get(key)
-            .filter(|e| e.is_valid())
+            .filter(|e| e.is_fresh(self.cache_ttl))
+            .map(|e| e.data.clone())
+    }
+
+    fn get_cached_list_within(&self, key: &str, ttl: Duration) -> Option<Vec<MediaItem>> {
+        self.list_cache
+            .read()
+            .ok()?
+            .get(key)
+            .filter(|e| e.is_fresh(ttl))
             .map(|e| e.data.clone())
     }
 
     fn set_cached_list(&self, key: String, data: Vec<MediaItem>) {
+        self.persist("lists", &key, &data);
         if let Ok(mut cache) = self.list_cache.write() {
-            cache.insert(key, CacheEntry::new(data));
+            insert_capped(&mut cache, key, data, self.max_cache_entries);
         }
     }
 
@@ -212,13 +559,14 @@ impl TmdbClient {
             .read()
             .ok()?
             .get(key)
-            .filter(|e| e.is_valid())
+            .filter(|e| e.is_fresh(self.cache_ttl))
             .map(|e| e.data.clone())
     }
 
     fn set_cached_details(&self, key: String, data: MediaItem) {
+        self.persist("details", &key, &data);
         if let Ok(mut cache) = self.details_cache.write() {
-            cache.insert(key, CacheEntry::new(data));
+            insert_capped(&mut cache, key, data, self.max_cache_entries);
         }
     }
 
@@ -227,13 +575,30 @@ impl TmdbClient {
             .read()
             .ok()?
             .get(key)
-            .filter(|e| e.is_valid())
+            .filter(|e| e.is_fresh(self.cache_ttl))
             .map(|e| e.data.clone())
     }
 
     fn set_cached_popup(&self, key: String, data: DetailPopupData) {
+        self.persist("popups", &key, &data);
         if let Ok(mut cache) = self.detail_popup_cache.write() {
-            cache.insert(key, CacheEntry::new(data));
+            insert_capped(&mut cache, key, data, self.max_cache_entries);
+        }
+    }
+
+    fn get_cached_season(&self, key: &str) -> Option<Vec<Episode>> {
+        self.season_cache
+            .read()
+            .ok()?
+            .get(key)
+            .filter(|e| e.is_fresh(self.cache_ttl))
+            .map(|e| e.data.clone())
+    }
+
+    fn set_cached_season(&self, key: String, data: Vec<Episode>) {
+        self.persist("seasons", &key, &data);
+        if let Ok(mut cache) = self.season_cache.write() {
+            insert_capped(&mut cache, key, data, self.max_cache_entries);
         }
     }
 
@@ -253,6 +618,16 @@ impl TmdbClient {
         }
     }
 
+    /// Returns whether the TMDb API is reachable at all, regardless of the
+    /// response status.
+    pub async fn is_reachable(&self) -> bool {
+        self.http_client
+            .get(self.build_url("/configuration"))
+            .send()
+            .await
+            .is_ok()
+    }
+
     async fn fetch_json<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T, ApiError> {
         self.fetch_response(url)
             .await?
@@ -282,7 +657,23 @@ impl TmdbClient {
     }
 
     pub async fn fetch_top_rated_movies(&self) -> Result<Vec<MediaItem>, ApiError> {
-        self.fetch_and_parse(&self.build_url("/movie/top_rated"), "top_rated_movies")
+        self.fetch_and_parse(
+            &self.build_regional_url("/movie/top_rated", ""),
+            "top_rated_movies",
+        )
+        .await
+    }
+
+    pub async fn fetch_now_playing(&self) -> Result<Vec<MediaItem>, ApiError> {
+        self.fetch_and_parse(
+            &self.build_regional_url("/movie/now_playing", ""),
+            "now_playing",
+        )
+        .await
+    }
+
+    pub async fn fetch_upcoming(&self) -> Result<Vec<MediaItem>, ApiError> {
+        self.fetch_and_parse(&self.build_regional_url("/movie/upcoming", ""), "upcoming")
             .await
     }
 
@@ -297,20 +688,169 @@ impl TmdbClient {
         media_type: &str,
     ) -> Result<Vec<MediaItem>, ApiError> {
         let cache_key = format!("genre_{}_{}", genre_id, media_type);
-        let url = self.build_url_with_params(
+        let url = self.build_regional_url(
             &format!("/discover/{}", media_type),
             &format!("with_genres={}&sort_by=popularity.desc", genre_id),
         );
         self.fetch_and_parse(&url, &cache_key).await
     }
 
-    pub async fn search(&self, query: &str) -> Result<Vec<MediaItem>, ApiError> {
-        let cache_key = format!("search_{}", query);
-        let url =
-            self.build_url_with_params("/search/multi", &format!("query={}", url_encode(query)));
+    pub async fn discover_by_provider(
+        &self,
+        provider_id: u64,
+        region: &str,
+    ) -> Result<Vec<MediaItem>, ApiError> {
+        let cache_key = format!("provider_{}_{}", provider_id, region);
+        let url = self.build_regional_url(
+            "/discover/movie",
+            &format!(
+                "with_watch_providers={}&watch_region={}&sort_by=popularity.desc",
+                provider_id,
+                url_encode(region)
+            ),
+        );
         self.fetch_and_parse(&url, &cache_key).await
     }
 
+    /// Streaming services available in `region`, most prominent first.
+    pub async fn fetch_watch_providers(
+        &self,
+        region: &str,
+    ) -> Result<Vec<WatchProvider>, ApiError> {
+        let url = self.build_url_with_params(
+            "/watch/providers/movie",
+            &format!("watch_region={}", url_encode(region)),
+        );
+        let mut response: TmdbWatchProvidersResponse = self.fetch_json(&url).await?;
+        response.results.sort_by_key(|p| p.display_priority);
+        Ok(response
+            .results
+            .into_iter()
+            .map(|p| WatchProvider {
+                id: p.provider_id,
+                name: p.provider_name,
+            })
+            .collect())
+    }
+
+    /// Not cached, since the list cache can't hold the page count.
+    pub async fn search(&self, query: &str, page: u32) -> Result<SearchPage, ApiError> {
+        let url = self.build_url_with_params(
+            "/search/multi",
+            &format!("query={}&page={}", url_encode(query), page),
+        );
+        let response: TmdbSearchResponse = self.fetch_json(&url).await?;
+        Ok(SearchPage {
+            items: response.results.into_iter().map(MediaItem::from).collect(),
+            page: response.page.max(page),
+            total_pages: response.total_pages,
+        })
+    }
+
+    /// Browses by the search filters alone via `/discover`, for an empty
+    /// query. Movies and series are fetched a few pages deep each and
+    /// interleaved.
+    pub async fn discover(&self, filters: &SearchFilters) -> Result<Vec<MediaItem>, ApiError> {
+        let movies = async {
+            match filters.media_type {
+                MediaTypeFilter::TvSeries => Ok(Vec::new()),
+                _ => self.discover_pages("movie", filters).await,
+            }
+        };
+        let series = async {
+            match filters.media_type {
+                MediaTypeFilter::Movies => Ok(Vec::new()),
+                _ => self.discover_pages("tv", filters).await,
+            }
+        };
+        let (movies, series) = match futures::join!(movies, series) {
+            (Err(e), Err(_)) => return Err(e),
+            (movies, series) => (movies.unwrap_or_default(), series.unwrap_or_default()),
+        };
+
+        Ok(interleave(movies, series))
+    }
+
+    async fn discover_pages(
+        &self,
+        media_type: &str,
+        filters: &SearchFilters,
+    ) -> Result<Vec<MediaItem>, ApiError> {
+        let params = discover_params(media_type, filters);
+        let mut items = Vec::new();
+        for page in 1..=DISCOVER_PAGES {
+            let url = self.build_regional_url(
+                &format!("/discover/{}", media_type),
+                &format!("{}&page={}", params, page),
+            );
+            let cache_key = format!("discover_{}_{}_{}", media_type, params, page);
+            match self.fetch_and_parse(&url, &cache_key).await {
+                Ok(page_items) if page_items.is_empty() => break,
+                Ok(page_items) => items.extend(page_items),
+                // Later pages are a bonus; keep what the first ones found.
+                Err(_) if page > 1 => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(items)
+    }
+
+    /// Popular movies and series interleaved, for empty states that need
+    /// something to show. Popularity moves slowly, so this is cached longer
+    /// than other lists.
+    pub async fn fetch_popular(&self) -> Result<Vec<MediaItem>, ApiError> {
+        const CACHE_KEY: &str = "popular";
+        let ttl = Duration::from_secs(POPULAR_CACHE_TTL_SECONDS);
+        if let Some(cached) = self.get_cached_list_within(CACHE_KEY, ttl) {
+            return Ok(cached);
+        }
+
+        let movies = self
+            .fetch_and_parse(&self.build_url("/movie/popular"), "popular_movies")
+            .await;
+        let series = self
+            .fetch_and_parse(&self.build_url("/tv/popular"), "popular_series")
+            .await;
+        let (movies, series) = match (movies, series) {
+            (Err(e), Err(_)) => return Err(e),
+            (movies, series) => (movies.unwrap_or_default(), series.unwrap_or_default()),
+        };
+
+        let items = interleave(movies, series);
+        self.set_cached_list(CACHE_KEY.to_string(), items.clone());
+        Ok(items)
+    }
+
+    pub async fn fetch_popular_people(&self) -> Result<Vec<Person>, ApiError> {
+        let response: TmdbPeopleResponse =
+            self.fetch_json(&self.build_url("/person/popular")).await?;
+        Ok(response.results.into_iter().map(Person::from).collect())
+    }
+
+    pub async fn fetch_person_details(&self, id: u64) -> Result<PersonDetail, ApiError> {
+        let url = self.build_url_with_params(
+            &format!("/person/{}", id),
+            "append_to_response=combined_credits",
+        );
+        let json: serde_json::Value = self.fetch_json(&url).await?;
+        let person: TmdbPerson =
+            serde_json::from_value(json.clone()).map_err(|e| ApiError::Parse(e.to_string()))?;
+        let text_field = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+        };
+
+        Ok(PersonDetail {
+            person: Person::from(person),
+            biography: text_field("biography").unwrap_or_default(),
+            birthday: text_field("birthday"),
+            place_of_birth: text_field("place_of_birth"),
+            filmography: parse_filmography(&json),
+        })
+    }
+
     pub async fn fetch_genres(&self) -> Result<Vec<Genre>, ApiError> {
         let movie_url = self.build_url("/genre/movie/list");
         let tv_url = self.build_url("/genre/tv/list");
@@ -318,10 +858,26 @@ impl TmdbClient {
         let movie_response: GenreListResponse = self.fetch_json(&movie_url).await?;
         let tv_response: GenreListResponse = self.fetch_json(&tv_url).await?;
 
-        let mut genres = movie_response.genres;
+        // TMDb reuses some ids for differently named movie and TV genres, so
+        // only an identical id and name counts as the same genre.
+        let mut genres: Vec<Genre> = movie_response
+            .genres
+            .into_iter()
+            .map(|genre| Genre {
+                media_scope: GenreScope::Movie,
+                ..genre
+            })
+            .collect();
         for tv_genre in tv_response.genres {
-            if !genres.iter().any(|g| g.id == tv_genre.id) {
-                genres.push(tv_genre);
+            match genres
+                .iter_mut()
+                .find(|g| g.id == tv_genre.id && g.name == tv_genre.name)
+            {
+                Some(genre) => genre.media_scope = GenreScope::Both,
+                None => genres.push(Genre {
+                    media_scope: GenreScope::Tv,
+                    ..tv_genre
+                }),
             }
         }
         genres.sort_by(|a, b| a.name.cmp(&b.name));
@@ -358,7 +914,8 @@ impl TmdbClient {
 
         let mut item = MediaItem::from(result);
         item.runtime = extract_runtime(&json, media_type);
-        item.certification = extract_certification(&json, media_type);
+        item.runtime_range = extract_runtime_range(&json, media_type);
+        item.certification = extract_certification(&json, media_type, &self.region);
         item.logo_path = extract_logo_path(&json);
 
         self.set_cached_details(cache_key, item.clone());
@@ -524,9 +1081,14 @@ impl TmdbClient {
         tv_id: MediaId,
         season_number: u32,
     ) -> Result<Vec<Episode>, ApiError> {
+        let cache_key = format!("season_{}_{}", tv_id, season_number);
+        if let Some(cached) = self.get_cached_season(&cache_key) {
+            return Ok(cached);
+        }
+
         let url = self.build_url(&format!("/tv/{}/season/{}", tv_id, season_number));
         let season: TmdbSeasonResponse = self.fetch_json(&url).await?;
-        Ok(season
+        let episodes: Vec<Episode> = season
             .episodes
             .into_iter()
             .map(|e| Episode {
@@ -540,7 +1102,10 @@ impl TmdbClient {
                 runtime: e.runtime,
                 vote_average: e.vote_average,
             })
-            .collect())
+            .collect();
+
+        self.set_cached_season(cache_key, episodes.clone());
+        Ok(episodes)
     }
 
     pub async fn fetch_detail_popup_data(
@@ -576,9 +1141,10 @@ impl TmdbClient {
             serde_json::from_value(json.clone()).map_err(|e| ApiError::Parse(e.to_string()))?;
 
         let mut item = MediaItem::from(result);
-        populate_media_item(&mut item, &json, media_type);
+        populate_media_item(&mut item, &json, media_type, &self.region);
 
         let cast = parse_credits(&json);
+        let crew = parse_crew(&json);
         let mut external_ids = parse_external_ids(&json);
         external_ids.homepage = json
             .get("homepage")
@@ -590,6 +1156,7 @@ impl TmdbClient {
         let similar_raw = parse_recommendations(&json);
         let production_companies = parse_production_companies(&json);
         let seasons = parse_seasons(&json);
+        let videos = parse_videos(&json);
 
         let similar = self.fetch_similar_with_logos(&similar_raw).await;
 
@@ -602,12 +1169,14 @@ impl TmdbClient {
         let data = DetailPopupData {
             media_item: item,
             cast,
+            crew,
             collection,
             similar,
             external_ids,
             keywords,
             production_companies,
             seasons,
+            videos,
         };
 
         self.set_cached_popup(cache_key, data.clone());
@@ -615,6 +1184,48 @@ impl TmdbClient {
     }
 }
 
+/// `/discover` query parameters for the filters. Movies and series name
+/// their date fields differently.
+fn discover_params(media_type: &str, filters: &SearchFilters) -> String {
+    let (year_param, date_field) = match media_type {
+        "tv" => ("first_air_date_year", "first_air_date"),
+        _ => ("primary_release_year", "primary_release_date"),
+    };
+    let sort_by = match filters.sort_by {
+        SortOption::Popularity => String::from("popularity.desc"),
+        SortOption::Rating => String::from("vote_average.desc"),
+        SortOption::ReleaseDate => format!("{}.desc", date_field),
+        SortOption::Alphabetical if media_type == "tv" => String::from("name.asc"),
+        SortOption::Alphabetical => String::from("title.asc"),
+    };
+    let mut params = vec![format!("sort_by={}", sort_by)];
+    if !filters.genre_ids.is_empty() {
+        let separator = match filters.genre_match {
+            GenreMatch::Any => "|",
+            GenreMatch::All => ",",
+        };
+        let ids: Vec<String> = filters.genre_ids.iter().map(u64::to_string).collect();
+        params.push(format!("with_genres={}", url_encode(&ids.join(separator))));
+    }
+    match filters.year_range() {
+        (Some(from), Some(to)) if from == to => params.push(format!("{}={}", year_param, from)),
+        (from, to) => {
+            if let Some(from) = from {
+                params.push(format!("{}.gte={}-01-01", date_field, from));
+            }
+            if let Some(to) = to {
+                params.push(format!("{}.lte={}-12-31", date_field, to));
+            }
+        }
+    }
+    if filters.min_rating > 0.0 {
+        params.push(format!("vote_average.gte={:.1}", filters.min_rating));
+        // Unrated titles report a perfect average from a single vote.
+        params.push(String::from("vote_count.gte=10"));
+    }
+    params.join("&")
+}
+
 fn media_type_path(media_type: &MediaType) -> &'static str {
     match media_type {
         MediaType::Movie => "movie",
@@ -622,39 +1233,83 @@ fn media_type_path(media_type: &MediaType) -> &'static str {
     }
 }
 
+fn episode_run_times(json: &serde_json::Value) -> Vec<u32> {
+    json.get("episode_run_time")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_u64())
+                .filter(|&v| v > 0)
+                .map(|v| v as u32)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// For series this is the average episode length, since TMDB lists one entry
+/// per distinct length.
 fn extract_runtime(json: &serde_json::Value, media_type: &MediaType) -> Option<u32> {
     match media_type {
         MediaType::Movie => json.get("runtime")?.as_u64().map(|v| v as u32),
-        MediaType::TvSeries => json
-            .get("episode_run_time")?
-            .as_array()?
-            .first()?
-            .as_u64()
-            .map(|v| v as u32),
+        MediaType::TvSeries => {
+            let times = episode_run_times(json);
+            if times.is_empty() {
+                return None;
+            }
+            let total: u32 = times.iter().sum();
+            Some((total as f32 / times.len() as f32).round() as u32)
+        }
     }
 }
 
-fn extract_certification(json: &serde_json::Value, media_type: &MediaType) -> Option<String> {
-    let (key, field) = match media_type {
-        MediaType::Movie => ("release_dates", "certification"),
-        MediaType::TvSeries => ("content_ratings", "rating"),
-    };
+fn extract_runtime_range(json: &serde_json::Value, media_type: &MediaType) -> Option<(u32, u32)> {
+    if !matches!(media_type, MediaType::TvSeries) {
+        return None;
+    }
+    let times = episode_run_times(json);
+    let min = *times.iter().min()?;
+    let max = *times.iter().max()?;
+    (min < max).then_some((min, max))
+}
 
+/// Prefers the rating for `region`, then the US one, then whichever country has one.
+fn extract_certification(
+    json: &serde_json::Value,
+    media_type: &MediaType,
+    region: &str,
+) -> Option<String> {
+    let key = match media_type {
+        MediaType::Movie => "release_dates",
+        MediaType::TvSeries => "content_ratings",
+    };
     let results = json.get(key)?.get("results")?.as_array()?;
-    let us_entry = results
-        .iter()
-        .find(|r| r.get("iso_3166_1").and_then(|v| v.as_str()) == Some("US"))?;
-
-    let cert = match media_type {
-        MediaType::Movie => us_entry
-            .get("release_dates")?
-            .as_array()?
-            .first()?
-            .get(field)?,
-        MediaType::TvSeries => us_entry.get(field)?,
+
+    let certification_of = |entry: &serde_json::Value| -> Option<String> {
+        let cert = match media_type {
+            MediaType::Movie => entry
+                .get("release_dates")?
+                .as_array()?
+                .iter()
+                .filter_map(|r| r.get("certification")?.as_str())
+                .find(|s| !s.is_empty())?,
+            MediaType::TvSeries => entry.get("rating")?.as_str()?,
+        };
+        (!cert.is_empty()).then(|| cert.to_string())
+    };
+    let for_country = |country: &str| {
+        results
+            .iter()
+            .find(|r| {
+                r.get("iso_3166_1")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|c| c.eq_ignore_ascii_case(country))
+            })
+            .and_then(certification_of)
     };
 
-    cert.as_str().filter(|s| !s.is_empty()).map(String::from)
+    for_country(region)
+        .or_else(|| for_country("US"))
+        .or_else(|| results.iter().find_map(certification_of))
 }
 
 fn extract_logo_path(json: &serde_json::Value) -> Option<String> {
@@ -668,9 +1323,15 @@ fn extract_logo_path(json: &serde_json::Value) -> Option<String> {
         .map(String::from)
 }
 
-fn populate_media_item(item: &mut MediaItem, json: &serde_json::Value, media_type: &MediaType) {
+fn populate_media_item(
+    item: &mut MediaItem,
+    json: &serde_json::Value,
+    media_type: &MediaType,
+    region: &str,
+) {
     item.runtime = extract_runtime(json, media_type);
-    item.certification = extract_certification(json, media_type);
+    item.runtime_range = extract_runtime_range(json, media_type);
+    item.certification = extract_certification(json, media_type, region);
     item.logo_path = extract_logo_path(json);
     item.tagline = json
         .get("tagline")
@@ -685,6 +1346,8 @@ fn populate_media_item(item: &mut MediaItem, json: &serde_json::Value, media_typ
         .get("original_language")
         .and_then(|v| v.as_str())
         .map(String::from);
+    item.production_countries = parse_production_countries(json);
+    item.spoken_languages = parse_spoken_languages(json);
     item.budget = json.get("budget").and_then(|v| v.as_u64());
     item.revenue = json.get("revenue").and_then(|v| v.as_u64());
     item.number_of_episodes = json
@@ -703,6 +1366,10 @@ fn populate_media_item(item: &mut MediaItem, json: &serde_json::Value, media_typ
                 Some(Genre {
                     id: g.get("id")?.as_u64()?,
                     name: g.get("name")?.as_str()?.to_string(),
+                    media_scope: match media_type {
+                        MediaType::Movie => GenreScope::Movie,
+                        MediaType::TvSeries => GenreScope::Tv,
+                    },
                 })
             })
             .collect();
@@ -740,6 +1407,40 @@ fn parse_credits(json: &serde_json::Value) -> Vec<CastMember> {
         .unwrap_or_default()
 }
 
+fn parse_crew(json: &serde_json::Value) -> Vec<CrewMember> {
+    let parse_member = |c: &serde_json::Value, job: Option<&str>| {
+        Some(CrewMember {
+            id: c.get("id")?.as_u64()?,
+            name: c.get("name")?.as_str()?.to_string(),
+            job: match job {
+                Some(job) => job.to_string(),
+                None => c.get("job")?.as_str()?.to_string(),
+            },
+            profile_path: c
+                .get("profile_path")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        })
+    };
+
+    let creators = json
+        .get("created_by")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|c| parse_member(c, Some("Creator")));
+
+    let crew = json
+        .get("credits")
+        .and_then(|c| c.get("crew"))
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|c| parse_member(c, None));
+
+    creators.chain(crew).collect()
+}
+
 fn parse_external_ids(json: &serde_json::Value) -> ExternalIds {
     json.get("external_ids")
         .map(|ids| ExternalIds {
@@ -783,6 +1484,19 @@ fn parse_keywords(json: &serde_json::Value) -> Vec<Keyword> {
     .unwrap_or_default()
 }
 
+fn parse_videos(json: &serde_json::Value) -> Vec<TrailerVideo> {
+    json.get("videos")
+        .and_then(|v| v.get("results"))
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| serde_json::from_value::<TrailerVideo>(v.clone()).ok())
+                .filter(|v| v.site == "YouTube")
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn parse_recommendations(json: &serde_json::Value) -> Vec<MediaItem> {
     json.get("recommendations")
         .and_then(|s| s.get("results"))
@@ -802,6 +1516,28 @@ fn parse_recommendations(json: &serde_json::Value) -> Vec<MediaItem> {
         .unwrap_or_default()
 }
 
+/// Cast credits, newest first. A person can be credited several times on the
+/// same title (e.g. multiple TV roles), so duplicates are dropped.
+fn parse_filmography(json: &serde_json::Value) -> Vec<MediaItem> {
+    let mut items: Vec<MediaItem> = json
+        .get("combined_credits")
+        .and_then(|c| c.get("cast"))
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|item| serde_json::from_value::<TmdbMediaResult>(item.clone()).ok())
+                .map(MediaItem::from)
+                .filter(|item| item.backdrop_path.is_some())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut seen = std::collections::HashSet::new();
+    items.retain(|item| seen.insert((item.id, media_type_path(&item.media_type))));
+    items.sort_by(|a, b| b.release_date.cmp(&a.release_date));
+    items
+}
+
 fn parse_production_companies(json: &serde_json::Value) -> Vec<ProductionCompany> {
     json.get("production_companies")
         .and_then(|v| v.as_array())
@@ -827,6 +1563,34 @@ fn parse_production_companies(json: &serde_json::Value) -> Vec<ProductionCompany
         .unwrap_or_default()
 }
 
+fn parse_production_countries(json: &serde_json::Value) -> Vec<String> {
+    json.get("production_countries")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|c| Some(c.get("name")?.as_str()?.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_spoken_languages(json: &serde_json::Value) -> Vec<String> {
+    json.get("spoken_languages")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|l| {
+                    l.get("english_name")
+                        .or_else(|| l.get("name"))
+                        .and_then(|v| v.as_str())
+                        .filter(|s| !s.is_empty())
+                        .map(String::from)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn parse_seasons(json: &serde_json::Value) -> Vec<Season> {
     json.get("seasons")
         .and_then(|v| v.as_array())
@@ -851,54 +1615,378 @@ fn parse_seasons(json: &serde_json::Value) -> Vec<Season> {
         .unwrap_or_default()
 }
 
-pub async fn load_initial_content(client: TmdbClient) -> Result<Vec<ContentSection>, ApiError> {
-    let trending = client.fetch_trending().await?;
-    let top_movies = client.fetch_top_rated_movies().await?;
-    let top_series = client.fetch_top_rated_series().await?;
-    let action = client.fetch_by_genre(28, "movie").await?;
-    let comedy = client.fetch_by_genre(35, "movie").await?;
-
-    Ok(vec![
-        ContentSection {
-            title: String::from("Top Picks"),
-            category: Category::Trending,
-            items: trending,
-        },
-        ContentSection {
-            title: String::from("Most Recent"),
-            category: Category::TopRated,
-            items: top_movies,
-        },
-        ContentSection {
-            title: String::from("Action Movies"),
-            category: Category::Action,
-            items: action,
-        },
-        ContentSection {
-            title: String::from("Series"),
-            category: Category::Series,
-            items: top_series,
-        },
-        ContentSection {
-            title: String::from("Recommended"),
-            category: Category::Recommended,
-            items: comedy,
-        },
-    ])
-}
-
-pub async fn load_hero_content(client: TmdbClient) -> Result<MediaItem, ApiError> {
+/// Titles from the playback progress store, most recently watched first.
+pub struct WatchHistory {
+    pub watched: Vec<MediaId>,
+    /// Started but unfinished titles.
+    pub in_progress: Vec<MediaId>,
+}
+
+/// Sources built from watch history fall back to trending while there is
+/// none, so the first row is never empty.
+async fn load_top_picks(
+    client: &TmdbClient,
+    source: TopPicksSource,
+    history: &WatchHistory,
+) -> Result<Vec<MediaItem>, ApiError> {
+    let watched = &history.watched;
+    let items = match source {
+        TopPicksSource::Trending => return client.fetch_trending().await,
+        TopPicksSource::Genre(genre_id) => client.fetch_by_genre(genre_id, "movie").await?,
+        TopPicksSource::Recommended => {
+            let mut items: Vec<MediaItem> = Vec::new();
+            for &id in watched.iter().take(MAX_TOP_PICKS_SEEDS) {
+                let Ok(recommendations) = client.fetch_recommendations(id, &MediaType::Movie).await
+                else {
+                    continue;
+                };
+                for item in recommendations {
+                    if !watched.contains(&item.id) && !items.iter().any(|i| i.id == item.id) {
+                        items.push(item);
+                    }
+                }
+            }
+            items
+        }
+        TopPicksSource::ContinueWatching => load_continue_watching(client, history).await,
+    };
+    if items.is_empty() {
+        return client.fetch_trending().await;
+    }
+    Ok(items)
+}
+
+pub async fn load_section(
+    client: &TmdbClient,
+    query: &SectionQuery,
+    region: &str,
+    history: &WatchHistory,
+) -> Result<Vec<MediaItem>, ApiError> {
+    match query {
+        SectionQuery::ContinueWatching => Ok(load_continue_watching(client, history).await),
+        SectionQuery::TopPicks(source) => load_top_picks(client, *source, history).await,
+        SectionQuery::TopRatedMovies => client.fetch_top_rated_movies().await,
+        SectionQuery::TopRatedSeries => client.fetch_top_rated_series().await,
+        SectionQuery::NowPlaying => client.fetch_now_playing().await,
+        SectionQuery::Upcoming => client.fetch_upcoming().await,
+        SectionQuery::Genre(genre_id) => client.fetch_by_genre(*genre_id, "movie").await,
+        SectionQuery::Provider(provider_id) => {
+            client.discover_by_provider(*provider_id, region).await
+        }
+    }
+}
+
+/// Episodes are kept out of the watch history, so every id in progress is a movie.
+async fn load_continue_watching(client: &TmdbClient, history: &WatchHistory) -> Vec<MediaItem> {
+    let mut items = Vec::new();
+    for &id in history.in_progress.iter().take(MAX_CONTINUE_WATCHING) {
+        if let Ok(item) = client.fetch_movie_details(id).await {
+            items.push(item);
+        }
+    }
+    items
+}
+
+pub async fn load_initial_content(
+    client: TmdbClient,
+    providers: Vec<WatchProvider>,
+    region: String,
+    top_picks: TopPicksSource,
+    history: WatchHistory,
+) -> Result<Vec<ContentSection>, ApiError> {
+    let mut sections = Vec::new();
+    let continue_watching = load_continue_watching(&client, &history).await;
+    if !continue_watching.is_empty() {
+        sections.push(ContentSection {
+            title: String::from("Continue Watching"),
+            category: Category::ContinueWatching,
+            items: continue_watching,
+            query: Some(SectionQuery::ContinueWatching),
+            error: None,
+            loading: false,
+        });
+    }
+
+    let core_rows = [
+        (
+            "Top Picks",
+            Category::Trending,
+            SectionQuery::TopPicks(top_picks),
+        ),
+        (
+            "Most Recent",
+            Category::TopRated,
+            SectionQuery::TopRatedMovies,
+        ),
+        (
+            "In Theaters",
+            Category::NowPlaying,
+            SectionQuery::NowPlaying,
+        ),
+        ("Coming Soon", Category::Upcoming, SectionQuery::Upcoming),
+        ("Action Movies", Category::Action, SectionQuery::Genre(28)),
+        ("Series", Category::Series, SectionQuery::TopRatedSeries),
+        (
+            "Recommended",
+            Category::Recommended,
+            SectionQuery::Genre(35),
+        ),
+    ];
+    // A failed row is kept with its error so it can be retried on its own;
+    // only when every row fails does the whole load fail.
+    let row_count = core_rows.len();
+    debug_assert_eq!(row_count + 1, BUILT_IN_SECTIONS);
+    let mut failures = 0;
+    let mut last_error = None;
+    for (title, category, query) in core_rows {
+        let (items, error) = match load_section(&client, &query, &region, &history).await {
+            Ok(items) => (items, None),
+            Err(e) => {
+                failures += 1;
+                let message = format!("{:?}", e);
+                last_error = Some(e);
+                (Vec::new(), Some(message))
+            }
+        };
+        sections.push(ContentSection {
+            title: String::from(title),
+            category,
+            items,
+            query: Some(query),
+            error,
+            loading: false,
+        });
+    }
+    if failures == row_count {
+        if let Some(e) = last_error {
+            return Err(e);
+        }
+    }
+
+    // A provider row that fails or comes back empty is skipped rather than
+    // failing the whole home page.
+    for provider in providers.into_iter().take(MAX_PROVIDER_SECTIONS) {
+        match client.discover_by_provider(provider.id, &region).await {
+            Ok(items) if !items.is_empty() => sections.push(ContentSection {
+                title: format!("On {}", provider.name),
+                category: Category::StreamingProvider(provider.id),
+                items,
+                query: Some(SectionQuery::Provider(provider.id)),
+                error: None,
+                loading: false,
+            }),
+            _ => {}
+        }
+    }
+
+    Ok(sections)
+}
+
+pub async fn load_hero_content(client: TmdbClient) -> Result<Vec<MediaItem>, ApiError> {
     let trending = client.fetch_trending().await?;
-    let hero = trending
+    let candidates: Vec<MediaItem> = trending
         .into_iter()
-        .find(|item| item.backdrop_path.is_some())
-        .ok_or_else(|| ApiError::Parse(String::from("No featured content available")))?;
+        .filter(|item| item.backdrop_path.is_some())
+        .take(HERO_ROTATION_SIZE)
+        .collect();
 
-    client
-        .fetch_full_media_details(hero.id, &hero.media_type)
-        .await
+    // Fetched together so the first hero doesn't wait on every round trip.
+    let details = futures::future::join_all(
+        candidates
+            .iter()
+            .map(|candidate| client.fetch_full_media_details(candidate.id, &candidate.media_type)),
+    )
+    .await;
+    let mut heroes = Vec::with_capacity(details.len());
+    let mut last_error = None;
+    for result in details {
+        match result {
+            Ok(item) => heroes.push(item),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    if heroes.is_empty() {
+        return Err(last_error
+            .unwrap_or_else(|| ApiError::Parse(String::from("No featured content available"))));
+    }
+    Ok(heroes)
+}
+
+/// Alternates between the two lists, then appends what's left of the longer one.
+fn interleave(movies: Vec<MediaItem>, series: Vec<MediaItem>) -> Vec<MediaItem> {
+    let mut items = Vec::with_capacity(movies.len() + series.len());
+    let mut movies = movies.into_iter();
+    let mut series = series.into_iter();
+    loop {
+        match (movies.next(), series.next()) {
+            (None, None) => break,
+            (movie, show) => items.extend(movie.into_iter().chain(show)),
+        }
+    }
+    items
 }
 
 pub async fn load_genres(client: TmdbClient) -> Result<Vec<Genre>, ApiError> {
     client.fetch_genres().await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A client whose requests can never succeed, so any result it returns
+    /// must have come from a cache.
+    fn offline_client() -> TmdbClient {
+        let mut client = TmdbClient::new(
+            String::from("key"),
+            String::from("en-US"),
+            String::from("US"),
+            "movix-test",
+            None,
+        );
+        client.base_url = String::from("http://127.0.0.1:9");
+        client
+    }
+
+    fn episode(id: u64, episode_number: u32) -> Episode {
+        Episode {
+            id,
+            episode_number,
+            season_number: 1,
+            name: format!("Episode {}", episode_number),
+            overview: String::new(),
+            air_date: None,
+            still_path: None,
+            runtime: Some(42),
+            vote_average: 0.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn reselecting_a_season_is_served_from_cache() {
+        let client = offline_client();
+        client.set_cached_season(
+            String::from("season_10_1"),
+            vec![episode(1, 1), episode(2, 2)],
+        );
+
+        for _ in 0..2 {
+            let episodes = client.fetch_season_episodes(10, 1).await.unwrap();
+            let ids: Vec<u64> = episodes.iter().map(|e| e.id).collect();
+            assert_eq!(ids, vec![1, 2]);
+        }
+        assert!(client.fetch_season_episodes(10, 2).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn expired_seasons_are_fetched_again() {
+        let mut client = offline_client();
+        client.cache_ttl = Duration::ZERO;
+        client.set_cached_season(String::from("season_10_1"), vec![episode(1, 1)]);
+
+        assert!(client.fetch_season_episodes(10, 1).await.is_err());
+    }
+
+    #[test]
+    fn varying_episode_lengths_give_an_average_and_a_range() {
+        let json = serde_json::json!({ "episode_run_time": [30, 60, 45] });
+        assert_eq!(extract_runtime(&json, &MediaType::TvSeries), Some(45));
+        assert_eq!(
+            extract_runtime_range(&json, &MediaType::TvSeries),
+            Some((30, 60))
+        );
+    }
+
+    #[test]
+    fn single_or_missing_episode_length_has_no_range() {
+        let single = serde_json::json!({ "episode_run_time": [42] });
+        assert_eq!(extract_runtime(&single, &MediaType::TvSeries), Some(42));
+        assert_eq!(extract_runtime_range(&single, &MediaType::TvSeries), None);
+
+        let empty = serde_json::json!({ "episode_run_time": [] });
+        assert_eq!(extract_runtime(&empty, &MediaType::TvSeries), None);
+        assert_eq!(extract_runtime_range(&empty, &MediaType::TvSeries), None);
+    }
+
+    fn release_dates() -> serde_json::Value {
+        serde_json::json!({ "release_dates": { "results": [
+            { "iso_3166_1": "US", "release_dates": [{ "certification": "R" }] },
+            { "iso_3166_1": "DE", "release_dates": [
+                { "certification": "" },
+                { "certification": "16" }
+            ] },
+            { "iso_3166_1": "FR", "release_dates": [{ "certification": "" }] }
+        ] } })
+    }
+
+    #[test]
+    fn certification_prefers_the_configured_region() {
+        let json = release_dates();
+        assert_eq!(
+            extract_certification(&json, &MediaType::Movie, "DE").as_deref(),
+            Some("16")
+        );
+        assert_eq!(
+            extract_certification(&json, &MediaType::Movie, "de").as_deref(),
+            Some("16")
+        );
+    }
+
+    #[test]
+    fn certification_falls_back_to_the_us_rating() {
+        let json = release_dates();
+        assert_eq!(
+            extract_certification(&json, &MediaType::Movie, "FR").as_deref(),
+            Some("R")
+        );
+    }
+
+    #[test]
+    fn tv_certification_reads_content_ratings() {
+        let json = serde_json::json!({ "content_ratings": { "results": [
+            { "iso_3166_1": "GB", "rating": "15" },
+            { "iso_3166_1": "US", "rating": "TV-MA" }
+        ] } });
+        assert_eq!(
+            extract_certification(&json, &MediaType::TvSeries, "GB").as_deref(),
+            Some("15")
+        );
+        assert_eq!(
+            extract_certification(&json, &MediaType::TvSeries, "JP").as_deref(),
+            Some("TV-MA")
+        );
+    }
+
+    fn titles(items: &[MediaItem]) -> Vec<&str> {
+        items.iter().map(|i| i.title.as_str()).collect()
+    }
+
+    fn list(titles: &[&str]) -> Vec<MediaItem> {
+        titles
+            .iter()
+            .enumerate()
+            .map(|(i, title)| {
+                let result: TmdbMediaResult =
+                    serde_json::from_value(serde_json::json!({ "id": i, "title": title })).unwrap();
+                MediaItem::from(result)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn popular_lists_are_interleaved_movie_first() {
+        let items = interleave(list(&["m1", "m2", "m3"]), list(&["s1"]));
+        assert_eq!(titles(&items), vec!["m1", "s1", "m2", "m3"]);
+    }
+
+    #[tokio::test]
+    async fn popular_titles_are_served_from_their_own_cache_entry() {
+        let client = offline_client();
+        assert!(client.fetch_popular().await.is_err());
+
+        client.set_cached_list(String::from("popular"), list(&["m1", "s1"]));
+        let items = client.fetch_popular().await.unwrap();
+        assert_eq!(titles(&items), vec!["m1", "s1"]);
+    }
+}
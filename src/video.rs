@@ -6,11 +6,13 @@ use std::thread;
 
 use iced::Task;
 use rodio::Sink;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tokio::process::Command;
 use tokio::sync::RwLock;
 
+use crate::audio::{create_audio_output, create_resampler, frame_source, AudioConfig};
 use crate::media::{ContentSection, MediaId, MediaType, Message};
+use crate::proxy;
 use crate::tmdb::ImageSize;
 use crate::Movix;
 
@@ -37,6 +39,37 @@ fn get_ytdlp_path() -> String {
     return "yt-dlp".to_string();
 }
 
+const DECODER_JOIN_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+/// Consecutive failed packet reads after which a decoder gives up on the
+/// stream instead of retrying forever.
+pub const MAX_PACKET_READ_ERRORS: u32 = 50;
+pub const PACKET_READ_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(20);
+
+/// Waits briefly for a stopped decoder to exit. A decoder still stuck in a
+/// blocking read is handed back so it can be joined once it notices shutdown.
+pub fn join_decoder_thread(handle: thread::JoinHandle<()>) -> Option<thread::JoinHandle<()>> {
+    let deadline = std::time::Instant::now() + DECODER_JOIN_TIMEOUT;
+    while !handle.is_finished() {
+        if std::time::Instant::now() >= deadline {
+            return Some(handle);
+        }
+        thread::sleep(std::time::Duration::from_millis(5));
+    }
+    let _ = handle.join();
+    None
+}
+
+/// Joins the stopped decoders that have exited since.
+pub fn reap_decoder_threads(threads: &mut Vec<thread::JoinHandle<()>>) {
+    let (finished, running): (Vec<_>, Vec<_>) = std::mem::take(threads)
+        .into_iter()
+        .partition(|handle| handle.is_finished());
+    for handle in finished {
+        let _ = handle.join();
+    }
+    *threads = running;
+}
+
 #[derive(Clone)]
 pub struct FrameData {
     pub width: u32,
@@ -44,29 +77,109 @@ pub struct FrameData {
     pub data: Vec<u8>,
 }
 
+/// Small enough for the 400x225 expanded card while keeping decode cheap.
+pub const CARD_TRAILER_SIZE: (u32, u32) = (640, 360);
+/// The detail mini-hero spans most of the popup, so it decodes sharper.
+pub const DETAIL_TRAILER_SIZE: (u32, u32) = (1280, 720);
+
+const AUDIO_FADE_DURATION: std::time::Duration = std::time::Duration::from_millis(300);
+
 enum PlayerCommand {
     Pause,
     Resume,
-    ToggleMute,
+    FadeTo(f32, std::time::Duration),
     Shutdown,
 }
 
+struct AudioFader {
+    from: f32,
+    to: f32,
+    started: std::time::Instant,
+    duration: std::time::Duration,
+    active: bool,
+    pause_when_done: bool,
+}
+
+impl AudioFader {
+    fn new() -> Self {
+        Self {
+            from: 1.0,
+            to: 1.0,
+            started: std::time::Instant::now(),
+            duration: std::time::Duration::ZERO,
+            active: false,
+            pause_when_done: false,
+        }
+    }
+
+    fn fade_to(&mut self, sink: &Sink, volume: f32, duration: std::time::Duration) {
+        self.from = sink.volume();
+        self.to = volume;
+        self.started = std::time::Instant::now();
+        self.duration = duration;
+        self.active = true;
+    }
+
+    fn pause(&mut self, sink: &Sink) {
+        if self.active {
+            self.pause_when_done = true;
+        } else {
+            sink.pause();
+        }
+    }
+
+    fn resume(&mut self, sink: &Sink) {
+        self.pause_when_done = false;
+        sink.play();
+    }
+
+    fn tick(&mut self, sink: &Sink) {
+        if !self.active {
+            return;
+        }
+        let t = if self.duration.is_zero() {
+            1.0
+        } else {
+            (self.started.elapsed().as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        };
+        sink.set_volume(self.from + (self.to - self.from) * t);
+        if t >= 1.0 {
+            self.active = false;
+            if self.pause_when_done {
+                self.pause_when_done = false;
+                sink.pause();
+            }
+        }
+    }
+
+    fn poll_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(if self.active { 10 } else { 50 })
+    }
+}
+
 pub struct VideoPlayer {
     current_media_id: Option<MediaId>,
     current_frame: Option<FrameData>,
     frame_receiver: Option<crossbeam_channel::Receiver<FrameData>>,
     command_sender: Option<crossbeam_channel::Sender<PlayerCommand>>,
     decoder_thread: Option<thread::JoinHandle<()>>,
+    /// Stopped decoders that didn't exit within the join timeout.
+    stopped_threads: Vec<thread::JoinHandle<()>>,
     is_playing: bool,
     is_muted: Arc<AtomicBool>,
     is_ended: Arc<AtomicBool>,
+    shutdown: Arc<AtomicBool>,
     current_url: Option<String>,
+    audio: AudioConfig,
+    proxy_url: Option<String>,
+    buffer_depth: usize,
     target_width: u32,
     target_height: u32,
+    volume: f32,
 }
 
 impl VideoPlayer {
-    pub fn new() -> Result<Self, String> {
+    pub fn new((target_width, target_height): (u32, u32)) -> Result<Self, String> {
         ffmpeg_next::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
         Ok(Self {
             current_media_id: None,
@@ -74,29 +187,55 @@ impl VideoPlayer {
             frame_receiver: None,
             command_sender: None,
             decoder_thread: None,
+            stopped_threads: Vec::new(),
             is_playing: false,
             is_muted: Arc::new(AtomicBool::new(false)),
             is_ended: Arc::new(AtomicBool::new(false)),
+            shutdown: Arc::new(AtomicBool::new(false)),
             current_url: None,
-            target_width: 640,
-            target_height: 360,
+            audio: AudioConfig::default(),
+            proxy_url: None,
+            buffer_depth: 4,
+            target_width,
+            target_height,
+            volume: 1.0,
         })
     }
 
-    pub fn play(&mut self, media_id: MediaId, url: &str) -> Result<(), String> {
+    /// Takes effect from the next `play`.
+    pub fn set_target_size(&mut self, (width, height): (u32, u32)) {
+        self.target_width = width;
+        self.target_height = height;
+    }
+
+    pub fn play(
+        &mut self,
+        media_id: MediaId,
+        url: &str,
+        buffer_depth: usize,
+    ) -> Result<(), String> {
         self.stop();
-        let (frame_tx, frame_rx) = crossbeam_channel::bounded(4);
+        let (frame_tx, frame_rx) = crossbeam_channel::bounded(buffer_depth);
         let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
         let url_clone = url.to_string();
         let width = self.target_width;
         let height = self.target_height;
         let is_muted = self.is_muted.clone();
+        self.is_ended = Arc::new(AtomicBool::new(false));
         let is_ended = self.is_ended.clone();
-        is_ended.store(false, Ordering::SeqCst);
+        self.shutdown = Arc::new(AtomicBool::new(false));
+        let shutdown = self.shutdown.clone();
+        let audio = self.audio.clone();
+        let proxy_url = self.proxy_url.clone();
+        let _ = cmd_tx.send(PlayerCommand::FadeTo(
+            self.target_volume(),
+            std::time::Duration::ZERO,
+        ));
 
         let handle = thread::spawn(move || {
             run_decoder(
-                url_clone, width, height, frame_tx, cmd_rx, is_muted, is_ended,
+                url_clone, width, height, frame_tx, cmd_rx, is_muted, is_ended, shutdown, audio,
+                proxy_url,
             );
         });
 
@@ -105,18 +244,21 @@ impl VideoPlayer {
         self.decoder_thread = Some(handle);
         self.current_media_id = Some(media_id);
         self.current_url = Some(url.to_string());
+        self.buffer_depth = buffer_depth;
         self.is_playing = true;
         Ok(())
     }
 
     pub fn stop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
         if let Some(sender) = self.command_sender.take() {
             let _ = sender.send(PlayerCommand::Shutdown);
         }
+        self.frame_receiver = None;
         if let Some(handle) = self.decoder_thread.take() {
-            let _ = handle.join();
+            self.stopped_threads.extend(join_decoder_thread(handle));
         }
-        self.frame_receiver = None;
+        reap_decoder_threads(&mut self.stopped_threads);
         self.current_media_id = None;
         self.current_url = None;
         self.is_playing = false;
@@ -125,6 +267,7 @@ impl VideoPlayer {
 
     pub fn pause(&mut self) {
         if let Some(ref sender) = self.command_sender {
+            let _ = sender.send(PlayerCommand::FadeTo(0.0, AUDIO_FADE_DURATION));
             let _ = sender.send(PlayerCommand::Pause);
         }
         self.is_playing = false;
@@ -133,10 +276,32 @@ impl VideoPlayer {
     pub fn resume(&mut self) {
         if let Some(ref sender) = self.command_sender {
             let _ = sender.send(PlayerCommand::Resume);
+            let _ = sender.send(PlayerCommand::FadeTo(
+                self.target_volume(),
+                AUDIO_FADE_DURATION,
+            ));
         }
         self.is_playing = true;
     }
 
+    fn target_volume(&self) -> f32 {
+        if self.is_muted.load(Ordering::SeqCst) {
+            0.0
+        } else {
+            self.volume
+        }
+    }
+
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume.clamp(0.0, 1.0);
+        if let Some(ref sender) = self.command_sender {
+            let _ = sender.send(PlayerCommand::FadeTo(
+                self.target_volume(),
+                AUDIO_FADE_DURATION,
+            ));
+        }
+    }
+
     pub fn is_playing(&self) -> bool {
         self.is_playing
     }
@@ -149,11 +314,20 @@ impl VideoPlayer {
         self.current_media_id
     }
 
+    pub fn set_muted(&mut self, muted: bool) {
+        if self.is_muted.load(Ordering::SeqCst) != muted {
+            self.toggle_mute();
+        }
+    }
+
     pub fn toggle_mute(&mut self) {
         let current = self.is_muted.load(Ordering::SeqCst);
         self.is_muted.store(!current, Ordering::SeqCst);
         if let Some(ref sender) = self.command_sender {
-            let _ = sender.send(PlayerCommand::ToggleMute);
+            let _ = sender.send(PlayerCommand::FadeTo(
+                self.target_volume(),
+                AUDIO_FADE_DURATION,
+            ));
         }
     }
 
@@ -161,6 +335,16 @@ impl VideoPlayer {
         self.is_muted.load(Ordering::SeqCst)
     }
 
+    /// Takes effect from the next `play`.
+    pub fn set_audio(&mut self, audio: AudioConfig) {
+        self.audio = audio;
+    }
+
+    /// Takes effect from the next `play`.
+    pub fn set_proxy(&mut self, proxy_url: Option<&str>) {
+        self.proxy_url = proxy_url.map(String::from);
+    }
+
     pub fn check_ended(&mut self) -> bool {
         if self.is_ended.load(Ordering::SeqCst) {
             self.is_playing = false;
@@ -172,7 +356,7 @@ impl VideoPlayer {
     pub fn replay(&mut self) -> Result<(), String> {
         let media_id = self.current_media_id.ok_or("No media to replay")?;
         let url = self.current_url.clone().ok_or("No URL to replay")?;
-        self.play(media_id, &url)
+        self.play(media_id, &url, self.buffer_depth)
     }
 
     pub fn get_frame(&self) -> Option<FrameData> {
@@ -195,6 +379,7 @@ impl Drop for VideoPlayer {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_decoder(
     url: String,
     target_width: u32,
@@ -203,16 +388,25 @@ fn run_decoder(
     command_receiver: crossbeam_channel::Receiver<PlayerCommand>,
     is_muted: Arc<AtomicBool>,
     is_ended: Arc<AtomicBool>,
+    shutdown: Arc<AtomicBool>,
+    audio: AudioConfig,
+    proxy_url: Option<String>,
 ) {
-    let (_stream, sink) = match create_audio_output() {
+    let (_stream, sink) = match create_audio_output(audio.device.as_deref()) {
         Some(s) => s,
         None => {
             is_ended.store(true, Ordering::SeqCst);
             return;
         }
     };
+    if is_muted.load(Ordering::SeqCst) {
+        sink.set_volume(0.0);
+    }
 
-    let mut ictx = match ffmpeg_next::format::input(&url) {
+    let interrupt = shutdown.clone();
+    let mut ictx = match proxy::open_input(&url, proxy_url.as_deref(), move || {
+        interrupt.load(Ordering::SeqCst)
+    }) {
         Ok(ctx) => ctx,
         Err(_) => {
             is_ended.store(true, Ordering::SeqCst);
@@ -255,55 +449,58 @@ fn run_decoder(
         .ok()
     });
 
-    let mut resampler = audio_decoder.as_ref().and_then(|dec| {
-        ffmpeg_next::software::resampling::Context::get(
-            dec.format(),
-            dec.channel_layout(),
-            dec.rate(),
-            ffmpeg_next::format::Sample::I16(ffmpeg_next::format::sample::Type::Packed),
-            ffmpeg_next::ChannelLayout::STEREO,
-            44100,
-        )
-        .ok()
-    });
+    let mut resampler = audio_decoder.as_ref().and_then(create_resampler);
 
     let playback_start = std::time::Instant::now();
     let mut pause_offset = std::time::Duration::ZERO;
     let mut pause_start: Option<std::time::Instant> = None;
     let mut is_paused = false;
+    let mut fader = AudioFader::new();
+    let mut read_errors = 0;
+
+    loop {
+        let mut packet = ffmpeg_next::Packet::empty();
+        match packet.read(&mut ictx) {
+            Ok(()) => read_errors = 0,
+            Err(ffmpeg_next::Error::Eof) => break,
+            Err(_) if shutdown.load(Ordering::SeqCst) => return,
+            Err(_) => {
+                read_errors += 1;
+                if read_errors >= MAX_PACKET_READ_ERRORS {
+                    is_ended.store(true, Ordering::SeqCst);
+                    return;
+                }
+                thread::sleep(PACKET_READ_RETRY_DELAY);
+                continue;
+            }
+        }
 
-    for (pkt_stream, packet) in ictx.packets() {
         while let Ok(cmd) = command_receiver.try_recv() {
             match cmd {
                 PlayerCommand::Shutdown => return,
                 PlayerCommand::Pause => {
                     is_paused = true;
                     pause_start = Some(std::time::Instant::now());
-                    sink.pause();
+                    fader.pause(&sink);
                 }
                 PlayerCommand::Resume => {
                     is_paused = false;
                     if let Some(ps) = pause_start.take() {
                         pause_offset += ps.elapsed();
                     }
-                    sink.play();
-                }
-                PlayerCommand::ToggleMute => {
-                    sink.set_volume(if is_muted.load(Ordering::SeqCst) {
-                        0.0
-                    } else {
-                        1.0
-                    });
+                    fader.resume(&sink);
                 }
+                PlayerCommand::FadeTo(volume, duration) => fader.fade_to(&sink, volume, duration),
             }
         }
+        fader.tick(&sink);
 
         if is_paused {
-            thread::sleep(std::time::Duration::from_millis(50));
+            thread::sleep(fader.poll_interval());
             continue;
         }
 
-        let stream_index = pkt_stream.index();
+        let stream_index = packet.stream();
 
         if Some(stream_index) == audio_index {
             if let (Some(ref mut decoder), Some(ref mut resamp)) =
@@ -314,13 +511,9 @@ fn run_decoder(
                     while decoder.receive_frame(&mut decoded).is_ok() {
                         let mut resampled = ffmpeg_next::frame::Audio::empty();
                         if resamp.run(&decoded, &mut resampled).is_ok() {
-                            let data = resampled.data(0);
-                            let samples: Vec<f32> = data
-                                .chunks_exact(2)
-                                .map(|c| i16::from_le_bytes([c[0], c[1]]) as f32 / 32768.0)
-                                .collect();
-                            let source = rodio::buffer::SamplesBuffer::new(2, 44100, samples);
-                            sink.append(source);
+                            if let Some(source) = frame_source(&resampled, audio.gain) {
+                                sink.append(source);
+                            }
                         }
                     }
                 }
@@ -362,28 +555,41 @@ fn run_decoder(
         }
     }
 
-    sink.sleep_until_end();
+    while !sink.empty() {
+        match command_receiver.try_recv() {
+            Ok(PlayerCommand::Shutdown) | Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                return
+            }
+            Ok(PlayerCommand::Pause) => fader.pause(&sink),
+            Ok(PlayerCommand::Resume) => fader.resume(&sink),
+            Ok(PlayerCommand::FadeTo(volume, duration)) => fader.fade_to(&sink, volume, duration),
+            Err(crossbeam_channel::TryRecvError::Empty) => {
+                fader.tick(&sink);
+                thread::sleep(fader.poll_interval());
+            }
+        }
+    }
     is_ended.store(true, Ordering::SeqCst);
 }
 
-fn create_audio_output() -> Option<(std::mem::ManuallyDrop<rodio::OutputStream>, Sink)> {
-    let stream = rodio::OutputStreamBuilder::open_default_stream().ok()?;
-    let sink = Sink::connect_new(stream.mixer());
-    Some((std::mem::ManuallyDrop::new(stream), sink))
-}
-
 #[derive(Clone)]
 pub struct TrailerManager {
     url_cache: Arc<RwLock<HashMap<String, String>>>,
+    proxy_url: Option<String>,
 }
 
 impl TrailerManager {
     pub fn new() -> Self {
         Self {
             url_cache: Arc::new(RwLock::new(HashMap::new())),
+            proxy_url: None,
         }
     }
 
+    pub fn set_proxy(&mut self, proxy_url: Option<&str>) {
+        self.proxy_url = proxy_url.map(String::from);
+    }
+
     pub async fn get_stream_url(&self, youtube_id: &str) -> Result<String, String> {
         {
             let cache = self.url_cache.read().await;
@@ -414,6 +620,9 @@ impl TrailerManager {
         ])
         .stdout(Stdio::piped())
         .stderr(Stdio::null());
+        if let Some(ref proxy_url) = self.proxy_url {
+            cmd.args(["--proxy", proxy_url]);
+        }
 
         #[cfg(windows)]
         {
@@ -456,9 +665,11 @@ pub fn select_best_trailer(videos: &[TrailerVideo]) -> Option<&TrailerVideo> {
         })
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrailerVideo {
     pub key: String,
+    #[serde(default)]
+    pub name: String,
     pub site: String,
     #[serde(rename = "type")]
     pub video_type: String,
@@ -495,8 +706,7 @@ impl Movix {
             return Task::none();
         };
         let mut tasks = Vec::new();
-        if let Some(backdrop_path) = &item.backdrop_path {
-            let url = client.image_url(backdrop_path, ImageSize::Backdrop);
+        if let Some(url) = self.backdrop_url(item) {
             if self.image_cache.get(&url).is_none() {
                 tasks.push(Task::done(Message::LoadImage(url)));
             }
@@ -525,20 +735,17 @@ impl Movix {
         };
 
         let mut tasks = Vec::new();
-        if let Some(backdrop_path) = &item.backdrop_path {
-            let url = client.image_url(backdrop_path, ImageSize::Backdrop);
+        if let Some(url) = self.backdrop_url(item) {
             if self.image_cache.get(&url).is_none() && !self.image_cache.is_pending(&url) {
                 tasks.push(Task::done(Message::LoadImage(url)));
             }
         }
 
         if item.logo_path.is_none() {
-            let fetch_client = client.clone();
-            let media_type = item.media_type.clone();
-            tasks.push(Task::perform(
-                async move { fetch_client.fetch_media_images(media_id, &media_type).await },
-                move |result| Message::LogoLoaded(media_id, result),
-            ));
+            tasks.push(Task::done(Message::FetchLogo(
+                media_id,
+                item.media_type.clone(),
+            )));
         } else if let Some(logo_path) = &item.logo_path {
             let url = client.image_url(logo_path, ImageSize::Original);
             if self.image_cache.get(&url).is_none() && !self.image_cache.is_pending(&url) {
@@ -549,34 +756,41 @@ impl Movix {
     }
 
     pub fn load_visible_images(&self, section_index: usize, scroll_offset: f32) -> Task<Message> {
+        let tasks: Vec<Task<Message>> = self
+            .visible_poster_urls(section_index, scroll_offset)
+            .into_iter()
+            .filter(|url| self.image_cache.get(url).is_none() && !self.image_cache.is_pending(url))
+            .map(|url| Task::done(Message::LoadImage(url)))
+            .collect();
+        Task::batch(tasks)
+    }
+
+    /// Poster URLs of the cards in view at `scroll_offset`, plus a couple
+    /// just past the right edge.
+    pub fn visible_poster_urls(&self, section_index: usize, scroll_offset: f32) -> Vec<String> {
         let Some(client) = &self.tmdb_client else {
-            return Task::none();
+            return Vec::new();
         };
         let Some(section) = self.content_sections.get(section_index) else {
-            return Task::none();
+            return Vec::new();
         };
 
         let card_width = 162.0;
         let visible_width = 1200.0;
         let start_index = (scroll_offset / card_width).floor() as usize;
         let visible_count = (visible_width / card_width).ceil() as usize + 2;
-        let end_index = (start_index + visible_count).min(section.items.len());
+        let end_index = (start_index + visible_count)
+            .min(section.items.len())
+            .min(self.settings.section_item_cap);
 
-        let mut tasks = Vec::new();
-        for item in section
+        section
             .items
             .iter()
             .skip(start_index)
-            .take(end_index - start_index)
-        {
-            if let Some(poster_path) = &item.poster_path {
-                let url = client.image_url(poster_path, ImageSize::Poster);
-                if self.image_cache.get(&url).is_none() && !self.image_cache.is_pending(&url) {
-                    tasks.push(Task::done(Message::LoadImage(url)));
-                }
-            }
-        }
-        Task::batch(tasks)
+            .take(end_index.saturating_sub(start_index))
+            .filter_map(|item| item.poster_path.as_ref())
+            .map(|poster_path| client.image_url(poster_path, ImageSize::Poster))
+            .collect()
     }
 
     pub fn load_trailer_for_media(
@@ -584,7 +798,10 @@ impl Movix {
         media_id: MediaId,
         media_type: &MediaType,
     ) -> Task<Message> {
-        if self.trailer_cache.contains_key(&media_id) {
+        if self.is_offline
+            || !self.settings.autoplay_trailers()
+            || self.trailer_cache.contains_key(&media_id)
+        {
             return Task::none();
         }
         let Some(client) = &self.tmdb_client else {
@@ -599,6 +816,9 @@ impl Movix {
     }
 
     pub fn fetch_trailer_stream_url(&self, media_id: MediaId, youtube_id: String) -> Task<Message> {
+        if self.is_offline || !self.settings.autoplay_trailers() {
+            return Task::none();
+        }
         let manager = self.trailer_manager.clone();
         Task::perform(
             async move { manager.get_stream_url(&youtube_id).await },
@@ -607,6 +827,9 @@ impl Movix {
     }
 
     pub fn load_trailer_for_hovered_card(&self, media_id: MediaId) -> Task<Message> {
+        if self.is_offline || !self.settings.autoplay_trailers() {
+            return Task::none();
+        }
         let pause_hero = Task::done(Message::PauseHeroTrailer);
 
         if self.stream_url_cache.contains_key(&media_id) {
@@ -634,10 +857,39 @@ impl Movix {
         Task::batch([pause_hero, load_task])
     }
 
+    /// Resolves the trailer of the hero that rotates in next, so it can start
+    /// playing as soon as it is shown.
+    pub fn preload_next_hero_trailer(&self) -> Task<Message> {
+        if self.is_offline || !self.settings.preload_trailers() || self.hero_items.len() < 2 {
+            return Task::none();
+        }
+        let next = &self.hero_items[(self.hero_index + 1) % self.hero_items.len()];
+        let media_id = next.id;
+        if self.stream_url_cache.contains_key(&media_id) {
+            return Task::none();
+        }
+        match self.trailer_cache.get(&media_id) {
+            Some(Some(youtube_id)) => {
+                let manager = self.trailer_manager.clone();
+                let youtube_id = youtube_id.clone();
+                Task::perform(
+                    async move { manager.get_stream_url(&youtube_id).await },
+                    move |result| Message::TrailerStreamUrlPreloaded(media_id, result),
+                )
+            }
+            Some(None) => Task::none(),
+            // Once the videos are in, the stream URL is preloaded as well.
+            None => self.load_trailer_for_media(media_id, &next.media_type),
+        }
+    }
+
     pub fn preload_trailer_urls(&self, sections: &[ContentSection]) -> Task<Message> {
         let Some(client) = &self.tmdb_client else {
             return Task::none();
         };
+        if self.is_offline || !self.settings.preload_trailers() {
+            return Task::none();
+        }
         let mut tasks = Vec::new();
         for section in sections.iter().take(2) {
             for item in section.items.iter().take(5) {
@@ -0,0 +1,122 @@
+use iced::widget::{button, column, container, text, Column, Space};
+use iced::{Border, Color, Element, Length, Padding, Shadow};
+
+use crate::media::{Message, NETFLIX_RED, SURFACE_DARK_GRAY, TEXT_GRAY, TEXT_WHITE};
+use crate::Movix;
+
+pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Release notes shown once after updating, newest first.
+const CHANGELOG: &[(&str, &[&str])] = &[(
+    "0.2.0",
+    &[
+        "Browse popular people and open their filmography from any cast list",
+        "Add home rows for your streaming services in Settings",
+        "Age ratings and release dates follow your region",
+        "Titles without a stream play their trailer instead",
+        "Report a broken stream to try another source",
+        "Shift+scroll moves card rows, and the wheel changes player volume",
+        "Works offline with your last loaded home page",
+    ],
+)];
+
+pub fn notes_for(version: &str) -> Option<&'static [&'static str]> {
+    CHANGELOG
+        .iter()
+        .find(|(v, _)| *v == version)
+        .map(|(_, notes)| *notes)
+}
+
+/// Upgrades from before the version was tracked have an empty
+/// `last_seen_version`, so they see the notes too.
+pub fn should_show(last_seen_version: &str) -> bool {
+    last_seen_version != APP_VERSION && notes_for(APP_VERSION).is_some()
+}
+
+impl Movix {
+    pub fn view_whats_new_overlay(&self) -> Element<'_, Message> {
+        let notes = notes_for(APP_VERSION).unwrap_or_default();
+        let items: Vec<Element<Message>> = notes
+            .iter()
+            .map(|note| {
+                text(format!("•  {}", note))
+                    .size(15)
+                    .color(TEXT_WHITE)
+                    .into()
+            })
+            .collect();
+
+        let dismiss = button(text("Got it").size(16).color(TEXT_WHITE))
+            .padding(Padding::new(10.0).left(28.0).right(28.0))
+            .style(|_theme, status| button::Style {
+                background: Some(iced::Background::Color(
+                    if matches!(status, button::Status::Hovered) {
+                        Color::from_rgb(0.698, 0.027, 0.063)
+                    } else {
+                        NETFLIX_RED
+                    },
+                )),
+                text_color: TEXT_WHITE,
+                border: Border {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                shadow: Shadow::default(),
+                snap: false,
+            })
+            .on_press(Message::DismissWhatsNew);
+
+        let content = column![
+            text("What's New")
+                .size(28)
+                .color(TEXT_WHITE)
+                .font(iced::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+            text(format!("Movix {}", APP_VERSION))
+                .size(14)
+                .color(TEXT_GRAY),
+            Column::with_children(items).spacing(10),
+            Space::new().height(8),
+            dismiss
+        ]
+        .spacing(16);
+
+        let dialog = container(content)
+            .max_width(520.0)
+            .padding(Padding::new(32.0))
+            .style(|_theme| container::Style {
+                background: Some(iced::Background::Color(SURFACE_DARK_GRAY)),
+                border: Border {
+                    radius: 12.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+        let overlay_bg = iced::widget::mouse_area(
+            container(Space::new().width(Length::Fill).height(Length::Fill))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(|_theme| container::Style {
+                    background: Some(iced::Background::Color(Color::from_rgba(
+                        0.0, 0.0, 0.0, 0.85,
+                    ))),
+                    ..Default::default()
+                }),
+        )
+        .on_press(Message::DismissWhatsNew);
+
+        let centered = container(iced::widget::mouse_area(dialog))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill);
+
+        iced::widget::stack![overlay_bg, centered]
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}